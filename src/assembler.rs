@@ -0,0 +1,918 @@
+//! Assembler and disassembler for TMCL source files.
+//!
+//! The source format follows the TMCL mnemonics:
+//!
+//! ```text
+//! // Move back and forth until input 0 is pulled high
+//! Speed = 500
+//!
+//! Loop:
+//!     ROR 0, Speed
+//!     WAIT TICKS, 0, 100
+//!     ROL 0, Speed
+//!     WAIT TICKS, 0, 100
+//!     GIO 0, 0
+//!     JC ZE, Loop
+//!     MST 0
+//!     STOP
+//! ```
+//!
+//! Every line holds at most one instruction, written as the mnemonic followed by the type,
+//! motor/bank and value operands separated by commas. Operands an instruction doesn't use are
+//! left out, and types are given by name where TMCL defines one (e.g. `ABS` or `NZ`).
+//! Operands are decimal numbers, hexadecimal numbers prefixed by `0x` or `$`, or symbols.
+//!
+//! A line can start with a label (`Name:`), which defines a symbol holding the address of the
+//! next instruction. Constants are defined with `Name = value`. Everything after `//` is a comment.
+//!
+//! ```
+//! extern crate tmcl;
+//!
+//! use tmcl::assembler;
+//!
+//! let program = assembler::assemble("Loop:\n    ROR 0, 500\n    JA Loop\n").unwrap();
+//! assert_eq!(program.instructions().len(), 2);
+//! assert_eq!(program.to_bytes()[8..], [22, 0, 0, 0, 0, 0, 0, 22]);
+//!
+//! let source = assembler::disassemble(0, &program.program_words()).unwrap();
+//! assert_eq!(source, "L0:\n    ROR 0, 500\n    JA L0\n");
+//! ```
+
+use std::collections::HashMap;
+use std::fmt::Write;
+
+use lib::ops::Deref;
+
+use interior_mut::InteriorMut;
+
+use instructions::{
+    ErrorFlag, InterruptVector, JumpCondition, MoveOperation, ReferenceSearchAction, WaitEvent,
+    ACO, CALC, CALCX, CCO, CLE, COMP, CSUB, DI, EI, GCO, GIO, JA, JC, MST, MVP, RETI, RFS, ROL,
+    ROR, RSUB, SCO, SIO, STOP, VECT, WAIT,
+};
+use modules::generic::instructions::{AAP, AGP, GAP, GGP, RSAP, RSGP, SAP, SGP, STAP, STGP};
+use modules::generic::GenericModule;
+use program::ProgramWord;
+use Error;
+use Interface;
+
+/// Type names of `MVP`
+const MOVE_TYPES: [&str; 3] = ["ABS", "REL", "COORD"];
+
+/// Type names of `RFS`
+const REFERENCE_SEARCH_ACTIONS: [(&str, ReferenceSearchAction); 3] = [
+    ("START", ReferenceSearchAction::Start),
+    ("STOP", ReferenceSearchAction::Stop),
+    ("STATUS", ReferenceSearchAction::Status),
+];
+
+/// Type names of `CALC`
+const CALC_TYPES: [&str; 10] = [
+    "ADD", "SUB", "MUL", "DIV", "MOD", "AND", "OR", "XOR", "NOT", "LOAD",
+];
+
+/// Type names of `CALCX`
+const CALCX_TYPES: [(&str, CALCX); 11] = [
+    ("ADD", CALCX::Add),
+    ("SUB", CALCX::Sub),
+    ("MUL", CALCX::Mul),
+    ("DIV", CALCX::Div),
+    ("MOD", CALCX::Mod),
+    ("AND", CALCX::And),
+    ("OR", CALCX::Or),
+    ("XOR", CALCX::Xor),
+    ("NOT", CALCX::Not),
+    ("LOAD", CALCX::Load),
+    ("SWAP", CALCX::Swap),
+];
+
+/// Type names of `JC`
+const JUMP_CONDITIONS: [(&str, JumpCondition); 12] = [
+    ("ZE", JumpCondition::Zero),
+    ("NZ", JumpCondition::NotZero),
+    ("EQ", JumpCondition::Equal),
+    ("NE", JumpCondition::NotEqual),
+    ("GT", JumpCondition::Greater),
+    ("GE", JumpCondition::GreaterOrEqual),
+    ("LT", JumpCondition::Lower),
+    ("LE", JumpCondition::LowerOrEqual),
+    ("ETO", JumpCondition::TimeoutError),
+    ("EAL", JumpCondition::ExternalAlarm),
+    ("EDV", JumpCondition::DeviationError),
+    ("EPO", JumpCondition::PositionError),
+];
+
+/// Type names of `WAIT`
+const WAIT_EVENTS: [&str; 5] = ["TICKS", "POS", "REFSW", "LIMSW", "RFS"];
+
+/// Type names of `CLE`
+const ERROR_FLAGS: [(&str, ErrorFlag); 5] = [
+    ("ALL", ErrorFlag::All),
+    ("ETO", ErrorFlag::TimeoutError),
+    ("EAL", ErrorFlag::ExternalAlarm),
+    ("EDV", ErrorFlag::DeviationError),
+    ("EPO", ErrorFlag::PositionError),
+];
+
+macro_rules! program_instruction {
+    ($($name:ident),*) => {
+        /// An instruction of an assembled TMCL program.
+        ///
+        /// Parameter instructions use the raw versions from `modules::generic::instructions`,
+        /// as the source only contains the parameter numbers.
+        #[derive(Debug, PartialEq)]
+        pub enum ProgramInstruction {
+            $($name($name),)*
+        }
+
+        impl ProgramInstruction {
+            /// The program word storing this instruction
+            pub fn program_word(&self) -> ProgramWord {
+                match *self {
+                    $(ProgramInstruction::$name(ref instruction) => {
+                        ProgramWord::from_instruction(instruction)
+                    })*
+                }
+            }
+
+            /// Write this instruction to the TMCL program memory of `module`
+            ///
+            /// See `GenericModule::store_instruction`.
+            pub fn store<'a, IF, Cell, T>(
+                self,
+                module: &'a GenericModule<'a, IF, Cell, T>,
+            ) -> Result<ProgramWord, Error<IF::Error>>
+            where
+                IF: Interface + 'a,
+                Cell: InteriorMut<'a, IF>,
+                T: Deref<Target = Cell> + 'a,
+            {
+                match self {
+                    $(ProgramInstruction::$name(instruction) => {
+                        module.store_instruction(instruction)
+                    })*
+                }
+            }
+        }
+    };
+}
+
+program_instruction!(
+    ROR, ROL, MST, MVP, SAP, GAP, STAP, RSAP, SGP, GGP, STGP, RSGP, RFS, SIO, GIO, CALC, COMP, JC,
+    JA, CSUB, RSUB, EI, DI, WAIT, STOP, SCO, GCO, CCO, CALCX, AAP, AGP, CLE, VECT, RETI, ACO
+);
+
+/// An assembled TMCL program.
+#[derive(Debug, PartialEq)]
+pub struct Program {
+    start_address: u32,
+    instructions: Vec<ProgramInstruction>,
+}
+
+impl Program {
+    /// The address of the first instruction, all labels are relative to it
+    pub fn start_address(&self) -> u32 {
+        self.start_address
+    }
+
+    /// Returns the instructions of the program
+    pub fn instructions(&self) -> &[ProgramInstruction] {
+        &self.instructions
+    }
+
+    /// Returns the program words of the program
+    pub fn program_words(&self) -> Vec<ProgramWord> {
+        self.instructions
+            .iter()
+            .map(ProgramInstruction::program_word)
+            .collect()
+    }
+
+    /// Serialize the program into consecutive 8 byte program words
+    ///
+    /// See `ProgramWord::to_bytes`.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.instructions
+            .iter()
+            .flat_map(|instruction| instruction.program_word().to_bytes().to_vec())
+            .collect()
+    }
+
+    /// Download the program to `module`, starting at the start address
+    ///
+    /// Returns the stored program words, which can be passed to `GenericModule::verify_program`.
+    pub fn download<'a, IF, Cell, T>(
+        self,
+        module: &'a GenericModule<'a, IF, Cell, T>,
+    ) -> Result<Vec<ProgramWord>, Error<IF::Error>>
+    where
+        IF: Interface + 'a,
+        Cell: InteriorMut<'a, IF>,
+        T: Deref<Target = Cell> + 'a,
+    {
+        let instructions = self.instructions;
+        module.download(self.start_address, |module| {
+            instructions
+                .into_iter()
+                .map(|instruction| instruction.store(module))
+                .collect()
+        })
+    }
+}
+
+/// All possible errors when assembling a TMCL source file.
+///
+/// Each error contains the number of the line it occurred on, starting at 1.
+#[derive(Debug, PartialEq, Clone)]
+pub enum AssemblerError {
+    /// The line is neither a label, a constant nor an instruction.
+    Syntax(usize),
+
+    /// The mnemonic is not a known instruction.
+    UnknownInstruction(usize, String),
+
+    /// The instruction has the wrong number of operands.
+    OperandCount(usize),
+
+    /// The operand is neither a number, a symbol nor a type name of the instruction.
+    InvalidOperand(usize, String),
+
+    /// The value does not fit in the operand.
+    OutOfRange(usize, i64),
+
+    /// The symbol is not defined.
+    UndefinedSymbol(usize, String),
+
+    /// The symbol is defined more than once.
+    DuplicateSymbol(usize, String),
+}
+
+impl AssemblerError {
+    /// The line the error occurred on
+    pub fn line(&self) -> usize {
+        match *self {
+            AssemblerError::Syntax(line)
+            | AssemblerError::UnknownInstruction(line, _)
+            | AssemblerError::OperandCount(line)
+            | AssemblerError::InvalidOperand(line, _)
+            | AssemblerError::OutOfRange(line, _)
+            | AssemblerError::UndefinedSymbol(line, _)
+            | AssemblerError::DuplicateSymbol(line, _) => line,
+        }
+    }
+}
+
+/// All possible errors when disassembling program words.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum DisassemblerError {
+    /// The word at this address is not an instruction the assembler can produce.
+    UnknownInstruction(u32),
+}
+
+/// Assemble a TMCL source file to be stored from address 0
+pub fn assemble(source: &str) -> Result<Program, AssemblerError> {
+    assemble_at(source, 0)
+}
+
+/// Assemble a TMCL source file to be stored from `start_address`
+pub fn assemble_at(source: &str, start_address: u32) -> Result<Program, AssemblerError> {
+    let mut symbols = Symbols::new();
+    let mut statements = Vec::new();
+
+    for (index, line) in source.lines().enumerate() {
+        let line_number = index + 1;
+        let mut line = match line.find("//") {
+            Some(comment) => &line[..comment],
+            None => line,
+        }
+        .trim();
+
+        while let Some(colon) = line.find(':') {
+            let address = i64::from(start_address) + statements.len() as i64;
+            symbols.define(line_number, line[..colon].trim(), address)?;
+            line = line[colon + 1..].trim();
+        }
+        if line.is_empty() {
+            continue;
+        }
+
+        if let Some(equals) = line.find('=') {
+            let value = symbols.value(line_number, line[equals + 1..].trim())?;
+            symbols.define(line_number, line[..equals].trim(), value)?;
+        } else {
+            statements.push(Statement::parse(line_number, line));
+        }
+    }
+
+    let instructions = statements
+        .iter()
+        .map(|statement| statement.assemble(&symbols))
+        .collect::<Result<_, _>>()?;
+    Ok(Program {
+        start_address,
+        instructions,
+    })
+}
+
+/// Disassemble program words stored from `start_address` into a TMCL source file
+///
+/// Jump and interrupt targets inside the program get a label `L<address>`. Assembling the
+/// result from the same start address gives back the same program words.
+pub fn disassemble(
+    start_address: u32,
+    program: &[ProgramWord],
+) -> Result<String, DisassemblerError> {
+    let end_address = start_address.saturating_add(program.len() as u32);
+    let mut targets: Vec<u32> = program
+        .iter()
+        .filter(|word| matches!(word.command_number(), 21..=23 | 37))
+        .map(|word| u32::from_le_bytes(word.operand()))
+        .filter(|address| *address >= start_address && *address < end_address)
+        .collect();
+    targets.sort_unstable();
+    targets.dedup();
+
+    let labels: Vec<String> = targets
+        .iter()
+        .map(|address| format!("L{}", address))
+        .collect();
+    let mut symbols = Symbols::new();
+    for (label, &address) in labels.iter().zip(&targets) {
+        symbols.define(0, label, i64::from(address)).unwrap();
+    }
+
+    let mut source = String::new();
+    for (address, word) in (start_address..).zip(program) {
+        if let Ok(index) = targets.binary_search(&address) {
+            writeln!(source, "{}:", labels[index]).unwrap();
+        }
+        let line = disassemble_word(word, &targets)
+            .ok_or(DisassemblerError::UnknownInstruction(address))?;
+        // Fields the source format can't express would get lost, so check the round trip.
+        match Statement::parse(0, &line).assemble(&symbols) {
+            Ok(ref instruction) if instruction.program_word() == *word => (),
+            _ => return Err(DisassemblerError::UnknownInstruction(address)),
+        }
+        writeln!(source, "    {}", line).unwrap();
+    }
+    Ok(source)
+}
+
+/// The source text of a single program word, without checking unused fields.
+fn disassemble_word(word: &ProgramWord, targets: &[u32]) -> Option<String> {
+    let type_number = word.type_number() as usize;
+    let motor_bank = word.motor_bank_number();
+    let value = i32::from_le_bytes(word.operand());
+    let address = || {
+        let address = value as u32;
+        if targets.binary_search(&address).is_ok() {
+            format!("L{}", address)
+        } else {
+            address.to_string()
+        }
+    };
+
+    let line = match word.command_number() {
+        1 => format!("ROR {}, {}", motor_bank, value as u32),
+        2 => format!("ROL {}, {}", motor_bank, value as u32),
+        3 => format!("MST {}", motor_bank),
+        4 => format!(
+            "MVP {}, {}, {}",
+            MOVE_TYPES.get(type_number)?,
+            motor_bank,
+            value
+        ),
+        5 => format!("SAP {}, {}, {}", type_number, motor_bank, value),
+        6 => format!("GAP {}, {}", type_number, motor_bank),
+        7 => format!("STAP {}, {}", type_number, motor_bank),
+        8 => format!("RSAP {}, {}", type_number, motor_bank),
+        9 => format!("SGP {}, {}, {}", type_number, motor_bank, value),
+        10 => format!("GGP {}, {}", type_number, motor_bank),
+        11 => format!("STGP {}, {}", type_number, motor_bank),
+        12 => format!("RSGP {}, {}", type_number, motor_bank),
+        13 => format!(
+            "RFS {}, {}",
+            REFERENCE_SEARCH_ACTIONS.get(type_number)?.0,
+            motor_bank
+        ),
+        14 => format!("SIO {}, {}, {}", type_number, motor_bank, value),
+        15 => format!("GIO {}, {}", type_number, motor_bank),
+        19 => match *CALC_TYPES.get(type_number)? {
+            "NOT" => "CALC NOT".to_string(),
+            name => format!("CALC {}, {}", name, value),
+        },
+        20 => format!("COMP {}", value),
+        21 => format!("JC {}, {}", JUMP_CONDITIONS.get(type_number)?.0, address()),
+        22 => format!("JA {}", address()),
+        23 => format!("CSUB {}", address()),
+        24 => "RSUB".to_string(),
+        25 => format!("EI {}", type_number),
+        26 => format!("DI {}", type_number),
+        27 => format!(
+            "WAIT {}, {}, {}",
+            WAIT_EVENTS.get(type_number)?,
+            motor_bank,
+            value as u32
+        ),
+        28 => "STOP".to_string(),
+        30 => format!("SCO {}, {}, {}", type_number, motor_bank, value),
+        31 => format!("GCO {}, {}", type_number, motor_bank),
+        32 => format!("CCO {}, {}", type_number, motor_bank),
+        33 => format!("CALCX {}", CALCX_TYPES.get(type_number)?.0),
+        34 => format!("AAP {}, {}", type_number, motor_bank),
+        35 => format!("AGP {}, {}", type_number, motor_bank),
+        36 => format!("CLE {}", ERROR_FLAGS.get(type_number)?.0),
+        37 => format!("VECT {}, {}", type_number, address()),
+        38 => "RETI".to_string(),
+        39 => format!("ACO {}, {}", type_number, motor_bank),
+        _ => return None,
+    };
+    Some(line)
+}
+
+/// Labels and constants of a source file.
+struct Symbols<'s> {
+    values: HashMap<&'s str, i64>,
+}
+
+impl<'s> Symbols<'s> {
+    fn new() -> Self {
+        Symbols {
+            values: HashMap::new(),
+        }
+    }
+
+    fn define(&mut self, line: usize, name: &'s str, value: i64) -> Result<(), AssemblerError> {
+        if !is_identifier(name) {
+            return Err(AssemblerError::Syntax(line));
+        }
+        if self.values.insert(name, value).is_some() {
+            return Err(AssemblerError::DuplicateSymbol(line, name.to_string()));
+        }
+        Ok(())
+    }
+
+    /// The value of a number or symbol
+    fn value(&self, line: usize, operand: &str) -> Result<i64, AssemblerError> {
+        if let Some(value) = parse_number(operand) {
+            Ok(value)
+        } else if is_identifier(operand) {
+            self.values
+                .get(operand)
+                .cloned()
+                .ok_or_else(|| AssemblerError::UndefinedSymbol(line, operand.to_string()))
+        } else {
+            Err(AssemblerError::InvalidOperand(line, operand.to_string()))
+        }
+    }
+}
+
+/// An instruction line of a source file.
+struct Statement<'s> {
+    line: usize,
+    mnemonic: &'s str,
+    operands: Vec<&'s str>,
+}
+
+impl<'s> Statement<'s> {
+    fn parse(line: usize, text: &'s str) -> Self {
+        let (mnemonic, operands) = match text.find(char::is_whitespace) {
+            Some(space) => (&text[..space], text[space..].trim()),
+            None => (text, ""),
+        };
+        let operands = if operands.is_empty() {
+            Vec::new()
+        } else {
+            operands.split(',').map(str::trim).collect()
+        };
+        Statement {
+            line,
+            mnemonic,
+            operands,
+        }
+    }
+
+    fn assemble(&self, symbols: &Symbols) -> Result<ProgramInstruction, AssemblerError> {
+        let instruction = match self.mnemonic.to_ascii_uppercase().as_str() {
+            "ROR" => {
+                self.operand_count(2)?;
+                ProgramInstruction::ROR(ROR::new(self.u8(symbols, 0)?, self.u32(symbols, 1)?))
+            }
+            "ROL" => {
+                self.operand_count(2)?;
+                ProgramInstruction::ROL(ROL::new(self.u8(symbols, 0)?, self.u32(symbols, 1)?))
+            }
+            "MST" => {
+                self.operand_count(1)?;
+                ProgramInstruction::MST(MST::new(self.u8(symbols, 0)?))
+            }
+            "MVP" => {
+                self.operand_count(3)?;
+                let operation = match self.type_name(0, &MOVE_TYPES)? {
+                    0 => MoveOperation::Absolute(self.i32(symbols, 2)?),
+                    1 => MoveOperation::Relative(self.i32(symbols, 2)?),
+                    _ => MoveOperation::Coordinate(self.u32(symbols, 2)?),
+                };
+                ProgramInstruction::MVP(MVP::new(self.u8(symbols, 1)?, operation))
+            }
+            "SAP" => {
+                self.operand_count(3)?;
+                ProgramInstruction::SAP(SAP::new(
+                    self.u8(symbols, 1)?,
+                    self.u8(symbols, 0)?,
+                    self.i32(symbols, 2)?.to_le_bytes(),
+                ))
+            }
+            "GAP" => {
+                self.operand_count(2)?;
+                ProgramInstruction::GAP(GAP::new(self.u8(symbols, 1)?, self.u8(symbols, 0)?))
+            }
+            "STAP" => {
+                self.operand_count(2)?;
+                ProgramInstruction::STAP(STAP::new(self.u8(symbols, 1)?, self.u8(symbols, 0)?))
+            }
+            "RSAP" => {
+                self.operand_count(2)?;
+                ProgramInstruction::RSAP(RSAP::new(self.u8(symbols, 1)?, self.u8(symbols, 0)?))
+            }
+            "SGP" => {
+                self.operand_count(3)?;
+                ProgramInstruction::SGP(SGP::new(
+                    self.u8(symbols, 1)?,
+                    self.u8(symbols, 0)?,
+                    self.i32(symbols, 2)?.to_le_bytes(),
+                ))
+            }
+            "GGP" => {
+                self.operand_count(2)?;
+                ProgramInstruction::GGP(GGP::new(self.u8(symbols, 1)?, self.u8(symbols, 0)?))
+            }
+            "STGP" => {
+                self.operand_count(2)?;
+                ProgramInstruction::STGP(STGP::new(self.u8(symbols, 1)?, self.u8(symbols, 0)?))
+            }
+            "RSGP" => {
+                self.operand_count(2)?;
+                ProgramInstruction::RSGP(RSGP::new(self.u8(symbols, 1)?, self.u8(symbols, 0)?))
+            }
+            "RFS" => {
+                self.operand_count(2)?;
+                let action =
+                    REFERENCE_SEARCH_ACTIONS[self.type_name(0, &REFERENCE_SEARCH_ACTIONS)?].1;
+                ProgramInstruction::RFS(RFS::new(self.u8(symbols, 1)?, action))
+            }
+            "SIO" => {
+                self.operand_count(3)?;
+                let state = match self.value(symbols, 2)? {
+                    0 => false,
+                    1 => true,
+                    value => return Err(AssemblerError::OutOfRange(self.line, value)),
+                };
+                ProgramInstruction::SIO(SIO::new(self.u8(symbols, 1)?, self.u8(symbols, 0)?, state))
+            }
+            "GIO" => {
+                self.operand_count(2)?;
+                ProgramInstruction::GIO(GIO::new(self.u8(symbols, 1)?, self.u8(symbols, 0)?))
+            }
+            "CALC" => {
+                let operation = self.type_name(0, &CALC_TYPES)?;
+                if CALC_TYPES[operation] == "NOT" {
+                    self.operand_count(1)?;
+                    ProgramInstruction::CALC(CALC::Not)
+                } else {
+                    self.operand_count(2)?;
+                    let value = self.i32(symbols, 1)?;
+                    ProgramInstruction::CALC(match operation {
+                        0 => CALC::Add(value),
+                        1 => CALC::Sub(value),
+                        2 => CALC::Mul(value),
+                        3 => CALC::Div(value),
+                        4 => CALC::Mod(value),
+                        5 => CALC::And(value),
+                        6 => CALC::Or(value),
+                        7 => CALC::Xor(value),
+                        _ => CALC::Load(value),
+                    })
+                }
+            }
+            "COMP" => {
+                self.operand_count(1)?;
+                ProgramInstruction::COMP(COMP::new(self.i32(symbols, 0)?))
+            }
+            "JC" => {
+                self.operand_count(2)?;
+                let condition = JUMP_CONDITIONS[self.type_name(0, &JUMP_CONDITIONS)?].1;
+                ProgramInstruction::JC(JC::new(condition, self.u32(symbols, 1)?))
+            }
+            "JA" => {
+                self.operand_count(1)?;
+                ProgramInstruction::JA(JA::new(self.u32(symbols, 0)?))
+            }
+            "CSUB" => {
+                self.operand_count(1)?;
+                ProgramInstruction::CSUB(CSUB::new(self.u32(symbols, 0)?))
+            }
+            "RSUB" => {
+                self.operand_count(0)?;
+                ProgramInstruction::RSUB(RSUB::new())
+            }
+            "WAIT" => {
+                self.operand_count(3)?;
+                let motor_number = self.u8(symbols, 1)?;
+                let event = match self.type_name(0, &WAIT_EVENTS)? {
+                    0 => WaitEvent::Ticks,
+                    1 => WaitEvent::PositionReached(motor_number),
+                    2 => WaitEvent::ReferenceSwitch(motor_number),
+                    3 => WaitEvent::LimitSwitch(motor_number),
+                    _ => WaitEvent::ReferenceSearch(motor_number),
+                };
+                ProgramInstruction::WAIT(WAIT::new(event, self.u32(symbols, 2)?))
+            }
+            "STOP" => {
+                self.operand_count(0)?;
+                ProgramInstruction::STOP(STOP::new())
+            }
+            "SCO" => {
+                self.operand_count(3)?;
+                ProgramInstruction::SCO(SCO::new(
+                    self.u8(symbols, 0)?,
+                    self.u8(symbols, 1)?,
+                    self.i32(symbols, 2)?,
+                ))
+            }
+            "GCO" => {
+                self.operand_count(2)?;
+                ProgramInstruction::GCO(GCO::new(self.u8(symbols, 0)?, self.u8(symbols, 1)?))
+            }
+            "CCO" => {
+                self.operand_count(2)?;
+                ProgramInstruction::CCO(CCO::new(self.u8(symbols, 0)?, self.u8(symbols, 1)?))
+            }
+            "CALCX" => {
+                self.operand_count(1)?;
+                ProgramInstruction::CALCX(CALCX_TYPES[self.type_name(0, &CALCX_TYPES)?].1)
+            }
+            "AAP" => {
+                self.operand_count(2)?;
+                ProgramInstruction::AAP(AAP::new(self.u8(symbols, 1)?, self.u8(symbols, 0)?))
+            }
+            "AGP" => {
+                self.operand_count(2)?;
+                ProgramInstruction::AGP(AGP::new(self.u8(symbols, 1)?, self.u8(symbols, 0)?))
+            }
+            "ACO" => {
+                self.operand_count(2)?;
+                ProgramInstruction::ACO(ACO::new(self.u8(symbols, 0)?, self.u8(symbols, 1)?))
+            }
+            "EI" => {
+                self.operand_count(1)?;
+                ProgramInstruction::EI(EI::new(self.interrupt_vector(symbols, 0)?))
+            }
+            "DI" => {
+                self.operand_count(1)?;
+                ProgramInstruction::DI(DI::new(self.interrupt_vector(symbols, 0)?))
+            }
+            "VECT" => {
+                self.operand_count(2)?;
+                let vector = match self.interrupt_vector(symbols, 0)? {
+                    InterruptVector::Global => {
+                        return Err(AssemblerError::OutOfRange(self.line, 255))
+                    }
+                    vector => vector,
+                };
+                ProgramInstruction::VECT(VECT::new(vector, self.u32(symbols, 1)?))
+            }
+            "RETI" => {
+                self.operand_count(0)?;
+                ProgramInstruction::RETI(RETI::new())
+            }
+            "CLE" => {
+                self.operand_count(1)?;
+                ProgramInstruction::CLE(CLE::new(ERROR_FLAGS[self.type_name(0, &ERROR_FLAGS)?].1))
+            }
+            _ => {
+                return Err(AssemblerError::UnknownInstruction(
+                    self.line,
+                    self.mnemonic.to_string(),
+                ))
+            }
+        };
+        Ok(instruction)
+    }
+
+    fn operand_count(&self, count: usize) -> Result<(), AssemblerError> {
+        if self.operands.len() == count {
+            Ok(())
+        } else {
+            Err(AssemblerError::OperandCount(self.line))
+        }
+    }
+
+    /// The index of the type name given as operand
+    fn type_name<N: TypeName>(&self, index: usize, names: &[N]) -> Result<usize, AssemblerError> {
+        let operand = self
+            .operands
+            .get(index)
+            .ok_or(AssemblerError::OperandCount(self.line))?;
+        names
+            .iter()
+            .position(|name| name.name().eq_ignore_ascii_case(operand))
+            .ok_or_else(|| AssemblerError::InvalidOperand(self.line, operand.to_string()))
+    }
+
+    fn value(&self, symbols: &Symbols, index: usize) -> Result<i64, AssemblerError> {
+        symbols.value(self.line, self.operands[index])
+    }
+
+    fn u8(&self, symbols: &Symbols, index: usize) -> Result<u8, AssemblerError> {
+        match self.value(symbols, index)? {
+            value if value >= 0 && value <= i64::from(u8::MAX) => Ok(value as u8),
+            value => Err(AssemblerError::OutOfRange(self.line, value)),
+        }
+    }
+
+    fn u32(&self, symbols: &Symbols, index: usize) -> Result<u32, AssemblerError> {
+        match self.value(symbols, index)? {
+            value if value >= 0 && value <= i64::from(u32::MAX) => Ok(value as u32),
+            value => Err(AssemblerError::OutOfRange(self.line, value)),
+        }
+    }
+
+    fn interrupt_vector(
+        &self,
+        symbols: &Symbols,
+        index: usize,
+    ) -> Result<InterruptVector, AssemblerError> {
+        let value = self.u8(symbols, index)?;
+        InterruptVector::try_from_u8(value)
+            .map_err(|_| AssemblerError::OutOfRange(self.line, i64::from(value)))
+    }
+
+    /// A 32 bit value, which may be given signed or unsigned
+    fn i32(&self, symbols: &Symbols, index: usize) -> Result<i32, AssemblerError> {
+        match self.value(symbols, index)? {
+            value if value >= i64::from(i32::MIN) && value <= i64::from(u32::MAX) => {
+                Ok(value as i32)
+            }
+            value => Err(AssemblerError::OutOfRange(self.line, value)),
+        }
+    }
+}
+
+/// An entry of a type name table.
+trait TypeName {
+    fn name(&self) -> &str;
+}
+
+impl TypeName for &str {
+    fn name(&self) -> &str {
+        self
+    }
+}
+
+impl<T> TypeName for (&str, T) {
+    fn name(&self) -> &str {
+        self.0
+    }
+}
+
+fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    match chars.next() {
+        Some(first) if first.is_ascii_alphabetic() || first == '_' => {
+            chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+        }
+        _ => false,
+    }
+}
+
+fn parse_number(operand: &str) -> Option<i64> {
+    let (negative, digits) = if let Some(digits) = operand.strip_prefix('-') {
+        (true, digits)
+    } else {
+        (false, operand)
+    };
+    let value = if let Some(hex) = digits
+        .strip_prefix("0x")
+        .or_else(|| digits.strip_prefix("0X"))
+        .or_else(|| digits.strip_prefix('$'))
+    {
+        i64::from_str_radix(hex, 16).ok()?
+    } else if digits.starts_with(|c: char| c.is_ascii_digit()) {
+        digits.parse().ok()?
+    } else {
+        return None;
+    };
+    Some(if negative { -value } else { value })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SOURCE: &str = "
+        // Move back and forth until input 0 is pulled high
+        Speed = 500
+        Input = 0
+
+        SAP 4, 0, $7ff    // maximum positioning speed
+        Loop: ROR 0, Speed
+            WAIT TICKS, 0, 100
+            ROL 0, Speed
+            WAIT TICKS, 0, 100
+            GIO Input, 0
+            JC ze, Loop
+            CSUB Stop
+            MVP ABS, 0, -1000
+            MVP REL, 0, 0x100
+            CCO 1, 0
+            CALCX SWAP
+            AAP 4, 0
+            AGP 66, 0
+            ACO 1, 0
+            MVP COORD, $43, 1
+            CALC NOT
+            STOP
+
+        Stop:
+            MST 0
+            RSUB
+
+        Timer:
+            VECT 0, Timer
+            EI 0
+            DI 255
+            RETI
+    ";
+
+    #[test]
+    fn assemble_source() {
+        let program = assemble(SOURCE).unwrap();
+        assert_eq!(program.instructions().len(), 24);
+        assert_eq!(
+            program.instructions()[1],
+            ProgramInstruction::ROR(ROR::new(0, 500))
+        );
+        assert_eq!(
+            program.instructions()[6],
+            ProgramInstruction::JC(JC::new(JumpCondition::Zero, 1))
+        );
+        assert_eq!(
+            program.instructions()[7],
+            ProgramInstruction::CSUB(CSUB::new(18))
+        );
+        assert_eq!(program.to_bytes()[..8], [5, 4, 0, 0, 0, 0x07, 0xff, 0x0f]);
+    }
+
+    #[test]
+    fn disassemble_round_trip() {
+        let program = assemble_at(SOURCE, 20).unwrap();
+        let words = program.program_words();
+        let source = disassemble(20, &words).unwrap();
+        assert!(source.starts_with("    SAP 4, 0, 2047\nL21:\n    ROR 0, 500\n"));
+        assert!(source.contains("    JC ZE, L21\n    CSUB L38\n"));
+        assert!(source.contains("    MVP COORD, 67, 1\n"));
+        assert!(source.contains("L40:\n    VECT 0, L40\n"));
+        assert_eq!(assemble_at(&source, 20).unwrap().program_words(), words);
+    }
+
+    #[test]
+    fn disassemble_unknown() {
+        let words = [
+            ProgramWord::new(28, 0, 0, [0; 4]),
+            ProgramWord::new(28, 1, 0, [0; 4]),
+        ];
+        assert_eq!(
+            disassemble(0, &words),
+            Err(DisassemblerError::UnknownInstruction(1))
+        );
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(
+            assemble("STOP\nFOO 1").unwrap_err(),
+            AssemblerError::UnknownInstruction(2, "FOO".to_string())
+        );
+        assert_eq!(
+            assemble("JA Nowhere").unwrap_err(),
+            AssemblerError::UndefinedSymbol(1, "Nowhere".to_string())
+        );
+        assert_eq!(
+            assemble("A:\nA: STOP").unwrap_err(),
+            AssemblerError::DuplicateSymbol(2, "A".to_string())
+        );
+        assert_eq!(
+            assemble("MST 256").unwrap_err(),
+            AssemblerError::OutOfRange(1, 256)
+        );
+        assert_eq!(
+            assemble("JC XX, 0").unwrap_err(),
+            AssemblerError::InvalidOperand(1, "XX".to_string())
+        );
+        assert_eq!(
+            assemble("ROR 0").unwrap_err(),
+            AssemblerError::OperandCount(1)
+        );
+    }
+}
@@ -0,0 +1,170 @@
+use lib::future::Future;
+use lib::marker::PhantomData;
+use lib::mem;
+use lib::ops::Deref;
+use lib::pin::Pin;
+use lib::task::{Context, Poll};
+
+use interior_mut::InteriorMut;
+
+use instructions::DirectInstruction;
+use Command;
+use Error;
+use Instruction;
+use Reply;
+use Return;
+use Status;
+
+/// A non-blocking `Interface`, for use with async runtimes.
+///
+/// A command is sent by waiting for `poll_ready`, starting it with `start_command` and then
+/// polling `poll_reply` until the reply has been received. Only one command can be in progress
+/// at a time, `poll_ready` stays pending until the command in progress is finished or
+/// cancelled.
+///
+/// The interface is only borrowed while it is polled, so there are no reply timeouts. Use the
+/// timeout of your runtime instead, dropping the future cancels the command.
+pub trait AsyncInterface {
+    type Error;
+
+    /// Poll whether the interface is ready to start a new command.
+    fn poll_ready(&mut self, cx: &mut Context) -> Poll<Result<(), Self::Error>>;
+
+    /// Start transmitting a command.
+    ///
+    /// Must only be called after `poll_ready` returned `Poll::Ready(Ok(()))`.
+    fn start_command<T: Instruction>(&mut self, command: &Command<T>) -> Result<(), Self::Error>;
+
+    /// Poll for the reply to the command in progress.
+    ///
+    /// This also finishes transmitting the command.
+    fn poll_reply(&mut self, cx: &mut Context) -> Poll<Result<Reply, Self::Error>>;
+
+    /// Abandon the command in progress, the interface is ready for a new command afterwards.
+    fn cancel(&mut self);
+}
+
+#[derive(Debug)]
+enum State<I: Instruction, E> {
+    Ready(Command<I>),
+    AwaitingReply,
+    Failed(Error<E>),
+    Done,
+}
+
+/// Future returned by `write_command_async`, resolving to the return value of the instruction.
+///
+/// Dropping it before it is finished cancels the command.
+#[derive(Debug)]
+pub struct WriteCommand<
+    'a,
+    IF: AsyncInterface + 'a,
+    Cell: InteriorMut<'a, IF> + 'a,
+    T: Deref<Target = Cell> + 'a,
+    I: Instruction,
+> {
+    interface: &'a T,
+    state: State<I, IF::Error>,
+    pd1: PhantomData<&'a IF>,
+    pd2: PhantomData<&'a Cell>,
+}
+
+impl<'a, IF, Cell, T, I> WriteCommand<'a, IF, Cell, T, I>
+where
+    IF: AsyncInterface,
+    Cell: InteriorMut<'a, IF>,
+    T: Deref<Target = Cell>,
+    I: DirectInstruction,
+{
+    pub(crate) fn new(interface: &'a T, address: u8, instruction: I) -> Self {
+        WriteCommand {
+            interface,
+            state: State::Ready(Command::new(address, instruction)),
+            pd1: PhantomData {},
+            pd2: PhantomData {},
+        }
+    }
+
+    /// A future failing with `error` without sending anything.
+    pub(crate) fn failed(interface: &'a T, error: Error<IF::Error>) -> Self {
+        WriteCommand {
+            interface,
+            state: State::Failed(error),
+            pd1: PhantomData {},
+            pd2: PhantomData {},
+        }
+    }
+}
+
+// The future is never pinned structurally, the command is only moved out by value.
+impl<'a, IF, Cell, T, I> Unpin for WriteCommand<'a, IF, Cell, T, I>
+where
+    IF: AsyncInterface,
+    Cell: InteriorMut<'a, IF>,
+    T: Deref<Target = Cell>,
+    I: Instruction,
+{
+}
+
+impl<'a, IF, Cell, T, I> Future for WriteCommand<'a, IF, Cell, T, I>
+where
+    IF: AsyncInterface,
+    Cell: InteriorMut<'a, IF>,
+    T: Deref<Target = Cell>,
+    I: DirectInstruction,
+{
+    type Output = Result<I::Return, Error<IF::Error>>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        let cell: &'a Cell = this.interface;
+        let mut interface = cell.borrow_int_mut().or(Err(Error::InterfaceUnavailable))?;
+
+        match mem::replace(&mut this.state, State::Done) {
+            State::Ready(command) => match interface.poll_ready(cx) {
+                Poll::Pending => {
+                    this.state = State::Ready(command);
+                    return Poll::Pending;
+                }
+                Poll::Ready(Err(e)) => return Poll::Ready(Err(Error::InterfaceError(e))),
+                Poll::Ready(Ok(())) => {
+                    interface
+                        .start_command(&command)
+                        .map_err(Error::InterfaceError)?;
+                }
+            },
+            State::AwaitingReply => (),
+            State::Failed(e) => return Poll::Ready(Err(e)),
+            State::Done => panic!("WriteCommand polled after completion"),
+        }
+
+        match interface.poll_reply(cx) {
+            Poll::Pending => {
+                this.state = State::AwaitingReply;
+                Poll::Pending
+            }
+            Poll::Ready(Err(e)) => Poll::Ready(Err(Error::InterfaceError(e))),
+            Poll::Ready(Ok(reply)) => Poll::Ready(match reply.status() {
+                Status::Ok(_) => Ok(<I::Return as Return>::from_operand(reply.operand())),
+                Status::Err(e) => Err(e.into()),
+            }),
+        }
+    }
+}
+
+impl<'a, IF, Cell, T, I> Drop for WriteCommand<'a, IF, Cell, T, I>
+where
+    IF: AsyncInterface,
+    Cell: InteriorMut<'a, IF>,
+    T: Deref<Target = Cell>,
+    I: Instruction,
+{
+    fn drop(&mut self) {
+        if let State::AwaitingReply = self.state {
+            let cell: &'a Cell = self.interface;
+            if let Ok(mut interface) = cell.borrow_int_mut() {
+                interface.cancel();
+            }
+        }
+    }
+}
@@ -0,0 +1,358 @@
+use std::io;
+use std::pin::Pin;
+use std::task::{Context, Poll, Waker};
+
+use futures_io::{AsyncRead, AsyncWrite};
+
+use calculate_checksum;
+use AsyncInterface;
+use Command;
+use Instruction;
+use Reply;
+use SerialError;
+
+/// An `AsyncInterface` for TMCM modules connected through RS232, RS485 or any other byte stream.
+///
+/// Works with anything implementing the `futures-io` `AsyncRead + AsyncWrite` traits, such as an
+/// async serial port or TCP stream. Streams of other runtimes can be used through their
+/// `futures-io` compatibility layer. Replies are received the same way as with
+/// `SerialInterface`, skipping bytes until the stream is synchronised with the reply frames again.
+///
+/// There is no reply timeout, use the timeout of your runtime on `write_command_async` instead.
+/// When a command is cancelled after part of it was written, the rest of the frame is written
+/// with a wrong checksum before the next command, so the module discards it.
+#[derive(Debug)]
+pub struct AsyncSerialInterface<T> {
+    port: T,
+    command: [u8; 9],
+    written: usize,
+    flushed: bool,
+    frame: [u8; 9],
+    received: usize,
+    expected: Option<(u8, u8)>,
+    waiting: Vec<Waker>,
+}
+
+impl<T: AsyncRead + AsyncWrite + Unpin> AsyncSerialInterface<T> {
+    /// Create a new interface on the given byte stream
+    pub fn new(port: T) -> Self {
+        AsyncSerialInterface {
+            port,
+            command: [0; 9],
+            written: 9,
+            flushed: true,
+            frame: [0; 9],
+            received: 0,
+            expected: None,
+            waiting: Vec::new(),
+        }
+    }
+
+    /// Returns a reference to the underlying byte stream
+    pub fn get_ref(&self) -> &T {
+        &self.port
+    }
+
+    /// Returns a mutable reference to the underlying byte stream
+    pub fn get_mut(&mut self) -> &mut T {
+        &mut self.port
+    }
+
+    /// Release the underlying byte stream
+    pub fn release(self) -> T {
+        self.port
+    }
+
+    /// End the command in progress and wake up all commands waiting for the interface.
+    fn finish(&mut self) {
+        self.expected = None;
+        for waker in self.waiting.drain(..) {
+            waker.wake();
+        }
+    }
+
+    fn poll_transmit(&mut self, cx: &mut Context) -> Poll<Result<(), SerialError>> {
+        while self.written < self.command.len() {
+            match Pin::new(&mut self.port).poll_write(cx, &self.command[self.written..]) {
+                Poll::Pending => return Poll::Pending,
+                Poll::Ready(Ok(0)) => {
+                    return Poll::Ready(Err(io::Error::from(io::ErrorKind::WriteZero).into()))
+                }
+                Poll::Ready(Ok(n)) => self.written += n,
+                Poll::Ready(Err(e)) => return Poll::Ready(Err(e.into())),
+            }
+        }
+        if !self.flushed {
+            match Pin::new(&mut self.port).poll_flush(cx) {
+                Poll::Pending => return Poll::Pending,
+                Poll::Ready(Ok(())) => self.flushed = true,
+                Poll::Ready(Err(e)) => return Poll::Ready(Err(e.into())),
+            }
+        }
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_receive(
+        &mut self,
+        cx: &mut Context,
+        module_address: u8,
+        command_number: u8,
+    ) -> Poll<Result<Reply, SerialError>> {
+        loop {
+            let frame_len = self.frame.len();
+            match Pin::new(&mut self.port).poll_read(cx, &mut self.frame[self.received..]) {
+                Poll::Pending => return Poll::Pending,
+                Poll::Ready(Ok(0)) => {
+                    return Poll::Ready(Err(io::Error::from(io::ErrorKind::UnexpectedEof).into()))
+                }
+                Poll::Ready(Ok(n)) => self.received += n,
+                Poll::Ready(Err(ref e)) if e.kind() == io::ErrorKind::Interrupted => continue,
+                Poll::Ready(Err(e)) => return Poll::Ready(Err(e.into())),
+            }
+
+            if self.received < frame_len {
+                continue;
+            }
+
+            match Reply::deserialize(&self.frame) {
+                Ok(reply) => {
+                    self.received = 0;
+                    if reply.module_address() == module_address
+                        && reply.command_number() == command_number
+                    {
+                        return Poll::Ready(Ok(reply));
+                    }
+                }
+                Err(_) => {
+                    // Not a reply frame, resynchronise by dropping the first byte.
+                    self.frame.copy_within(1.., 0);
+                    self.received -= 1;
+                }
+            }
+        }
+    }
+}
+
+impl<T: AsyncRead + AsyncWrite + Unpin> AsyncInterface for AsyncSerialInterface<T> {
+    type Error = SerialError;
+
+    fn poll_ready(&mut self, cx: &mut Context) -> Poll<Result<(), Self::Error>> {
+        if self.expected.is_none() {
+            // Finish the frame of a cancelled command first
+            return match self.poll_transmit(cx) {
+                Poll::Ready(Err(e)) => {
+                    self.written = self.command.len();
+                    self.flushed = true;
+                    Poll::Ready(Err(e))
+                }
+                poll => poll,
+            };
+        }
+        if !self.waiting.iter().any(|waker| waker.will_wake(cx.waker())) {
+            self.waiting.push(cx.waker().clone());
+        }
+        Poll::Pending
+    }
+
+    fn start_command<I: Instruction>(&mut self, command: &Command<I>) -> Result<(), Self::Error> {
+        self.command = command.serialize();
+        self.written = 0;
+        self.flushed = false;
+        self.received = 0;
+        self.expected = Some((command.module_address(), I::INSTRUCTION_NUMBER));
+        Ok(())
+    }
+
+    fn poll_reply(&mut self, cx: &mut Context) -> Poll<Result<Reply, Self::Error>> {
+        let (module_address, command_number) = match self.expected {
+            Some(expected) => expected,
+            None => return Poll::Ready(Err(SerialError::NoCommandTransmitted)),
+        };
+        let result = match self.poll_transmit(cx) {
+            Poll::Pending => return Poll::Pending,
+            Poll::Ready(Err(e)) => Err(e),
+            Poll::Ready(Ok(())) => match self.poll_receive(cx, module_address, command_number) {
+                Poll::Pending => return Poll::Pending,
+                Poll::Ready(result) => result,
+            },
+        };
+        self.finish();
+        Poll::Ready(result)
+    }
+
+    fn cancel(&mut self) {
+        if self.written == 0 {
+            self.written = self.command.len();
+            self.flushed = true;
+        } else if self.written < self.command.len() {
+            // The module already received part of the frame, pad it with a wrong checksum.
+            for byte in &mut self.command[self.written..] {
+                *byte = 0;
+            }
+            self.command[8] = calculate_checksum(&self.command[..8]).wrapping_add(1);
+        }
+        self.finish();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::cell::RefCell;
+    use std::future::Future;
+
+    use modules::generic::instructions::SAP;
+    use modules::generic::GenericModule;
+    use modules::tmcm::axis_parameters::ActualPosition;
+    use modules::tmcm::instructions::{GAP, ROR};
+    use modules::tmcm::TmcmModule;
+    use Error;
+
+    /// Replays `input`, and is pending once it is exhausted. Accepts up to `writable` bytes.
+    struct MockPort {
+        written: Vec<u8>,
+        writable: usize,
+        input: Vec<u8>,
+    }
+
+    impl AsyncRead for MockPort {
+        fn poll_read(
+            self: Pin<&mut Self>,
+            _cx: &mut Context,
+            buf: &mut [u8],
+        ) -> Poll<io::Result<usize>> {
+            let this = self.get_mut();
+            if this.input.is_empty() {
+                return Poll::Pending;
+            }
+            let n = buf.len().min(this.input.len());
+            buf[..n].copy_from_slice(&this.input[..n]);
+            this.input.drain(..n);
+            Poll::Ready(Ok(n))
+        }
+    }
+
+    impl AsyncWrite for MockPort {
+        fn poll_write(
+            self: Pin<&mut Self>,
+            _cx: &mut Context,
+            buf: &[u8],
+        ) -> Poll<io::Result<usize>> {
+            let this = self.get_mut();
+            if this.writable == 0 {
+                return Poll::Pending;
+            }
+            let n = buf.len().min(this.writable);
+            this.written.extend_from_slice(&buf[..n]);
+            this.writable -= n;
+            Poll::Ready(Ok(n))
+        }
+
+        fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context) -> Poll<io::Result<()>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn poll_close(self: Pin<&mut Self>, _cx: &mut Context) -> Poll<io::Result<()>> {
+            Poll::Ready(Ok(()))
+        }
+    }
+
+    fn poll<F: Future + Unpin>(future: &mut F) -> Poll<F::Output> {
+        Pin::new(future).poll(&mut Context::from_waker(Waker::noop()))
+    }
+
+    #[test]
+    fn write_command_async() {
+        let mut input = vec![0xff];
+        input.extend_from_slice(&[2, 1, 100, 6, 0, 0, 0x01, 0xf4, 98]);
+        let port = MockPort {
+            written: Vec::new(),
+            writable: usize::MAX,
+            input,
+        };
+        let interface = RefCell::new(AsyncSerialInterface::new(port));
+        let module = TmcmModule::new(&interface, 1);
+
+        let mut command = module.write_command_async(GAP::<ActualPosition>::new(0));
+        match poll(&mut command) {
+            Poll::Ready(Ok(position)) => assert_eq!(i32::from(position), 500),
+            _ => panic!("expected a reply"),
+        }
+        assert_eq!(
+            interface.borrow().get_ref().written,
+            vec![1, 6, 1, 0, 0, 0, 0, 0, 8]
+        );
+    }
+
+    #[test]
+    fn one_command_at_a_time() {
+        let port = MockPort {
+            written: Vec::new(),
+            writable: usize::MAX,
+            input: Vec::new(),
+        };
+        let interface = RefCell::new(AsyncSerialInterface::new(port));
+        let module = TmcmModule::new(&interface, 1);
+
+        let mut first = module.write_command_async(ROR::new(0, 100));
+        let mut second = module.write_command_async(ROR::new(0, 200));
+        assert!(poll(&mut first).is_pending());
+        assert!(poll(&mut second).is_pending());
+        assert_eq!(interface.borrow().get_ref().written.len(), 9);
+
+        // Dropping the first command cancels it, so the second can be sent
+        drop(first);
+        interface
+            .borrow_mut()
+            .get_mut()
+            .input
+            .extend_from_slice(&[2, 1, 100, 1, 0, 0, 0, 0, 104]);
+        assert!(matches!(poll(&mut second), Poll::Ready(Ok(()))));
+        assert_eq!(interface.borrow().get_ref().written.len(), 18);
+    }
+
+    #[test]
+    fn cancel_during_transmit() {
+        let port = MockPort {
+            written: Vec::new(),
+            writable: 4,
+            input: Vec::new(),
+        };
+        let interface = RefCell::new(AsyncSerialInterface::new(port));
+        let module = TmcmModule::new(&interface, 1);
+
+        let mut first = module.write_command_async(ROR::new(0, 100));
+        let mut second = module.write_command_async(ROR::new(0, 200));
+        assert!(poll(&mut first).is_pending());
+        assert_eq!(interface.borrow().get_ref().written, vec![1, 1, 0, 0]);
+
+        // The rest of the first frame is written with a wrong checksum before the second command
+        drop(first);
+        interface.borrow_mut().get_mut().writable = usize::MAX;
+        assert!(poll(&mut second).is_pending());
+        let written = interface.borrow().get_ref().written.clone();
+        assert_eq!(written.len(), 18);
+        assert_eq!(written[..8], [1, 1, 0, 0, 0, 0, 0, 0]);
+        assert_ne!(written[8], calculate_checksum(&written[..8]));
+        assert_eq!(written[9..], Command::new(1, ROR::new(0, 200)).serialize());
+    }
+
+    #[test]
+    fn checked_write_async() {
+        let port = MockPort {
+            written: Vec::new(),
+            writable: usize::MAX,
+            input: Vec::new(),
+        };
+        let interface = RefCell::new(AsyncSerialInterface::new(port));
+        let module = GenericModule::new(&interface, 1);
+
+        let mut command = module.write_command_async(SAP::new(0, 154, [14, 0, 0, 0]));
+        assert!(matches!(
+            poll(&mut command),
+            Poll::Ready(Err(Error::InvalidParameterValue(154)))
+        ));
+        assert!(interface.borrow().get_ref().written.is_empty());
+    }
+}
@@ -3,10 +3,19 @@
 //! ## `axis_param` macros
 //! These macros works for most cases, if the register is represented by an enum or
 //! a type not implemented `Return` for these macros wont work.
+//!
+//! The macros take an optional range after the parameter number, e.g. `0..=13`. It is
+//! available as `is_valid` for asserting in the constructor, values outside of it fail to
+//! deserialize, and it is the range in the generated `metadata::Describe` implementation.
+//! Without a range the whole range of the value type is valid.
 
 macro_rules! axis_param_r {
     ($(#[$doc:meta])* $name:ident, $ty:ty, $number:expr) => {
-        axis_param_define!($(#[$doc])* $name, $ty, $number);
+        axis_param_define!($(#[$doc])* $name, $ty, $number, Read);
+        axis_param_define_read!($name, $ty);
+    };
+    ($(#[$doc:meta])* $name:ident, $ty:ty, $number:expr, $range:expr) => {
+        axis_param_define!(@checked $(#[$doc])* $name, $ty, $number, Read, $range);
         axis_param_define_read!($name, $ty);
     };
 }
@@ -20,18 +29,51 @@ macro_rules! axis_param_w {
 */
 macro_rules! axis_param_rw {
     ($(#[$doc:meta])* $name:ident, $ty:tt, $number:expr) => {
-        axis_param_define!($(#[$doc])* $name, $ty, $number);
+        axis_param_define!($(#[$doc])* $name, $ty, $number, ReadWrite);
+        axis_param_define_read!($name, $ty);
+        axis_param_define_write!($name, $ty);
+    };
+    ($(#[$doc:meta])* $name:ident, $ty:tt, $number:expr, $range:expr) => {
+        axis_param_define!(@checked $(#[$doc])* $name, $ty, $number, ReadWrite, $range);
         axis_param_define_read!($name, $ty);
         axis_param_define_write!($name, $ty);
     };
 }
 
 macro_rules! axis_param_define{
-    ($(#[$doc:meta])* $name:ident, $ty:ty, $number:expr) => {
+    ($(#[$doc:meta])* $name:ident, $ty:ty, $number:expr, $access:ident) => {
+        $(#[$doc])*
+        #[derive(Debug, PartialEq)]
+        #[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+        pub struct $name($ty);
+
+        axis_param_define!(@impl $name, $ty, $number);
+        describe!(
+            $name,
+            $access,
+            $ty,
+            <$ty as ::metadata::Value>::MIN,
+            <$ty as ::metadata::Value>::MAX
+        );
+    };
+    (@checked $(#[$doc:meta])* $name:ident, $ty:ty, $number:expr, $access:ident, $range:expr) => {
         $(#[$doc])*
         #[derive(Debug, PartialEq)]
+        #[cfg_attr(feature = "serde", derive(::serde::Serialize))]
         pub struct $name($ty);
 
+        param_is_valid!($name, $ty, |value| ($range).contains(&value));
+        deserialize_checked!($name, $ty);
+        axis_param_define!(@impl $name, $ty, $number);
+        describe!(
+            $name,
+            $access,
+            $ty,
+            *($range).start() as i64,
+            *($range).end() as i64
+        );
+    };
+    (@impl $name:ident, $ty:ty, $number:expr) => {
         impl From<$name> for $ty {
             fn from(v: $name) -> $ty {
                 v.0
@@ -44,6 +86,23 @@ macro_rules! axis_param_define{
     };
 }
 
+/// Implements `metadata::Describe` for `$name`, holding a `$ty`, optionally with the only
+/// valid `$values`.
+macro_rules! describe {
+    ($name:ident, $access:ident, $ty:ty, $min:expr, $max:expr) => {
+        describe!($name, $access, $ty, $min, $max, &[]);
+    };
+    ($name:ident, $access:ident, $ty:ty, $min:expr, $max:expr, $values:expr) => {
+        impl ::metadata::Describe for $name {
+            const ACCESS: ::metadata::Access = ::metadata::Access::$access;
+            const VALUE_TYPE: ::metadata::ValueType = <$ty as ::metadata::Value>::VALUE_TYPE;
+            const MIN: i64 = $min;
+            const MAX: i64 = $max;
+            const VALUES: &'static [i64] = $values;
+        }
+    };
+}
+
 macro_rules! axis_param_define_read {
     ($name:ident, $ty:ty) => {
         impl Return for $name {
@@ -56,63 +115,85 @@ macro_rules! axis_param_define_read {
 }
 
 macro_rules! axis_param_define_write {
-    ($name:ident, u32) => {
+    ($name:ident, $ty:tt) => {
         impl WriteableAxisParameter for $name {
             fn operand(&self) -> [u8; 4] {
-                [
-                    (self.0 >> 0) as u8,
-                    (self.0 >> 8) as u8,
-                    (self.0 >> 16) as u8,
-                    (self.0 >> 24) as u8,
-                ]
+                param_operand!(self.0, $ty)
             }
         }
     };
-    ($name:ident, u16) => {
-        impl WriteableAxisParameter for $name {
-            fn operand(&self) -> [u8; 4] {
-                [(self.0 >> 0) as u8, (self.0 >> 8) as u8, 0u8, 0u8]
+}
+
+/// Defines `is_valid`, returning the result of the closure `$valid` for a value of `$name`.
+macro_rules! param_is_valid {
+    ($name:ident, $ty:ty, $valid:expr) => {
+        impl $name {
+            /// Returns true if `value` is in the valid range of the parameter
+            pub fn is_valid(value: $ty) -> bool {
+                let valid: fn($ty) -> bool = $valid;
+                valid(value)
             }
         }
     };
-    ($name:ident, u8) => {
-        impl WriteableAxisParameter for $name {
-            fn operand(&self) -> [u8; 4] {
-                [(self.0 >> 0) as u8, 0u8, 0u8, 0u8]
+}
+
+/// Implements `Deserialize` for the newtype `$name` of `$ty` like the derive does, but
+/// rejects the values that `$name::is_valid` rejects.
+macro_rules! deserialize_checked {
+    ($name:ident, $ty:ty) => {
+        #[cfg(feature = "serde")]
+        impl<'de> ::serde::Deserialize<'de> for $name {
+            fn deserialize<D: ::serde::Deserializer<'de>>(
+                deserializer: D,
+            ) -> Result<Self, D::Error> {
+                #[derive(::serde::Deserialize)]
+                struct $name($ty);
+
+                let $name(value) = <$name as ::serde::Deserialize<'de>>::deserialize(deserializer)?;
+                if Self::is_valid(value) {
+                    Ok(Self(value))
+                } else {
+                    Err(::serde::de::Error::custom(concat!(
+                        "value out of range for ",
+                        stringify!($name)
+                    )))
+                }
             }
         }
     };
-    ($name:ident, i32) => {
-        impl WriteableAxisParameter for $name {
-            fn operand(&self) -> [u8; 4] {
-                [
-                    (self.0 >> 0) as u8,
-                    (self.0 >> 8) as u8,
-                    (self.0 >> 16) as u8,
-                    (self.0 >> 24) as u8,
-                ]
-            }
-        }
+}
+
+/// Serializes a parameter value of a primitive type into an operand.
+macro_rules! param_operand {
+    ($value:expr, u32) => {
+        [
+            $value as u8,
+            ($value >> 8) as u8,
+            ($value >> 16) as u8,
+            ($value >> 24) as u8,
+        ]
     };
-    ($name:ident, i16) => {
-        impl WriteableAxisParameter for $name {
-            fn operand(&self) -> [u8; 4] {
-                [(self.0 >> 0) as u8, (self.0 >> 8) as u8, 0u8, 0u8]
-            }
-        }
+    ($value:expr, u16) => {
+        [$value as u8, ($value >> 8) as u8, 0u8, 0u8]
     };
-    ($name:ident, i8) => {
-        impl WriteableAxisParameter for $name {
-            fn operand(&self) -> [u8; 4] {
-                [(self.0 >> 0) as u8, 0u8, 0u8, 0u8]
-            }
-        }
+    ($value:expr, u8) => {
+        [$value as u8, 0u8, 0u8, 0u8]
     };
-    ($name:ident, bool) => {
-        impl WriteableAxisParameter for $name {
-            fn operand(&self) -> [u8; 4] {
-                [self.0 as u8, 0, 0, 0]
-            }
-        }
+    ($value:expr, i32) => {
+        [
+            $value as u8,
+            ($value >> 8) as u8,
+            ($value >> 16) as u8,
+            ($value >> 24) as u8,
+        ]
+    };
+    ($value:expr, i16) => {
+        [$value as u8, ($value >> 8) as u8, 0u8, 0u8]
+    };
+    ($value:expr, i8) => {
+        [$value as u8, 0u8, 0u8, 0u8]
+    };
+    ($value:expr, bool) => {
+        [$value as u8, 0, 0, 0]
     };
 }
@@ -0,0 +1,342 @@
+use std::collections::HashMap;
+use std::marker::PhantomData;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use instructions::DirectInstruction;
+use Command;
+use Error;
+use Instruction;
+use Interface;
+use Reply;
+use Return;
+use Status;
+
+/// The id of the next `Bus`, to tell which bus a `Pending` belongs to.
+static NEXT_BUS_ID: AtomicUsize = AtomicUsize::new(0);
+
+/// A driver for many modules sharing one bus, with one command in flight per module address.
+///
+/// Commands are sent with `send` without waiting for the reply. The returned `Pending` is later
+/// passed to `receive`, which reads replies from the interface until the one from that module
+/// arrives. Replies from other modules with a command in flight are kept until they are received,
+/// all other replies are dropped. Replies are routed by the module address in the reply frame
+/// and must answer the command number of the command in flight.
+///
+/// The interface must return every reply from `receive_reply`, regardless of the last transmitted
+/// command. `CanInterface` does so after `set_match_command(false)`, and so do a bare `CANSocket`
+/// and `simulator::SimulatedBus`. `SerialInterface` only returns the reply to the last command,
+/// RS485 does not allow several commands in flight anyway. The interface's own timeout applies to
+/// every reply read.
+///
+/// ```
+/// # extern crate tmcl;
+/// use tmcl::modules::tmcm::axis_parameters::ActualPosition;
+/// use tmcl::modules::tmcm::instructions::GAP;
+/// use tmcl::{Bus, Error, Interface};
+///
+/// // Ask all modules for their position before waiting for any reply
+/// fn positions<IF: Interface>(bus: &mut Bus<IF>) -> Result<Vec<i32>, Error<IF::Error>> {
+///     let pending = (1..=10)
+///         .map(|address| bus.send(address, GAP::<ActualPosition>::new(0)))
+///         .collect::<Result<Vec<_>, _>>()?;
+///     pending
+///         .into_iter()
+///         .map(|pending| bus.receive(pending).map(i32::from))
+///         .collect()
+/// }
+/// # fn main() {}
+/// ```
+#[derive(Debug)]
+pub struct Bus<IF> {
+    interface: IF,
+    id: usize,
+    slots: HashMap<u8, Slot>,
+}
+
+#[derive(Debug)]
+enum Slot {
+    /// Waiting for the reply to the given command number
+    Waiting(u8),
+    Received(Reply),
+}
+
+/// A command in flight on a `Bus`, pass it to `Bus::receive` to get its return value.
+#[derive(Debug)]
+#[must_use = "the module address stays busy until the reply is received or cancelled"]
+pub struct Pending<I: Instruction> {
+    bus: usize,
+    address: u8,
+    pd: PhantomData<I>,
+}
+
+impl<I: Instruction> Pending<I> {
+    /// Returns the address of the module the command was sent to
+    pub fn module_address(&self) -> u8 {
+        self.address
+    }
+}
+
+impl<IF: Interface> Bus<IF> {
+    /// Create a new bus driver on the given interface
+    pub fn new(interface: IF) -> Self {
+        Bus {
+            interface,
+            id: NEXT_BUS_ID.fetch_add(1, Ordering::Relaxed),
+            slots: HashMap::new(),
+        }
+    }
+
+    /// Returns a reference to the underlying interface
+    pub fn get_ref(&self) -> &IF {
+        &self.interface
+    }
+
+    /// Returns a mutable reference to the underlying interface
+    pub fn get_mut(&mut self) -> &mut IF {
+        &mut self.interface
+    }
+
+    /// Release the underlying interface
+    pub fn release(self) -> IF {
+        self.interface
+    }
+
+    /// Send a command to the module at `address` without waiting for the reply.
+    ///
+    /// Returns `Error::CommandPending` if a command to this module is still in flight.
+    pub fn send<I: DirectInstruction>(
+        &mut self,
+        address: u8,
+        instruction: I,
+    ) -> Result<Pending<I>, Error<IF::Error>> {
+        if self.slots.contains_key(&address) {
+            return Err(Error::CommandPending(address));
+        }
+        self.interface
+            .transmit_command(&Command::new(address, instruction))
+            .map_err(Error::InterfaceError)?;
+        self.slots
+            .insert(address, Slot::Waiting(I::INSTRUCTION_NUMBER));
+        Ok(Pending {
+            bus: self.id,
+            address,
+            pd: PhantomData,
+        })
+    }
+
+    /// Wait for the reply to a command sent with `send`.
+    ///
+    /// The module address is free for a new command afterwards, also when an error is returned.
+    /// Returns `Error::NoCommandPending` if the command was not sent on this bus.
+    pub fn receive<I: DirectInstruction>(
+        &mut self,
+        pending: Pending<I>,
+    ) -> Result<I::Return, Error<IF::Error>> {
+        let address = pending.address;
+        if pending.bus != self.id || !self.slots.contains_key(&address) {
+            return Err(Error::NoCommandPending(address));
+        }
+        let reply = loop {
+            if let Some(Slot::Received(_)) = self.slots.get(&address) {
+                match self.slots.remove(&address) {
+                    Some(Slot::Received(reply)) => break reply,
+                    _ => unreachable!(),
+                }
+            }
+            let reply = match self.interface.receive_reply() {
+                Ok(reply) => reply,
+                Err(e) => {
+                    self.slots.remove(&address);
+                    return Err(Error::InterfaceError(e));
+                }
+            };
+            self.route(reply);
+        };
+        match reply.status() {
+            Status::Ok(_) => Ok(<I::Return as Return>::from_operand(reply.operand())),
+            Status::Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Send a command and wait for its reply.
+    pub fn write_command<I: DirectInstruction>(
+        &mut self,
+        address: u8,
+        instruction: I,
+    ) -> Result<I::Return, Error<IF::Error>> {
+        let pending = self.send(address, instruction)?;
+        self.receive(pending)
+    }
+
+    /// Give up on a command in flight, a late reply to it is dropped.
+    ///
+    /// Does nothing if the command was not sent on this bus.
+    pub fn cancel<I: Instruction>(&mut self, pending: Pending<I>) {
+        if pending.bus == self.id {
+            self.slots.remove(&pending.address);
+        }
+    }
+
+    /// Returns the number of commands in flight
+    pub fn pending(&self) -> usize {
+        self.slots.len()
+    }
+
+    /// Store `reply` for the command in flight it answers, or drop it.
+    fn route(&mut self, reply: Reply) {
+        if let Some(slot) = self.slots.get_mut(&reply.module_address()) {
+            if let Slot::Waiting(command_number) = *slot {
+                if reply.command_number() == command_number {
+                    *slot = Slot::Received(reply);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::collections::VecDeque;
+
+    use modules::tmcm::axis_parameters::ActualPosition;
+    use modules::tmcm::instructions::{GAP, ROR, SAP};
+    use simulator::{SimulatedBus, SimulatedModule};
+    use ErrStatus;
+    use OkStatus;
+
+    /// Queues a reply to every command, replying in reverse order of transmission.
+    struct MockBus {
+        transmitted: Vec<u8>,
+        queued: Vec<Reply>,
+        replies: VecDeque<Reply>,
+    }
+
+    impl MockBus {
+        fn new() -> Self {
+            MockBus {
+                transmitted: Vec::new(),
+                queued: Vec::new(),
+                replies: VecDeque::new(),
+            }
+        }
+    }
+
+    impl Interface for MockBus {
+        type Error = ();
+
+        fn transmit_command<T: Instruction>(&mut self, command: &Command<T>) -> Result<(), ()> {
+            let address = command.module_address();
+            self.transmitted.push(address);
+            let status = if address == 13 {
+                Status::Err(ErrStatus::InvalidValue)
+            } else {
+                Status::Ok(OkStatus::Ok)
+            };
+            self.queued.push(Reply::new(
+                2,
+                address,
+                status,
+                T::INSTRUCTION_NUMBER,
+                [address, 0, 0, 0],
+            ));
+            Ok(())
+        }
+
+        fn receive_reply(&mut self) -> Result<Reply, ()> {
+            if self.replies.is_empty() {
+                self.replies.extend(self.queued.drain(..).rev());
+            }
+            self.replies.pop_front().ok_or(())
+        }
+    }
+
+    #[test]
+    fn out_of_order_replies() {
+        let mut bus = Bus::new(MockBus::new());
+
+        let pending: Vec<_> = (1..=10)
+            .map(|address| bus.send(address, GAP::<ActualPosition>::new(0)).unwrap())
+            .collect();
+        assert_eq!(bus.get_ref().transmitted, (1..=10).collect::<Vec<_>>());
+        assert_eq!(bus.pending(), 10);
+
+        for pending in pending {
+            let address = pending.module_address();
+            assert_eq!(bus.receive(pending).map(i32::from), Ok(i32::from(address)));
+        }
+        assert_eq!(bus.pending(), 0);
+    }
+
+    #[test]
+    fn one_command_per_address() {
+        let mut bus = Bus::new(MockBus::new());
+
+        let first = bus.send(1, ROR::new(0, 100)).unwrap();
+        assert_eq!(
+            bus.send(1, ROR::new(0, 200)).map(|_| ()),
+            Err(Error::CommandPending(1))
+        );
+        assert_eq!(bus.get_ref().transmitted.len(), 1);
+
+        bus.cancel(first);
+        assert_eq!(bus.write_command(1, ROR::new(0, 200)), Ok(()));
+        assert_eq!(
+            bus.write_command(13, ROR::new(0, 200)),
+            Err(Error::ProtocolError(ErrStatus::InvalidValue))
+        );
+        assert_eq!(bus.pending(), 0);
+    }
+
+    #[test]
+    fn simulated_bus() {
+        let modules = (1..=3)
+            .rev()
+            .map(|address| SimulatedModule::new(address, 1));
+        let mut bus = Bus::new(SimulatedBus::new(modules.collect()));
+        for address in 1..=3 {
+            let position = ActualPosition::new(100 * i32::from(address));
+            bus.write_command(address, SAP::new(0, position)).unwrap();
+        }
+
+        let pending: Vec<_> = (1..=3)
+            .map(|address| bus.send(address, GAP::<ActualPosition>::new(0)).unwrap())
+            .collect();
+        let positions = pending
+            .into_iter()
+            .map(|pending| bus.receive(pending).map(i32::from))
+            .collect::<Result<Vec<_>, _>>();
+        assert_eq!(positions, Ok(vec![100, 200, 300]));
+    }
+
+    #[test]
+    fn foreign_pending() {
+        let mut first = Bus::new(MockBus::new());
+        let mut second = Bus::new(MockBus::new());
+
+        let pending = first.send(4, ROR::new(0, 100)).unwrap();
+        assert_eq!(second.receive(pending), Err(Error::NoCommandPending(4)));
+        assert!(second.get_ref().transmitted.is_empty());
+        assert_eq!(first.pending(), 1);
+
+        // A different command is in flight at the same address on the other bus
+        let in_flight = second.send(4, ROR::new(0, 100)).unwrap();
+        let pending = first.send(5, GAP::<ActualPosition>::new(0)).unwrap();
+        let pending = Pending::<GAP<ActualPosition>> {
+            address: 4,
+            ..pending
+        };
+        assert_eq!(
+            second.receive(pending).map(i32::from),
+            Err(Error::NoCommandPending(4))
+        );
+        let pending = first.send(6, GAP::<ActualPosition>::new(0)).unwrap();
+        second.cancel(Pending {
+            address: 4,
+            ..pending
+        });
+        assert_eq!(second.pending(), 1);
+        assert_eq!(second.receive(in_flight), Ok(()));
+    }
+}
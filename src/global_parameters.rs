@@ -0,0 +1,142 @@
+//! Tools for implementing global parameters.
+//!
+//! ## `global_param` macros
+//! These macros mirror the `axis_param` macros, but also take the bank number
+//! the parameter is located in. If the parameter is represented by an enum or
+//! a type not implemented `Return` for these macros wont work. Like `axis_param_rw`,
+//! `global_param_rw` takes an optional range of valid values.
+//!
+//! ## `user_variables` macro
+//! Defines the general purpose variables in bank 2 for a module family, with the
+//! variable number as const generic parameter. Using a number above the highest
+//! variable of the family fails to compile. Without a highest variable all 256
+//! numbers are valid.
+
+macro_rules! global_param_rw {
+    ($(#[$doc:meta])* $name:ident, $ty:tt, $bank:expr, $number:expr) => {
+        global_param_define!($(#[$doc])* $name, $ty, $bank, $number);
+        global_param_define_read!($name, $ty);
+        global_param_define_write!($name, $ty);
+    };
+    ($(#[$doc:meta])* $name:ident, $ty:tt, $bank:expr, $number:expr, $range:expr) => {
+        global_param_define!(@checked $(#[$doc])* $name, $ty, $bank, $number, $range);
+        global_param_define_read!($name, $ty);
+        global_param_define_write!($name, $ty);
+    };
+}
+
+macro_rules! global_param_define{
+    ($(#[$doc:meta])* $name:ident, $ty:ty, $bank:expr, $number:expr) => {
+        $(#[$doc])*
+        #[derive(Debug, PartialEq)]
+        #[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+        pub struct $name($ty);
+
+        global_param_define!(@impl $name, $ty, $bank, $number);
+        describe!(
+            $name,
+            ReadWrite,
+            $ty,
+            <$ty as ::metadata::Value>::MIN,
+            <$ty as ::metadata::Value>::MAX
+        );
+    };
+    (@checked $(#[$doc:meta])* $name:ident, $ty:ty, $bank:expr, $number:expr, $range:expr) => {
+        $(#[$doc])*
+        #[derive(Debug, PartialEq)]
+        #[cfg_attr(feature = "serde", derive(::serde::Serialize))]
+        pub struct $name($ty);
+
+        param_is_valid!($name, $ty, |value| ($range).contains(&value));
+        deserialize_checked!($name, $ty);
+        global_param_define!(@impl $name, $ty, $bank, $number);
+        describe!(
+            $name,
+            ReadWrite,
+            $ty,
+            *($range).start() as i64,
+            *($range).end() as i64
+        );
+    };
+    (@impl $name:ident, $ty:ty, $bank:expr, $number:expr) => {
+        impl From<$name> for $ty {
+            fn from(v: $name) -> $ty {
+                v.0
+            }
+        }
+
+        impl GlobalParameter for $name {
+            const BANK: u8 = $bank;
+            const NUMBER: u8 = $number;
+        }
+    };
+}
+
+macro_rules! global_param_define_read {
+    ($name:ident, $ty:ty) => {
+        impl Return for $name {
+            fn from_operand(operand: [u8; 4]) -> Self {
+                $name(<$ty as Return>::from_operand(operand))
+            }
+        }
+        impl ReadableGlobalParameter for $name {}
+    };
+}
+
+macro_rules! global_param_define_write {
+    ($name:ident, $ty:tt) => {
+        impl WriteableGlobalParameter for $name {
+            fn operand(&self) -> [u8; 4] {
+                param_operand!(self.0, $ty)
+            }
+        }
+    };
+}
+
+macro_rules! user_variables {
+    ($(#[$doc:meta])* $name:ident) => {
+        user_variables!(@define $(#[$doc])* $name, N);
+    };
+    ($(#[$doc:meta])* $name:ident, $max:expr) => {
+        user_variables!(@define $(#[$doc])* $name, {
+            assert!(N <= $max, "user variable number out of range");
+            N
+        });
+    };
+    (@define $(#[$doc:meta])* $name:ident, $number:expr) => {
+        $(#[$doc])*
+        #[derive(Debug, PartialEq, Clone, Copy)]
+        #[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+        pub struct $name<const N: u8>(i32);
+
+        impl<const N: u8> $name<N> {
+            pub fn new(value: i32) -> Self {
+                $name(value)
+            }
+        }
+
+        impl<const N: u8> From<$name<N>> for i32 {
+            fn from(v: $name<N>) -> i32 {
+                v.0
+            }
+        }
+
+        impl<const N: u8> GlobalParameter for $name<N> {
+            const BANK: u8 = 2;
+            const NUMBER: u8 = $number;
+        }
+
+        impl<const N: u8> Return for $name<N> {
+            fn from_operand(operand: [u8; 4]) -> Self {
+                $name(<i32 as Return>::from_operand(operand))
+            }
+        }
+        impl<const N: u8> ReadableGlobalParameter for $name<N> {}
+
+        impl<const N: u8> WriteableGlobalParameter for $name<N> {
+            fn operand(&self) -> [u8; 4] {
+                param_operand!(self.0, i32)
+            }
+        }
+    };
+}
@@ -0,0 +1,136 @@
+use embedded_hal::i2c::I2c;
+
+use Command;
+use Instruction;
+use Interface;
+use ParseError;
+use Reply;
+
+/// An `Interface` for TMCM modules connected to an I2C bus.
+///
+/// The module address of a `Command` is used as the 7-bit I2C address of the module.
+/// The reply is read from the module that was last sent a command.
+#[derive(Debug)]
+pub struct I2cInterface<I2C> {
+    bus: I2C,
+    address: Option<u8>,
+}
+
+/// All possible errors when communicating over I2C.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum I2cError<E> {
+    /// The I2C bus had an error.
+    BusError(E),
+
+    /// The reply read from the module was not valid.
+    ParseError(ParseError),
+
+    /// A reply was requested before any command was transmitted.
+    NoCommandTransmitted,
+}
+
+impl<I2C: I2c> I2cInterface<I2C> {
+    /// Create a new interface on the given I2C bus
+    pub fn new(bus: I2C) -> Self {
+        I2cInterface { bus, address: None }
+    }
+
+    /// Release the underlying I2C bus
+    pub fn release(self) -> I2C {
+        self.bus
+    }
+}
+
+impl<I2C: I2c> Interface for I2cInterface<I2C> {
+    type Error = I2cError<I2C::Error>;
+
+    fn transmit_command<T: Instruction>(
+        &mut self,
+        command: &Command<T>,
+    ) -> Result<(), Self::Error> {
+        self.address = Some(command.module_address());
+        self.bus
+            .write(command.module_address(), &command.serialize_i2c())
+            .map_err(I2cError::BusError)
+    }
+
+    fn receive_reply(&mut self) -> Result<Reply, Self::Error> {
+        let address = self.address.ok_or(I2cError::NoCommandTransmitted)?;
+        let mut data = [0u8; 8];
+        self.bus
+            .read(address, &mut data)
+            .map_err(I2cError::BusError)?;
+        Reply::deserialize_i2c(address, &data).map_err(I2cError::ParseError)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use embedded_hal::i2c::{ErrorKind, ErrorType, Operation};
+
+    use instructions::ROR;
+    use OkStatus;
+    use Status;
+
+    struct MockBus {
+        written: Option<(u8, [u8; 8])>,
+        reply: [u8; 8],
+    }
+
+    impl ErrorType for MockBus {
+        type Error = ErrorKind;
+    }
+
+    impl I2c for MockBus {
+        fn transaction(
+            &mut self,
+            address: u8,
+            operations: &mut [Operation],
+        ) -> Result<(), Self::Error> {
+            for operation in operations {
+                match operation {
+                    Operation::Write(bytes) => {
+                        let mut frame = [0u8; 8];
+                        frame.copy_from_slice(bytes);
+                        self.written = Some((address, frame));
+                    }
+                    Operation::Read(buffer) => buffer.copy_from_slice(&self.reply),
+                }
+            }
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn command_and_reply() {
+        let mut interface = I2cInterface::new(MockBus {
+            written: None,
+            reply: [5, 100, 1, 0, 0, 0, 0, 106],
+        });
+
+        interface
+            .transmit_command(&Command::new(5, ROR::new(0, 250)))
+            .unwrap();
+        assert_eq!(
+            interface.bus.written,
+            Some((5, [1, 0, 0, 0, 0, 0, 250, 251]))
+        );
+
+        let reply = interface.receive_reply().unwrap();
+        assert_eq!(reply.status(), Status::Ok(OkStatus::Ok));
+    }
+
+    #[test]
+    fn reply_before_command() {
+        let mut interface = I2cInterface::new(MockBus {
+            written: None,
+            reply: [0u8; 8],
+        });
+        assert_eq!(
+            interface.receive_reply(),
+            Err(I2cError::NoCommandTransmitted)
+        );
+    }
+}
@@ -5,7 +5,10 @@ use core::marker::PhantomData;
 #[cfg(feature = "std")]
 use std::marker::PhantomData;
 
+use lib::convert::TryFrom;
+use ParseError;
 use {ReadableAxisParameter, WriteableAxisParameter};
+use {ReadableGlobalParameter, WriteableGlobalParameter};
 
 /// A `TMCL` `Instruction`
 pub trait Instruction {
@@ -47,6 +50,7 @@ pub trait Return {
 ///
 /// This instruction starts rotation in "right" direction, i.e. increasing the position counter.
 #[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
 pub struct ROR {
     motor_number: u8,
     velocity: u32,
@@ -63,12 +67,12 @@ impl Instruction for ROR {
     const INSTRUCTION_NUMBER: u8 = 1;
 
     fn operand(&self) -> [u8; 4] {
-        return [
+        [
             (self.velocity & 0xff) as u8,
             ((self.velocity >> 8) & 0xff) as u8,
             ((self.velocity >> 16) & 0xff) as u8,
             ((self.velocity >> 24) & 0xff) as u8,
-        ];
+        ]
     }
 
     fn type_number(&self) -> u8 {
@@ -87,6 +91,7 @@ impl DirectInstruction for ROR {
 ///
 /// This instruction starts rotation in "left" direction, i.e. decreasing the position counter.
 #[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
 pub struct ROL {
     motor_number: u8,
     velocity: u32,
@@ -103,12 +108,12 @@ impl Instruction for ROL {
     const INSTRUCTION_NUMBER: u8 = 2;
 
     fn operand(&self) -> [u8; 4] {
-        return [
+        [
             (self.velocity & 0xff) as u8,
             ((self.velocity >> 8) & 0xff) as u8,
             ((self.velocity >> 16) & 0xff) as u8,
             ((self.velocity >> 24) & 0xff) as u8,
-        ];
+        ]
     }
 
     fn type_number(&self) -> u8 {
@@ -127,6 +132,7 @@ impl DirectInstruction for ROL {
 ///
 /// This instruction stops the motor.
 #[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
 pub struct MST {
     motor_number: u8,
 }
@@ -139,7 +145,7 @@ impl Instruction for MST {
     const INSTRUCTION_NUMBER: u8 = 3;
 
     fn operand(&self) -> [u8; 4] {
-        return [0, 0, 0, 0];
+        [0, 0, 0, 0]
     }
 
     fn type_number(&self) -> u8 {
@@ -156,6 +162,7 @@ impl DirectInstruction for MST {
 
 /// The type and value of a `MVP` instruction
 #[derive(Debug, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
 pub enum MoveOperation {
     /// Moving to an absolute position in the range from -8388608 to +8388608 (-2^23 to +2^23).
     Absolute(i32),
@@ -179,10 +186,39 @@ pub enum MoveOperation {
 /// A movement towards the specified position is started, with automatic generation of acceleration
 /// and deceleration ramps. The maximum velocity and acceleration are defined by axis parameters #4 and #5.
 #[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize))]
 pub struct MVP {
     motor_number: u8,
     value: MoveOperation,
 }
+/// Rejects interpolated moves with a motor mask `MVP::interpolated` does not accept.
+#[cfg(feature = "serde")]
+impl<'de> ::serde::Deserialize<'de> for MVP {
+    fn deserialize<D: ::serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(::serde::Deserialize)]
+        #[serde(rename = "MVP")]
+        struct Unchecked {
+            motor_number: u8,
+            value: MoveOperation,
+        }
+
+        let Unchecked {
+            motor_number,
+            value,
+        } = <Unchecked as ::serde::Deserialize<'de>>::deserialize(deserializer)?;
+        match value {
+            MoveOperation::Coordinate(_) if motor_number & 0x40 != 0 && motor_number >= 0x80 => {
+                Err(::serde::de::Error::custom(
+                    "motor mask out of range for MVP",
+                ))
+            }
+            _ => Ok(Self {
+                motor_number,
+                value,
+            }),
+        }
+    }
+}
 impl MVP {
     pub fn new(motor_number: u8, value: MoveOperation) -> MVP {
         MVP {
@@ -190,6 +226,17 @@ impl MVP {
             value,
         }
     }
+
+    /// Move all motors in `motor_mask` to a stored coordinate with interpolation
+    ///
+    /// Bit n of the mask selects motor n. Only available on modules with more than one motor.
+    pub fn interpolated(motor_mask: u8, coordinate: u32) -> MVP {
+        assert!(motor_mask < 0x40);
+        MVP {
+            motor_number: 0x40 | motor_mask,
+            value: MoveOperation::Coordinate(coordinate),
+        }
+    }
 }
 impl Instruction for MVP {
     const INSTRUCTION_NUMBER: u8 = 4;
@@ -218,7 +265,11 @@ impl Instruction for MVP {
     }
 
     fn type_number(&self) -> u8 {
-        0
+        match self.value {
+            MoveOperation::Absolute(_) => 0,
+            MoveOperation::Relative(_) => 1,
+            MoveOperation::Coordinate(_) => 2,
+        }
     }
 
     fn motor_bank_number(&self) -> u8 {
@@ -236,6 +287,7 @@ impl DirectInstruction for MVP {
 /// and physical locations (TMC428, TMC453, controller RAM, controller EEPROM),
 /// they all can be set by this function.
 #[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
 pub struct SAP<T: WriteableAxisParameter> {
     motor_number: u8,
     axis_parameter: T,
@@ -274,6 +326,7 @@ impl<T: WriteableAxisParameter> DirectInstruction for SAP<T> {
 /// and physical locations (TMC428, TMC453, controller RAM, controller EEPROM),
 /// they all can be read by this function.
 #[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
 pub struct GAP<T: ReadableAxisParameter> {
     motor_number: u8,
     phantom: PhantomData<T>,
@@ -310,6 +363,7 @@ impl<T: ReadableAxisParameter> DirectInstruction for GAP<T> {
 /// Axis parameters are located in RAM memory, so modifications are lost at power down.
 /// This instruction enables permanent storing.
 #[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
 pub struct STAP<T: WriteableAxisParameter> {
     motor_number: u8,
     phantom: PhantomData<T>,
@@ -347,6 +401,7 @@ impl<T: WriteableAxisParameter> DirectInstruction for STAP<T> {
 /// By default, most parameters are automatically restored after power up (see axis parameter list in
 /// chapter 4). A single parameter that has been changed before can be reset by this instruction.
 #[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
 pub struct RSAP<T: WriteableAxisParameter> {
     motor_number: u8,
     phantom: PhantomData<T>,
@@ -378,8 +433,163 @@ impl<T: WriteableAxisParameter> DirectInstruction for RSAP<T> {
     type Return = ();
 }
 
+/// SGP - Set Global Parameter
+///
+/// Global parameters are related to the host interface, peripherals or application specific
+/// variables. The different groups of these parameters are organized in "banks" to allow a larger
+/// total number for future products. Currently, only bank 0 and 1 are used for global parameters,
+/// and bank 2 is used for user variables.
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub struct SGP<T: WriteableGlobalParameter> {
+    global_parameter: T,
+}
+impl<T: WriteableGlobalParameter> SGP<T> {
+    pub fn new(global_parameter: T) -> SGP<T> {
+        SGP { global_parameter }
+    }
+}
+impl<T: WriteableGlobalParameter> Instruction for SGP<T> {
+    const INSTRUCTION_NUMBER: u8 = 9;
+
+    fn operand(&self) -> [u8; 4] {
+        self.global_parameter.operand()
+    }
+
+    fn type_number(&self) -> u8 {
+        T::NUMBER
+    }
+
+    fn motor_bank_number(&self) -> u8 {
+        T::BANK
+    }
+}
+impl<T: WriteableGlobalParameter> DirectInstruction for SGP<T> {
+    type Return = ();
+}
+
+/// GGP - Get Global Parameter
+///
+/// All global parameters can be read with this function.
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub struct GGP<T: ReadableGlobalParameter> {
+    phantom: PhantomData<T>,
+}
+impl<T: ReadableGlobalParameter> GGP<T> {
+    pub fn new() -> GGP<T> {
+        GGP {
+            phantom: PhantomData,
+        }
+    }
+}
+impl<T: ReadableGlobalParameter> Default for GGP<T> {
+    fn default() -> GGP<T> {
+        GGP::new()
+    }
+}
+impl<T: ReadableGlobalParameter> Instruction for GGP<T> {
+    const INSTRUCTION_NUMBER: u8 = 10;
+
+    fn operand(&self) -> [u8; 4] {
+        [0u8, 0u8, 0u8, 0u8]
+    }
+
+    fn type_number(&self) -> u8 {
+        T::NUMBER
+    }
+
+    fn motor_bank_number(&self) -> u8 {
+        T::BANK
+    }
+}
+impl<T: ReadableGlobalParameter> DirectInstruction for GGP<T> {
+    type Return = T;
+}
+
+/// STGP - Store Global Parameter
+///
+/// Some global parameters are located in RAM memory, so modifications are lost at power down.
+/// This instruction enables permanent storing.
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub struct STGP<T: WriteableGlobalParameter> {
+    phantom: PhantomData<T>,
+}
+impl<T: WriteableGlobalParameter> STGP<T> {
+    pub fn new() -> STGP<T> {
+        STGP {
+            phantom: PhantomData,
+        }
+    }
+}
+impl<T: WriteableGlobalParameter> Default for STGP<T> {
+    fn default() -> STGP<T> {
+        STGP::new()
+    }
+}
+impl<T: WriteableGlobalParameter> Instruction for STGP<T> {
+    const INSTRUCTION_NUMBER: u8 = 11;
+
+    fn operand(&self) -> [u8; 4] {
+        [0u8, 0u8, 0u8, 0u8]
+    }
+
+    fn type_number(&self) -> u8 {
+        T::NUMBER
+    }
+
+    fn motor_bank_number(&self) -> u8 {
+        T::BANK
+    }
+}
+impl<T: WriteableGlobalParameter> DirectInstruction for STGP<T> {
+    type Return = ();
+}
+
+/// RSGP - Restore Global Parameter
+///
+/// With this command the contents of a global parameter can be restored from its
+/// non-volatile memory location.
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub struct RSGP<T: WriteableGlobalParameter> {
+    phantom: PhantomData<T>,
+}
+impl<T: WriteableGlobalParameter> RSGP<T> {
+    pub fn new() -> RSGP<T> {
+        RSGP {
+            phantom: PhantomData,
+        }
+    }
+}
+impl<T: WriteableGlobalParameter> Default for RSGP<T> {
+    fn default() -> RSGP<T> {
+        RSGP::new()
+    }
+}
+impl<T: WriteableGlobalParameter> Instruction for RSGP<T> {
+    const INSTRUCTION_NUMBER: u8 = 12;
+
+    fn operand(&self) -> [u8; 4] {
+        [0u8, 0u8, 0u8, 0u8]
+    }
+
+    fn type_number(&self) -> u8 {
+        T::NUMBER
+    }
+
+    fn motor_bank_number(&self) -> u8 {
+        T::BANK
+    }
+}
+impl<T: WriteableGlobalParameter> DirectInstruction for RSGP<T> {
+    type Return = ();
+}
+
 /// Choses what action to execute with the `RFS` instruction
 #[derive(Debug, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
 pub enum ReferenceSearchAction {
     /// Start reference search
     Start = 0,
@@ -399,6 +609,7 @@ pub enum ReferenceSearchAction {
 /// reference search algorithm to meet your needs. The reference search can be started or stop
 /// ped, or the actual status of the reference search can be checked.
 #[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
 pub struct RFS {
     motor_number: u8,
     action: ReferenceSearchAction,
@@ -435,6 +646,7 @@ impl DirectInstruction for RFS {
 ///
 /// This command sets the status of a digital output either to low (0) or to high (1).
 #[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
 pub struct SIO {
     bank_number: u8,
     port_number: u8,
@@ -476,6 +688,7 @@ impl DirectInstruction for SIO {
 /// as conditioned jumps. In  direct  mode the value is only output in the “value” field of the reply,
 /// without affecting the accumulator. The actual status of a digital output line can also be read.
 #[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
 pub struct GIO {
     bank_number: u8,
     port_number: u8,
@@ -509,6 +722,7 @@ impl DirectInstruction for GIO {
 
 /// CALC - Calculate
 #[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
 pub enum CALC {
     /// Add the operand to the accumulator
     Add(i32),
@@ -546,61 +760,16 @@ impl Instruction for CALC {
 
     fn operand(&self) -> [u8; 4] {
         match self {
-            CALC::Add(x) => [
-                (x >> 0) as u8,
-                (x >> 8) as u8,
-                (x >> 16) as u8,
-                (x >> 24) as u8,
-            ],
-            CALC::Sub(x) => [
-                (x >> 0) as u8,
-                (x >> 8) as u8,
-                (x >> 16) as u8,
-                (x >> 24) as u8,
-            ],
-            CALC::Mul(x) => [
-                (x >> 0) as u8,
-                (x >> 8) as u8,
-                (x >> 16) as u8,
-                (x >> 24) as u8,
-            ],
-            CALC::Div(x) => [
-                (x >> 0) as u8,
-                (x >> 8) as u8,
-                (x >> 16) as u8,
-                (x >> 24) as u8,
-            ],
-            CALC::Mod(x) => [
-                (x >> 0) as u8,
-                (x >> 8) as u8,
-                (x >> 16) as u8,
-                (x >> 24) as u8,
-            ],
-            CALC::And(x) => [
-                (x >> 0) as u8,
-                (x >> 8) as u8,
-                (x >> 16) as u8,
-                (x >> 24) as u8,
-            ],
-            CALC::Or(x) => [
-                (x >> 0) as u8,
-                (x >> 8) as u8,
-                (x >> 16) as u8,
-                (x >> 24) as u8,
-            ],
-            CALC::Xor(x) => [
-                (x >> 0) as u8,
-                (x >> 8) as u8,
-                (x >> 16) as u8,
-                (x >> 24) as u8,
-            ],
+            CALC::Add(x) => [*x as u8, (x >> 8) as u8, (x >> 16) as u8, (x >> 24) as u8],
+            CALC::Sub(x) => [*x as u8, (x >> 8) as u8, (x >> 16) as u8, (x >> 24) as u8],
+            CALC::Mul(x) => [*x as u8, (x >> 8) as u8, (x >> 16) as u8, (x >> 24) as u8],
+            CALC::Div(x) => [*x as u8, (x >> 8) as u8, (x >> 16) as u8, (x >> 24) as u8],
+            CALC::Mod(x) => [*x as u8, (x >> 8) as u8, (x >> 16) as u8, (x >> 24) as u8],
+            CALC::And(x) => [*x as u8, (x >> 8) as u8, (x >> 16) as u8, (x >> 24) as u8],
+            CALC::Or(x) => [*x as u8, (x >> 8) as u8, (x >> 16) as u8, (x >> 24) as u8],
+            CALC::Xor(x) => [*x as u8, (x >> 8) as u8, (x >> 16) as u8, (x >> 24) as u8],
             CALC::Not => [0u8, 0u8, 0u8, 0u8],
-            CALC::Load(x) => [
-                (x >> 0) as u8,
-                (x >> 8) as u8,
-                (x >> 16) as u8,
-                (x >> 24) as u8,
-            ],
+            CALC::Load(x) => [*x as u8, (x >> 8) as u8, (x >> 16) as u8, (x >> 24) as u8],
         }
     }
 
@@ -626,3 +795,1493 @@ impl Instruction for CALC {
 impl DirectInstruction for CALC {
     type Return = ();
 }
+
+/// COMP - Compare
+///
+/// The specified number is compared to the value in the accumulator register. The result of the
+/// comparison can for example be used by the conditional jump (JC) instruction. This command is
+/// intended for use in standalone operation only.
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub struct COMP {
+    value: i32,
+}
+impl COMP {
+    pub fn new(value: i32) -> COMP {
+        COMP { value }
+    }
+}
+impl Instruction for COMP {
+    const INSTRUCTION_NUMBER: u8 = 20;
+
+    fn operand(&self) -> [u8; 4] {
+        [
+            self.value as u8,
+            (self.value >> 8) as u8,
+            (self.value >> 16) as u8,
+            (self.value >> 24) as u8,
+        ]
+    }
+
+    fn type_number(&self) -> u8 {
+        0
+    }
+
+    fn motor_bank_number(&self) -> u8 {
+        0
+    }
+}
+
+/// The condition of a `JC` instruction
+#[derive(Debug, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub enum JumpCondition {
+    /// ZE - The accumulator is zero
+    Zero = 0,
+    /// NZ - The accumulator is not zero
+    NotZero = 1,
+    /// EQ - The last comparison was equal
+    Equal = 2,
+    /// NE - The last comparison was not equal
+    NotEqual = 3,
+    /// GT - The last comparison was greater
+    Greater = 4,
+    /// GE - The last comparison was greater or equal
+    GreaterOrEqual = 5,
+    /// LT - The last comparison was lower
+    Lower = 6,
+    /// LE - The last comparison was lower or equal
+    LowerOrEqual = 7,
+    /// ETO - A WAIT instruction timed out
+    TimeoutError = 8,
+    /// EAL - External alarm
+    ExternalAlarm = 9,
+    /// EDV - Deviation error
+    DeviationError = 10,
+    /// EPO - Position error
+    PositionError = 11,
+}
+
+/// JC - Jump Conditional
+///
+/// The JC instruction enables a conditional jump to a fixed address in the TMCL program memory,
+/// if the specified condition is met. The conditions refer to the result of a preceding
+/// comparison. This command is intended for use in standalone operation only.
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub struct JC {
+    condition: JumpCondition,
+    address: u32,
+}
+impl JC {
+    pub fn new(condition: JumpCondition, address: u32) -> JC {
+        JC { condition, address }
+    }
+}
+impl Instruction for JC {
+    const INSTRUCTION_NUMBER: u8 = 21;
+
+    fn operand(&self) -> [u8; 4] {
+        [
+            self.address as u8,
+            (self.address >> 8) as u8,
+            (self.address >> 16) as u8,
+            (self.address >> 24) as u8,
+        ]
+    }
+
+    fn type_number(&self) -> u8 {
+        self.condition as u8
+    }
+
+    fn motor_bank_number(&self) -> u8 {
+        0
+    }
+}
+
+/// JA - Jump Always
+///
+/// The TMCL program jumps unconditionally to a fixed address in the TMCL program memory.
+/// This command is intended for use in standalone operation only.
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub struct JA {
+    address: u32,
+}
+impl JA {
+    pub fn new(address: u32) -> JA {
+        JA { address }
+    }
+}
+impl Instruction for JA {
+    const INSTRUCTION_NUMBER: u8 = 22;
+
+    fn operand(&self) -> [u8; 4] {
+        [
+            self.address as u8,
+            (self.address >> 8) as u8,
+            (self.address >> 16) as u8,
+            (self.address >> 24) as u8,
+        ]
+    }
+
+    fn type_number(&self) -> u8 {
+        0
+    }
+
+    fn motor_bank_number(&self) -> u8 {
+        0
+    }
+}
+
+/// CSUB - Call Subroutine
+///
+/// This function calls a subroutine in the TMCL program memory. The return address is pushed on
+/// the stack, which has room for 8 return addresses. This command is intended for use in
+/// standalone operation only.
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub struct CSUB {
+    address: u32,
+}
+impl CSUB {
+    pub fn new(address: u32) -> CSUB {
+        CSUB { address }
+    }
+}
+impl Instruction for CSUB {
+    const INSTRUCTION_NUMBER: u8 = 23;
+
+    fn operand(&self) -> [u8; 4] {
+        [
+            self.address as u8,
+            (self.address >> 8) as u8,
+            (self.address >> 16) as u8,
+            (self.address >> 24) as u8,
+        ]
+    }
+
+    fn type_number(&self) -> u8 {
+        0
+    }
+
+    fn motor_bank_number(&self) -> u8 {
+        0
+    }
+}
+
+/// RSUB - Return from Subroutine
+///
+/// The TMCL program execution is continued with the address taken from the stack.
+/// This command is intended for use in standalone operation only.
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub struct RSUB {}
+impl RSUB {
+    pub fn new() -> RSUB {
+        RSUB {}
+    }
+}
+impl Default for RSUB {
+    fn default() -> RSUB {
+        RSUB::new()
+    }
+}
+impl Instruction for RSUB {
+    const INSTRUCTION_NUMBER: u8 = 24;
+
+    fn operand(&self) -> [u8; 4] {
+        [0u8, 0u8, 0u8, 0u8]
+    }
+
+    fn type_number(&self) -> u8 {
+        0
+    }
+
+    fn motor_bank_number(&self) -> u8 {
+        0
+    }
+}
+
+/// An interrupt of the TMCL program
+///
+/// Not all modules support all interrupts, see the firmware manual of the module.
+#[derive(Debug, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub enum InterruptVector {
+    /// Timer 0
+    Timer0 = 0,
+    /// Timer 1
+    Timer1 = 1,
+    /// Timer 2
+    Timer2 = 2,
+    /// Target position reached
+    TargetPositionReached = 3,
+    /// stallGuard
+    StallGuard = 15,
+    /// Deviation
+    Deviation = 21,
+    /// Left stop switch
+    LeftStopSwitch = 27,
+    /// Right stop switch
+    RightStopSwitch = 28,
+    /// Input change 0
+    InputChange0 = 39,
+    /// Input change 1
+    InputChange1 = 40,
+    /// Global interrupts, only useable with `EI` and `DI`
+    Global = 255,
+}
+impl InterruptVector {
+    #[allow(clippy::result_unit_err)]
+    pub fn try_from_u8(v: u8) -> Result<Self, ()> {
+        match v {
+            0 => Ok(InterruptVector::Timer0),
+            1 => Ok(InterruptVector::Timer1),
+            2 => Ok(InterruptVector::Timer2),
+            3 => Ok(InterruptVector::TargetPositionReached),
+            15 => Ok(InterruptVector::StallGuard),
+            21 => Ok(InterruptVector::Deviation),
+            27 => Ok(InterruptVector::LeftStopSwitch),
+            28 => Ok(InterruptVector::RightStopSwitch),
+            39 => Ok(InterruptVector::InputChange0),
+            40 => Ok(InterruptVector::InputChange1),
+            255 => Ok(InterruptVector::Global),
+            _ => Err(()),
+        }
+    }
+}
+
+/// EI - Enable Interrupt
+///
+/// The EI command enables an interrupt. It needs to be executed after the interrupt vector has
+/// been defined with `VECT`. `InterruptVector::Global` has to be enabled as well before any
+/// interrupt is processed. This command is intended for use in standalone operation only.
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub struct EI {
+    vector: InterruptVector,
+}
+impl EI {
+    pub fn new(vector: InterruptVector) -> EI {
+        EI { vector }
+    }
+}
+impl Instruction for EI {
+    const INSTRUCTION_NUMBER: u8 = 25;
+
+    fn operand(&self) -> [u8; 4] {
+        [0u8, 0u8, 0u8, 0u8]
+    }
+
+    fn type_number(&self) -> u8 {
+        self.vector as u8
+    }
+
+    fn motor_bank_number(&self) -> u8 {
+        0
+    }
+}
+
+/// DI - Disable Interrupt
+///
+/// The DI command disables an interrupt. Disabling `InterruptVector::Global` disables all
+/// interrupts. This command is intended for use in standalone operation only.
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub struct DI {
+    vector: InterruptVector,
+}
+impl DI {
+    pub fn new(vector: InterruptVector) -> DI {
+        DI { vector }
+    }
+}
+impl Instruction for DI {
+    const INSTRUCTION_NUMBER: u8 = 26;
+
+    fn operand(&self) -> [u8; 4] {
+        [0u8, 0u8, 0u8, 0u8]
+    }
+
+    fn type_number(&self) -> u8 {
+        self.vector as u8
+    }
+
+    fn motor_bank_number(&self) -> u8 {
+        0
+    }
+}
+
+/// The event a `WAIT` instruction waits for
+#[derive(Debug, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub enum WaitEvent {
+    /// TICKS - Wait for the specified number of timer ticks.
+    Ticks,
+    /// POS - Wait until the target position of the motor is reached.
+    PositionReached(u8),
+    /// REFSW - Wait until the reference switch of the motor is triggered.
+    ReferenceSwitch(u8),
+    /// LIMSW - Wait until a limit switch of the motor is triggered.
+    LimitSwitch(u8),
+    /// RFS - Wait until the reference search of the motor has finished.
+    ReferenceSearch(u8),
+}
+
+/// WAIT - Wait for an event to occur
+///
+/// This instruction interrupts the execution of the TMCL program until the specified condition
+/// is met. The ticks are given in units of 10ms. When waiting for `WaitEvent::Ticks` they are the
+/// time to wait, for all other events they are a timeout, where 0 means no timeout. If the
+/// timeout expires the ETO flag is set, which can be tested with `JC`.
+/// This command is intended for use in standalone operation only.
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub struct WAIT {
+    event: WaitEvent,
+    ticks: u32,
+}
+impl WAIT {
+    pub fn new(event: WaitEvent, ticks: u32) -> WAIT {
+        WAIT { event, ticks }
+    }
+}
+impl Instruction for WAIT {
+    const INSTRUCTION_NUMBER: u8 = 27;
+
+    fn operand(&self) -> [u8; 4] {
+        [
+            self.ticks as u8,
+            (self.ticks >> 8) as u8,
+            (self.ticks >> 16) as u8,
+            (self.ticks >> 24) as u8,
+        ]
+    }
+
+    fn type_number(&self) -> u8 {
+        match self.event {
+            WaitEvent::Ticks => 0,
+            WaitEvent::PositionReached(_) => 1,
+            WaitEvent::ReferenceSwitch(_) => 2,
+            WaitEvent::LimitSwitch(_) => 3,
+            WaitEvent::ReferenceSearch(_) => 4,
+        }
+    }
+
+    fn motor_bank_number(&self) -> u8 {
+        match self.event {
+            WaitEvent::Ticks => 0,
+            WaitEvent::PositionReached(motor_number)
+            | WaitEvent::ReferenceSwitch(motor_number)
+            | WaitEvent::LimitSwitch(motor_number)
+            | WaitEvent::ReferenceSearch(motor_number) => motor_number,
+        }
+    }
+}
+
+/// STOP - Stop TMCL program execution
+///
+/// This function stops executing a TMCL program. This command is intended for use in
+/// standalone operation only.
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub struct STOP {}
+impl STOP {
+    pub fn new() -> STOP {
+        STOP {}
+    }
+}
+impl Default for STOP {
+    fn default() -> STOP {
+        STOP::new()
+    }
+}
+impl Instruction for STOP {
+    const INSTRUCTION_NUMBER: u8 = 28;
+
+    fn operand(&self) -> [u8; 4] {
+        [0u8, 0u8, 0u8, 0u8]
+    }
+
+    fn type_number(&self) -> u8 {
+        0
+    }
+
+    fn motor_bank_number(&self) -> u8 {
+        0
+    }
+}
+
+/// SCO - Set Coordinate
+///
+/// Up to 20 position values (coordinates) can be stored for every axis for use with the MVP COORD
+/// command. This command sets a coordinate to a specified value. Depending on the global parameter
+/// 84, the coordinates are only stored in RAM or also stored in the EEPROM and copied back on
+/// startup.
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub struct SCO {
+    coordinate_number: u8,
+    motor_number: u8,
+    position: i32,
+}
+impl SCO {
+    pub fn new(coordinate_number: u8, motor_number: u8, position: i32) -> SCO {
+        SCO {
+            coordinate_number,
+            motor_number,
+            position,
+        }
+    }
+}
+impl Instruction for SCO {
+    const INSTRUCTION_NUMBER: u8 = 30;
+
+    fn operand(&self) -> [u8; 4] {
+        [
+            self.position as u8,
+            (self.position >> 8) as u8,
+            (self.position >> 16) as u8,
+            (self.position >> 24) as u8,
+        ]
+    }
+
+    fn type_number(&self) -> u8 {
+        self.coordinate_number
+    }
+
+    fn motor_bank_number(&self) -> u8 {
+        self.motor_number
+    }
+}
+impl DirectInstruction for SCO {
+    type Return = ();
+}
+
+/// GCO - Get Coordinate
+///
+/// Using this command previously stored coordinate can be read back. In standalone mode the
+/// requested value is copied to the accumulator register for further processing purposes such as
+/// conditioned jumps. In direct mode, the value is only output in the value field of the reply,
+/// without affecting the accumulator.
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub struct GCO {
+    coordinate_number: u8,
+    motor_number: u8,
+}
+impl GCO {
+    pub fn new(coordinate_number: u8, motor_number: u8) -> GCO {
+        GCO {
+            coordinate_number,
+            motor_number,
+        }
+    }
+}
+impl Instruction for GCO {
+    const INSTRUCTION_NUMBER: u8 = 31;
+
+    fn operand(&self) -> [u8; 4] {
+        [0u8, 0u8, 0u8, 0u8]
+    }
+
+    fn type_number(&self) -> u8 {
+        self.coordinate_number
+    }
+
+    fn motor_bank_number(&self) -> u8 {
+        self.motor_number
+    }
+}
+impl DirectInstruction for GCO {
+    type Return = i32;
+}
+
+/// CCO - Capture Coordinate
+///
+/// The actual position of the axis is copied to the selected coordinate variable.
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub struct CCO {
+    coordinate_number: u8,
+    motor_number: u8,
+}
+impl CCO {
+    pub fn new(coordinate_number: u8, motor_number: u8) -> CCO {
+        CCO {
+            coordinate_number,
+            motor_number,
+        }
+    }
+}
+impl Instruction for CCO {
+    const INSTRUCTION_NUMBER: u8 = 32;
+
+    fn operand(&self) -> [u8; 4] {
+        [0u8, 0u8, 0u8, 0u8]
+    }
+
+    fn type_number(&self) -> u8 {
+        self.coordinate_number
+    }
+
+    fn motor_bank_number(&self) -> u8 {
+        self.motor_number
+    }
+}
+impl DirectInstruction for CCO {
+    type Return = ();
+}
+
+/// CALCX - Calculate using the X register
+///
+/// This instruction is very similar to CALC, but the second operand comes from the X register.
+/// The X register can be loaded with the LOAD or the SWAP type of this instruction. The result is
+/// written back to the accumulator for further processing like comparisons or data transfer.
+/// This command is intended for use in standalone operation only.
+#[derive(Debug, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub enum CALCX {
+    /// Add X register to accumulator
+    Add = 0,
+
+    /// Subtract X register from accumulator
+    Sub = 1,
+
+    /// Multiply accumulator by X register
+    Mul = 2,
+
+    /// Divide accumulator by X register
+    Div = 3,
+
+    /// Modulo divide accumulator by X register
+    Mod = 4,
+
+    /// Logical and accumulator with X register
+    And = 5,
+
+    /// Logical or accumulator with X register
+    Or = 6,
+
+    /// Logical xor accumulator with X register
+    Xor = 7,
+
+    /// Logical invert X register
+    Not = 8,
+
+    /// Load accumulator to X register
+    Load = 9,
+
+    /// Swap accumulator with X register
+    Swap = 10,
+}
+impl Instruction for CALCX {
+    const INSTRUCTION_NUMBER: u8 = 33;
+
+    fn operand(&self) -> [u8; 4] {
+        [0u8, 0u8, 0u8, 0u8]
+    }
+
+    fn type_number(&self) -> u8 {
+        *self as u8
+    }
+
+    fn motor_bank_number(&self) -> u8 {
+        0
+    }
+}
+
+/// AAP - Accumulator to Axis Parameter
+///
+/// The content of the accumulator register is transferred to the specified axis parameter.
+/// This command is intended for use in standalone operation only.
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub struct AAP<T: WriteableAxisParameter> {
+    motor_number: u8,
+    phantom: PhantomData<T>,
+}
+impl<T: WriteableAxisParameter> AAP<T> {
+    pub fn new(motor_number: u8) -> AAP<T> {
+        AAP {
+            motor_number,
+            phantom: PhantomData,
+        }
+    }
+}
+impl<T: WriteableAxisParameter> Instruction for AAP<T> {
+    const INSTRUCTION_NUMBER: u8 = 34;
+
+    fn operand(&self) -> [u8; 4] {
+        [0u8, 0u8, 0u8, 0u8]
+    }
+
+    fn type_number(&self) -> u8 {
+        T::NUMBER
+    }
+
+    fn motor_bank_number(&self) -> u8 {
+        self.motor_number
+    }
+}
+
+/// AGP - Accumulator to Global Parameter
+///
+/// The content of the accumulator register is transferred to the specified global parameter.
+/// This command is intended for use in standalone operation only.
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub struct AGP<T: WriteableGlobalParameter> {
+    phantom: PhantomData<T>,
+}
+impl<T: WriteableGlobalParameter> AGP<T> {
+    pub fn new() -> AGP<T> {
+        AGP {
+            phantom: PhantomData,
+        }
+    }
+}
+impl<T: WriteableGlobalParameter> Default for AGP<T> {
+    fn default() -> AGP<T> {
+        AGP::new()
+    }
+}
+impl<T: WriteableGlobalParameter> Instruction for AGP<T> {
+    const INSTRUCTION_NUMBER: u8 = 35;
+
+    fn operand(&self) -> [u8; 4] {
+        [0u8, 0u8, 0u8, 0u8]
+    }
+
+    fn type_number(&self) -> u8 {
+        T::NUMBER
+    }
+
+    fn motor_bank_number(&self) -> u8 {
+        T::BANK
+    }
+}
+
+/// The error flag cleared by a `CLE` instruction
+#[derive(Debug, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub enum ErrorFlag {
+    /// ALL - All error flags
+    All = 0,
+    /// ETO - Timeout flag
+    TimeoutError = 1,
+    /// EAL - External alarm flag
+    ExternalAlarm = 2,
+    /// EDV - Deviation flag
+    DeviationError = 3,
+    /// EPO - Position error flag
+    PositionError = 4,
+}
+
+/// CLE - Clear Error Flags
+///
+/// This command clears the internal error flags. This command is intended for use in
+/// standalone operation only.
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub struct CLE {
+    flag: ErrorFlag,
+}
+impl CLE {
+    pub fn new(flag: ErrorFlag) -> CLE {
+        CLE { flag }
+    }
+}
+impl Instruction for CLE {
+    const INSTRUCTION_NUMBER: u8 = 36;
+
+    fn operand(&self) -> [u8; 4] {
+        [0u8, 0u8, 0u8, 0u8]
+    }
+
+    fn type_number(&self) -> u8 {
+        self.flag as u8
+    }
+
+    fn motor_bank_number(&self) -> u8 {
+        0
+    }
+}
+
+/// VECT - Set Interrupt Vector
+///
+/// The VECT command defines an interrupt vector. The address is the start of the interrupt
+/// handling routine in the TMCL program memory, which has to end with `RETI`.
+/// This command is intended for use in standalone operation only.
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize))]
+pub struct VECT {
+    vector: InterruptVector,
+    address: u32,
+}
+/// Rejects `InterruptVector::Global`, like `VECT::new` does.
+#[cfg(feature = "serde")]
+impl<'de> ::serde::Deserialize<'de> for VECT {
+    fn deserialize<D: ::serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(::serde::Deserialize)]
+        #[serde(rename = "VECT")]
+        struct Unchecked {
+            vector: InterruptVector,
+            address: u32,
+        }
+
+        let Unchecked { vector, address } =
+            <Unchecked as ::serde::Deserialize<'de>>::deserialize(deserializer)?;
+        if vector == InterruptVector::Global {
+            Err(::serde::de::Error::custom(
+                "VECT can not set the global interrupt vector",
+            ))
+        } else {
+            Ok(Self { vector, address })
+        }
+    }
+}
+impl VECT {
+    pub fn new(vector: InterruptVector, address: u32) -> VECT {
+        assert!(vector != InterruptVector::Global);
+        VECT { vector, address }
+    }
+}
+impl Instruction for VECT {
+    const INSTRUCTION_NUMBER: u8 = 37;
+
+    fn operand(&self) -> [u8; 4] {
+        [
+            self.address as u8,
+            (self.address >> 8) as u8,
+            (self.address >> 16) as u8,
+            (self.address >> 24) as u8,
+        ]
+    }
+
+    fn type_number(&self) -> u8 {
+        self.vector as u8
+    }
+
+    fn motor_bank_number(&self) -> u8 {
+        0
+    }
+}
+
+/// RETI - Return from Interrupt
+///
+/// This command terminates an interrupt handling routine, and the normal program flow continues.
+/// This command is intended for use in standalone operation only.
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub struct RETI {}
+impl RETI {
+    pub fn new() -> RETI {
+        RETI {}
+    }
+}
+impl Default for RETI {
+    fn default() -> RETI {
+        RETI::new()
+    }
+}
+impl Instruction for RETI {
+    const INSTRUCTION_NUMBER: u8 = 38;
+
+    fn operand(&self) -> [u8; 4] {
+        [0u8, 0u8, 0u8, 0u8]
+    }
+
+    fn type_number(&self) -> u8 {
+        0
+    }
+
+    fn motor_bank_number(&self) -> u8 {
+        0
+    }
+}
+
+/// ACO - Accumulator to Coordinate
+///
+/// The content of the accumulator register is transferred to the specified coordinate.
+/// This command is intended for use in standalone operation only.
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub struct ACO {
+    coordinate_number: u8,
+    motor_number: u8,
+}
+impl ACO {
+    pub fn new(coordinate_number: u8, motor_number: u8) -> ACO {
+        ACO {
+            coordinate_number,
+            motor_number,
+        }
+    }
+}
+impl Instruction for ACO {
+    const INSTRUCTION_NUMBER: u8 = 39;
+
+    fn operand(&self) -> [u8; 4] {
+        [0u8, 0u8, 0u8, 0u8]
+    }
+
+    fn type_number(&self) -> u8 {
+        self.coordinate_number
+    }
+
+    fn motor_bank_number(&self) -> u8 {
+        self.motor_number
+    }
+}
+
+/// Stop Application
+///
+/// A running TMCL standalone application is stopped.
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub struct StopApplication {}
+impl StopApplication {
+    pub fn new() -> StopApplication {
+        StopApplication {}
+    }
+}
+impl Default for StopApplication {
+    fn default() -> StopApplication {
+        StopApplication::new()
+    }
+}
+impl Instruction for StopApplication {
+    const INSTRUCTION_NUMBER: u8 = 128;
+
+    fn operand(&self) -> [u8; 4] {
+        [0u8, 0u8, 0u8, 0u8]
+    }
+
+    fn type_number(&self) -> u8 {
+        0
+    }
+
+    fn motor_bank_number(&self) -> u8 {
+        0
+    }
+}
+impl DirectInstruction for StopApplication {
+    type Return = ();
+}
+
+/// Run Application
+///
+/// The TMCL program in the program memory of the module is started, either from the address
+/// where it was stopped or from a specified address.
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub struct RunApplication {
+    address: Option<u32>,
+}
+impl RunApplication {
+    /// Run from the current address
+    pub fn new() -> RunApplication {
+        RunApplication { address: None }
+    }
+
+    /// Run from the specified address
+    pub fn from_address(address: u32) -> RunApplication {
+        RunApplication {
+            address: Some(address),
+        }
+    }
+}
+impl Default for RunApplication {
+    fn default() -> RunApplication {
+        RunApplication::new()
+    }
+}
+impl Instruction for RunApplication {
+    const INSTRUCTION_NUMBER: u8 = 129;
+
+    fn operand(&self) -> [u8; 4] {
+        let address = self.address.unwrap_or(0);
+        [
+            address as u8,
+            (address >> 8) as u8,
+            (address >> 16) as u8,
+            (address >> 24) as u8,
+        ]
+    }
+
+    fn type_number(&self) -> u8 {
+        match self.address {
+            None => 0,
+            Some(_) => 1,
+        }
+    }
+
+    fn motor_bank_number(&self) -> u8 {
+        0
+    }
+}
+impl DirectInstruction for RunApplication {
+    type Return = ();
+}
+
+/// Step Application
+///
+/// Only the next command of a TMCL application is executed.
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub struct StepApplication {}
+impl StepApplication {
+    pub fn new() -> StepApplication {
+        StepApplication {}
+    }
+}
+impl Default for StepApplication {
+    fn default() -> StepApplication {
+        StepApplication::new()
+    }
+}
+impl Instruction for StepApplication {
+    const INSTRUCTION_NUMBER: u8 = 130;
+
+    fn operand(&self) -> [u8; 4] {
+        [0u8, 0u8, 0u8, 0u8]
+    }
+
+    fn type_number(&self) -> u8 {
+        0
+    }
+
+    fn motor_bank_number(&self) -> u8 {
+        0
+    }
+}
+impl DirectInstruction for StepApplication {
+    type Return = ();
+}
+
+/// Reset Application
+///
+/// The program counter is set to zero, and the standalone application is stopped
+/// (when running or stepped).
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub struct ResetApplication {}
+impl ResetApplication {
+    pub fn new() -> ResetApplication {
+        ResetApplication {}
+    }
+}
+impl Default for ResetApplication {
+    fn default() -> ResetApplication {
+        ResetApplication::new()
+    }
+}
+impl Instruction for ResetApplication {
+    const INSTRUCTION_NUMBER: u8 = 131;
+
+    fn operand(&self) -> [u8; 4] {
+        [0u8, 0u8, 0u8, 0u8]
+    }
+
+    fn type_number(&self) -> u8 {
+        0
+    }
+
+    fn motor_bank_number(&self) -> u8 {
+        0
+    }
+}
+impl DirectInstruction for ResetApplication {
+    type Return = ();
+}
+
+/// Enter Download Mode
+///
+/// Subsequent instructions are not executed, but stored in the TMCL program memory starting at
+/// the given address. The module answers them with `OkStatus::LoadedIntoEEPROM`.
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub struct EnterDownloadMode {
+    address: u32,
+}
+impl EnterDownloadMode {
+    pub fn new(address: u32) -> EnterDownloadMode {
+        EnterDownloadMode { address }
+    }
+}
+impl Instruction for EnterDownloadMode {
+    const INSTRUCTION_NUMBER: u8 = 132;
+
+    fn operand(&self) -> [u8; 4] {
+        [
+            self.address as u8,
+            (self.address >> 8) as u8,
+            (self.address >> 16) as u8,
+            (self.address >> 24) as u8,
+        ]
+    }
+
+    fn type_number(&self) -> u8 {
+        0
+    }
+
+    fn motor_bank_number(&self) -> u8 {
+        0
+    }
+}
+impl DirectInstruction for EnterDownloadMode {
+    type Return = ();
+}
+
+/// Exit Download Mode
+///
+/// Subsequent instructions are executed again instead of stored in the TMCL program memory.
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub struct ExitDownloadMode {}
+impl ExitDownloadMode {
+    pub fn new() -> ExitDownloadMode {
+        ExitDownloadMode {}
+    }
+}
+impl Default for ExitDownloadMode {
+    fn default() -> ExitDownloadMode {
+        ExitDownloadMode::new()
+    }
+}
+impl Instruction for ExitDownloadMode {
+    const INSTRUCTION_NUMBER: u8 = 133;
+
+    fn operand(&self) -> [u8; 4] {
+        [0u8, 0u8, 0u8, 0u8]
+    }
+
+    fn type_number(&self) -> u8 {
+        0
+    }
+
+    fn motor_bank_number(&self) -> u8 {
+        0
+    }
+}
+impl DirectInstruction for ExitDownloadMode {
+    type Return = ();
+}
+
+/// The part of a stored instruction read by `ReadProgramMemory`
+#[derive(Debug, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub enum ProgramMemoryPart {
+    /// The command, type and motor/bank number as `[CMD_N, TYPE_N, MOTOR_N, 0]`
+    Command = 0,
+    /// The operand
+    Value = 1,
+}
+
+/// Read TMCL Memory
+///
+/// Reads back an instruction stored in the TMCL program memory. As a reply only has room for
+/// one operand, the instruction is read in two parts.
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub struct ReadProgramMemory {
+    address: u32,
+    part: ProgramMemoryPart,
+}
+impl ReadProgramMemory {
+    pub fn new(address: u32, part: ProgramMemoryPart) -> ReadProgramMemory {
+        ReadProgramMemory { address, part }
+    }
+}
+impl Instruction for ReadProgramMemory {
+    const INSTRUCTION_NUMBER: u8 = 134;
+
+    fn operand(&self) -> [u8; 4] {
+        [
+            self.address as u8,
+            (self.address >> 8) as u8,
+            (self.address >> 16) as u8,
+            (self.address >> 24) as u8,
+        ]
+    }
+
+    fn type_number(&self) -> u8 {
+        self.part as u8
+    }
+
+    fn motor_bank_number(&self) -> u8 {
+        0
+    }
+}
+impl DirectInstruction for ReadProgramMemory {
+    type Return = [u8; 4];
+}
+
+/// The state of the TMCL standalone application
+#[derive(Debug, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub enum ApplicationStatus {
+    /// The application is stopped
+    Stop,
+    /// The application is running
+    Run,
+    /// The application is executed step by step
+    Step,
+    /// The application has been reset
+    Reset,
+    /// A status this crate does not know, as sent by the module
+    Unknown(u8),
+}
+impl ApplicationStatus {
+    #[allow(clippy::result_unit_err)]
+    pub fn try_from_u8(v: u8) -> Result<Self, ()> {
+        match v {
+            0 => Ok(ApplicationStatus::Stop),
+            1 => Ok(ApplicationStatus::Run),
+            2 => Ok(ApplicationStatus::Step),
+            3 => Ok(ApplicationStatus::Reset),
+            _ => Err(()),
+        }
+    }
+}
+impl Return for ApplicationStatus {
+    fn from_operand(array: [u8; 4]) -> Self {
+        ApplicationStatus::try_from_u8(array[0]).unwrap_or(ApplicationStatus::Unknown(array[0]))
+    }
+}
+
+/// Get Application Status
+///
+/// Reads whether the TMCL standalone application is stopped, running, stepped or reset.
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub struct GetApplicationStatus {}
+impl GetApplicationStatus {
+    pub fn new() -> GetApplicationStatus {
+        GetApplicationStatus {}
+    }
+}
+impl Default for GetApplicationStatus {
+    fn default() -> GetApplicationStatus {
+        GetApplicationStatus::new()
+    }
+}
+impl Instruction for GetApplicationStatus {
+    const INSTRUCTION_NUMBER: u8 = 135;
+
+    fn operand(&self) -> [u8; 4] {
+        [0u8, 0u8, 0u8, 0u8]
+    }
+
+    fn type_number(&self) -> u8 {
+        0
+    }
+
+    fn motor_bank_number(&self) -> u8 {
+        0
+    }
+}
+impl DirectInstruction for GetApplicationStatus {
+    type Return = ApplicationStatus;
+}
+
+/// The module type and firmware revision of a module
+#[derive(Debug, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub struct FirmwareVersion {
+    module_type: u16,
+    major: u8,
+    minor: u8,
+}
+impl FirmwareVersion {
+    pub fn new(module_type: u16, major: u8, minor: u8) -> Self {
+        FirmwareVersion {
+            module_type,
+            major,
+            minor,
+        }
+    }
+
+    /// Parse the ASCII form of the firmware version.
+    ///
+    /// These are the 8 characters following the reply address in the reply to
+    /// `GetFirmwareVersionAscii`, for example `1140V211` meaning TMCM-1140 version 2.11.
+    pub fn from_ascii(string: &[u8]) -> Result<Self, ParseError> {
+        if string.len() < 8 {
+            return Err(ParseError::ShortFrame);
+        }
+        let string = &string[..8];
+        let separator = string
+            .iter()
+            .position(|&c| c == b'V')
+            .ok_or(ParseError::NonValidAscii)?;
+        let (module_type, revision) = (&string[..separator], &string[separator + 1..]);
+        let (major, minor) = match revision.iter().position(|&c| c == b'.') {
+            Some(dot) => (&revision[..dot], &revision[dot + 1..]),
+            None if !revision.is_empty() => (&revision[..1], &revision[1..]),
+            None => return Err(ParseError::NonValidAscii),
+        };
+        Ok(FirmwareVersion {
+            module_type: u16::try_from(parse_decimal(module_type)?)
+                .map_err(|_| ParseError::NonValidAscii)?,
+            major: u8::try_from(parse_decimal(major)?).map_err(|_| ParseError::NonValidAscii)?,
+            minor: u8::try_from(parse_decimal(minor)?).map_err(|_| ParseError::NonValidAscii)?,
+        })
+    }
+
+    /// The module type, e.g. 1140 for a TMCM-1140
+    pub fn module_type(&self) -> u16 {
+        self.module_type
+    }
+
+    /// The major firmware revision
+    pub fn major(&self) -> u8 {
+        self.major
+    }
+
+    /// The minor firmware revision
+    pub fn minor(&self) -> u8 {
+        self.minor
+    }
+}
+impl Return for FirmwareVersion {
+    fn from_operand(array: [u8; 4]) -> Self {
+        FirmwareVersion {
+            module_type: array[2] as u16 | ((array[3] as u16) << 8),
+            major: array[1],
+            minor: array[0],
+        }
+    }
+}
+
+/// Parse a non-empty string of at most 5 decimal digits
+fn parse_decimal(digits: &[u8]) -> Result<u32, ParseError> {
+    if digits.is_empty() || digits.len() > 5 {
+        return Err(ParseError::NonValidAscii);
+    }
+    digits.iter().try_fold(0u32, |value, &c| match c {
+        b'0'..=b'9' => Ok(value * 10 + (c - b'0') as u32),
+        _ => Err(ParseError::NonValidAscii),
+    })
+}
+
+/// Get Firmware Version
+///
+/// Reads the module type and firmware revision in binary form.
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub struct GetFirmwareVersion {}
+impl GetFirmwareVersion {
+    pub fn new() -> GetFirmwareVersion {
+        GetFirmwareVersion {}
+    }
+}
+impl Default for GetFirmwareVersion {
+    fn default() -> GetFirmwareVersion {
+        GetFirmwareVersion::new()
+    }
+}
+impl Instruction for GetFirmwareVersion {
+    const INSTRUCTION_NUMBER: u8 = 136;
+
+    fn operand(&self) -> [u8; 4] {
+        [0u8, 0u8, 0u8, 0u8]
+    }
+
+    fn type_number(&self) -> u8 {
+        1
+    }
+
+    fn motor_bank_number(&self) -> u8 {
+        0
+    }
+}
+impl DirectInstruction for GetFirmwareVersion {
+    type Return = FirmwareVersion;
+}
+
+/// Get Firmware Version (ASCII)
+///
+/// Reads the module type and firmware revision as a string. The module does not answer with
+/// a regular reply: the reply address is followed by 8 ASCII characters and there is neither
+/// a status nor a checksum. The reply can therefore not be received as a `Reply`, use
+/// `FirmwareVersion::from_ascii` on the received characters instead.
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub struct GetFirmwareVersionAscii {}
+impl GetFirmwareVersionAscii {
+    pub fn new() -> GetFirmwareVersionAscii {
+        GetFirmwareVersionAscii {}
+    }
+}
+impl Default for GetFirmwareVersionAscii {
+    fn default() -> GetFirmwareVersionAscii {
+        GetFirmwareVersionAscii::new()
+    }
+}
+impl Instruction for GetFirmwareVersionAscii {
+    const INSTRUCTION_NUMBER: u8 = 136;
+
+    fn operand(&self) -> [u8; 4] {
+        [0u8, 0u8, 0u8, 0u8]
+    }
+
+    fn type_number(&self) -> u8 {
+        0
+    }
+
+    fn motor_bank_number(&self) -> u8 {
+        0
+    }
+}
+
+/// Restore Factory Defaults
+///
+/// Reset all settings stored in the EEPROM to their factory defaults.
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub struct RestoreFactoryDefaults {}
+impl RestoreFactoryDefaults {
+    /// Magic value that must be sent as operand.
+    const MAGIC: u32 = 1234;
+
+    pub fn new() -> RestoreFactoryDefaults {
+        RestoreFactoryDefaults {}
+    }
+}
+impl Default for RestoreFactoryDefaults {
+    fn default() -> RestoreFactoryDefaults {
+        RestoreFactoryDefaults::new()
+    }
+}
+impl Instruction for RestoreFactoryDefaults {
+    const INSTRUCTION_NUMBER: u8 = 137;
+
+    fn operand(&self) -> [u8; 4] {
+        [
+            Self::MAGIC as u8,
+            (Self::MAGIC >> 8) as u8,
+            (Self::MAGIC >> 16) as u8,
+            (Self::MAGIC >> 24) as u8,
+        ]
+    }
+
+    fn type_number(&self) -> u8 {
+        0
+    }
+
+    fn motor_bank_number(&self) -> u8 {
+        0
+    }
+}
+impl DirectInstruction for RestoreFactoryDefaults {
+    type Return = ();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use Command;
+
+    #[test]
+    fn jc_encoding() {
+        let command = Command::new(1, JC::new(JumpCondition::GreaterOrEqual, 0x0102));
+        assert_eq!(command.serialize_can(), [21, 5, 0, 0, 0, 1, 2]);
+    }
+
+    #[test]
+    fn wait_encoding() {
+        let command = Command::new(1, WAIT::new(WaitEvent::PositionReached(2), 100));
+        assert_eq!(command.serialize_can(), [27, 1, 2, 0, 0, 0, 100]);
+
+        let command = Command::new(1, WAIT::new(WaitEvent::Ticks, 500));
+        assert_eq!(command.serialize_can(), [27, 0, 0, 0, 0, 1, 244]);
+    }
+
+    #[test]
+    fn firmware_version() {
+        assert_eq!(
+            <FirmwareVersion as Return>::from_operand([11, 2, 0x74, 0x04]),
+            FirmwareVersion::new(1140, 2, 11)
+        );
+        assert_eq!(
+            FirmwareVersion::from_ascii(b"1140V211"),
+            Ok(FirmwareVersion::new(1140, 2, 11))
+        );
+        assert_eq!(
+            FirmwareVersion::from_ascii(b"110V1.40"),
+            Ok(FirmwareVersion::new(110, 1, 40))
+        );
+        assert_eq!(
+            FirmwareVersion::from_ascii(b"1140X211"),
+            Err(ParseError::NonValidAscii)
+        );
+        assert_eq!(
+            FirmwareVersion::from_ascii(b"99999V11"),
+            Err(ParseError::NonValidAscii)
+        );
+        assert_eq!(
+            FirmwareVersion::from_ascii(b"1V1.9999"),
+            Err(ParseError::NonValidAscii)
+        );
+    }
+
+    #[test]
+    fn application_status() {
+        assert_eq!(
+            ApplicationStatus::from_operand([1, 0, 0, 0]),
+            ApplicationStatus::Run
+        );
+        assert_eq!(
+            ApplicationStatus::from_operand([7, 0, 0, 0]),
+            ApplicationStatus::Unknown(7)
+        );
+    }
+
+    #[test]
+    fn comp_encoding() {
+        let command = Command::new(1, COMP::new(-1));
+        assert_eq!(command.serialize_can(), [20, 0, 0, 255, 255, 255, 255]);
+    }
+
+    #[test]
+    fn mvp_type() {
+        let command = Command::new(1, MVP::new(0, MoveOperation::Relative(-2)));
+        assert_eq!(command.serialize_can(), [4, 1, 0, 255, 255, 255, 254]);
+        let command = Command::new(1, MVP::interpolated(0b101, 3));
+        assert_eq!(command.serialize_can(), [4, 2, 0x45, 0, 0, 0, 3]);
+    }
+
+    #[test]
+    fn coordinates() {
+        let command = Command::new(1, SCO::new(2, 1, 1000));
+        assert_eq!(command.serialize_can(), [30, 2, 1, 0, 0, 0x03, 0xe8]);
+        let command = Command::new(1, GCO::new(2, 1));
+        assert_eq!(command.serialize_can(), [31, 2, 1, 0, 0, 0, 0]);
+        let command = Command::new(1, CCO::new(20, 0));
+        assert_eq!(command.serialize_can(), [32, 20, 0, 0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn accumulator_transfer() {
+        let command = Command::new(1, CALCX::Swap);
+        assert_eq!(command.serialize_can(), [33, 10, 0, 0, 0, 0, 0]);
+        let command = Command::new(1, ACO::new(3, 2));
+        assert_eq!(command.serialize_can(), [39, 3, 2, 0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn interrupts() {
+        let command = Command::new(1, VECT::new(InterruptVector::LeftStopSwitch, 0x123));
+        assert_eq!(command.serialize_can(), [37, 27, 0, 0, 0, 0x01, 0x23]);
+        let command = Command::new(1, EI::new(InterruptVector::Global));
+        assert_eq!(command.serialize_can(), [25, 255, 0, 0, 0, 0, 0]);
+        assert_eq!(
+            InterruptVector::try_from_u8(40),
+            Ok(InterruptVector::InputChange1)
+        );
+        assert_eq!(InterruptVector::try_from_u8(4), Err(()));
+    }
+}
@@ -166,6 +166,15 @@
 //! # fn main() {}
 //! ```
 //!
+//! ## Testing without hardware
+//! With the std feature enabled, `simulator::SimulatedModule` can be used as the interface.
+//! It answers parameter and I/O commands like the firmware of a TMCM module.
+//!
+//! ## Serde
+//! With the serde feature enabled, instructions, `Command`, `Reply`, `Status` and the parameter
+//! types implement `Serialize` and `Deserialize`, also without std. Values that the constructors
+//! reject with a panic, e.g. a `CANId` above 0x7ff, fail to deserialize.
+//!
 //! ## No-std
 //! When using with no-std you can implement `Interface` on the interface you intent to use.
 //!
@@ -233,16 +242,73 @@ extern crate socketcan;
 #[cfg(feature = "socketcan")]
 mod socketcan_impl;
 
+#[cfg(feature = "embedded-hal")]
+extern crate embedded_hal;
+
+#[cfg(feature = "embedded-hal")]
+mod i2c_impl;
+
+#[cfg(feature = "serial")]
+mod serial_impl;
+
+#[cfg(feature = "async-serial")]
+extern crate futures_io;
+
+#[cfg(feature = "async-serial")]
+mod async_serial_impl;
+
+#[cfg(feature = "async")]
+mod async_interface;
+
+#[cfg(feature = "serde")]
+extern crate serde;
+
+#[cfg(all(test, unix))]
+extern crate libc;
+
+#[cfg(all(test, feature = "serde"))]
+extern crate serde_test;
+
 mod instructions;
 #[macro_use]
 mod axis_parameters;
+#[macro_use]
+mod global_parameters;
 
+pub mod metadata;
 pub mod modules;
+pub mod program;
+
+mod scan;
+
+#[cfg(feature = "std")]
+pub mod assembler;
+
+#[cfg(feature = "std")]
+mod bus;
+
+#[cfg(feature = "std")]
+pub mod simulator;
 
-pub use instructions::Instruction;
 pub use instructions::DirectInstruction;
+pub use instructions::Instruction;
 pub use instructions::Return;
 
+pub use scan::{scan, DiscoveredModule, Scan};
+
+#[cfg(feature = "async")]
+pub use async_interface::{AsyncInterface, WriteCommand};
+#[cfg(feature = "async-serial")]
+pub use async_serial_impl::AsyncSerialInterface;
+#[cfg(feature = "std")]
+pub use bus::{Bus, Pending};
+#[cfg(feature = "embedded-hal")]
+pub use i2c_impl::{I2cError, I2cInterface};
+#[cfg(feature = "serial")]
+pub use serial_impl::{SerialError, SerialInterface};
+#[cfg(feature = "socketcan")]
+pub use socketcan_impl::{CanError, CanInterface};
+
 /// A interface for a TMCM module
 ///
 /// Can be RS232, RS485, CAN or I2C
@@ -254,6 +320,21 @@ pub trait Interface {
     fn receive_reply(&mut self) -> Result<Reply, Self::Error>;
 }
 
+/// An `Interface` with a reply timeout, used by `scan` to probe addresses with a short timeout.
+pub trait ReplyTimeout: Interface {
+    /// How the timeout is given, e.g. `Duration`, or `Option<Duration>` if it can wait forever
+    type Timeout;
+
+    /// Returns the reply timeout
+    fn reply_timeout(&self) -> Self::Timeout;
+
+    /// Set the reply timeout
+    fn set_reply_timeout(&mut self, timeout: Self::Timeout) -> Result<(), Self::Error>;
+
+    /// Returns true if `error` means that no reply was received before the timeout expired
+    fn is_timeout(error: &Self::Error) -> bool;
+}
+
 /// All possible errors when communicating with
 #[derive(Debug, PartialEq, Clone, Copy)]
 pub enum Error<T> {
@@ -269,12 +350,42 @@ pub enum Error<T> {
 
     /// The `TMCL` module reported an error.
     ProtocolError(ErrStatus),
+
+    /// The module executed an instruction that was supposed to be stored in the TMCL program memory.
+    ///
+    /// This happens when the module is not in download mode.
+    NotLoadedIntoEEPROM,
+
+    /// The TMCL program memory does not contain the expected instruction at this address.
+    VerificationFailed(u32),
+
+    /// The instruction addresses a motor the module does not have.
+    InvalidMotor(u8),
+
+    /// A command to the module at this address is still waiting for its reply.
+    CommandPending(u8),
+
+    /// No command to the module at this address is waiting for its reply.
+    NoCommandPending(u8),
+
+    /// After re-addressing the module read back this value instead of the new address.
+    AddressNotApplied(u32),
+
+    /// Another module already answers at the address a module was going to be moved to.
+    AddressInUse(u8),
+
+    /// The parameter with this number can not be written.
+    ReadOnlyParameter(u8),
+
+    /// The value is outside the valid range of the parameter with this number.
+    InvalidParameterValue(u8),
 }
 
 /// A `Comamnd` is an `Instruction` with a module address.
 ///
 /// It contains everything required to serialize itself into Binary command format.
 #[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
 pub struct Command<T: Instruction> {
     module_address: u8,
     instruction: T,
@@ -282,8 +393,9 @@ pub struct Command<T: Instruction> {
 
 /// A TMCM module will respond with a `Reply` after receiving a `Command`.
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
 pub struct Reply {
-    reply_address: u8,
+    reply_address: u16,
 
     module_address: u8,
 
@@ -308,8 +420,26 @@ pub trait WriteableAxisParameter: AxisParameter {
     fn operand(&self) -> [u8; 4];
 }
 
+/// Global parameter - useable with SGP, GGP, AGP, STGP and/or RSGP instructions.
+pub trait GlobalParameter {
+    /// The Bank Number.
+    const BANK: u8;
+
+    /// The Parameter Number.
+    const NUMBER: u8;
+}
+
+/// A global parameter useable with the GGP instruction.
+pub trait ReadableGlobalParameter: GlobalParameter + Return {}
+
+/// A global parameter useable with the SGP instruction.
+pub trait WriteableGlobalParameter: GlobalParameter {
+    fn operand(&self) -> [u8; 4];
+}
+
 /// A `Status` that indicates that everything went well.
 #[derive(Debug, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
 pub enum OkStatus {
     /// Successfully executed, no error
     Ok = 100,
@@ -320,6 +450,7 @@ pub enum OkStatus {
 
 /// A `Status` that indicate an `Error` has occured.
 #[derive(Debug, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
 pub enum ErrStatus {
     /// Wrong checksum
     WrongChecksum = 1,
@@ -343,6 +474,7 @@ pub enum ErrStatus {
 /// Every reply from a `Module` contains a `Status`
 #[must_use]
 #[derive(Debug, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
 pub enum Status {
     Ok(OkStatus),
     Err(ErrStatus),
@@ -383,10 +515,23 @@ impl<T: Instruction> Command<T> {
 
     /// Serialize into binary command format suited for I2C
     ///
+    /// When using I2C the module address is the I2C address and is excluded from the frame.
+    /// The checksum is calculated over the remaining bytes.
     /// The array will look like the following:
     /// `[CMD_N, TYPE_N, MOTOR_N, VALUE3, VALUE2, VALUE1, VALUE0, CHECKSUM]`
     pub fn serialize_i2c(&self) -> [u8; 8] {
-        unimplemented!()
+        let mut data = [
+            T::INSTRUCTION_NUMBER,
+            self.instruction.type_number(),
+            self.instruction.motor_bank_number(),
+            self.instruction.operand()[3],
+            self.instruction.operand()[2],
+            self.instruction.operand()[1],
+            self.instruction.operand()[0],
+            0,
+        ];
+        data[7] = Self::calculate_checksum(&data[..7]);
+        data
     }
 
     /// Serialize into binary command format suited for CAN (controller area network)
@@ -407,7 +552,7 @@ impl<T: Instruction> Command<T> {
     }
 
     fn calculate_checksum(bytes: &[u8]) -> u8 {
-        bytes.iter().fold(0u8, |sum, &x| sum.overflowing_add(x).0)
+        calculate_checksum(bytes)
     }
 }
 
@@ -420,7 +565,7 @@ impl Reply {
         operand: [u8; 4],
     ) -> Self {
         Reply {
-            reply_address,
+            reply_address: u16::from(reply_address),
             module_address,
             status,
             command_number,
@@ -428,6 +573,75 @@ impl Reply {
         }
     }
 
+    /// Deserialize from binary reply format used with RS232, RS485 etc
+    ///
+    /// The frame should look like the following:
+    /// `[REPLY_ADR, MODULE_ADR, STATUS, CMD_N, VALUE3, VALUE2, VALUE1, VALUE0, CHECKSUM]`
+    pub fn deserialize(data: &[u8]) -> Result<Self, ParseError> {
+        if data.len() < 9 {
+            return Err(ParseError::ShortFrame);
+        }
+        if calculate_checksum(&data[..8]) != data[8] {
+            return Err(ParseError::WrongChecksum);
+        }
+        Self::from_fields(u16::from(data[0]), &data[1..8])
+    }
+
+    /// Deserialize from binary reply format used with I2C
+    ///
+    /// The reply address is the I2C address the reply was read from, it is not part of the frame.
+    /// The checksum is calculated over the preceding bytes.
+    /// The frame should look like the following:
+    /// `[MODULE_ADR, STATUS, CMD_N, VALUE3, VALUE2, VALUE1, VALUE0, CHECKSUM]`
+    pub fn deserialize_i2c(reply_address: u8, data: &[u8]) -> Result<Self, ParseError> {
+        if data.len() < 8 {
+            return Err(ParseError::ShortFrame);
+        }
+        if calculate_checksum(&data[..7]) != data[7] {
+            return Err(ParseError::WrongChecksum);
+        }
+        Self::from_fields(u16::from(reply_address), &data[..7])
+    }
+
+    /// Deserialize from binary reply format used with CAN (controller area network)
+    ///
+    /// When using CAN the reply address is the 11 bit CAN identifier of the frame, and the checksum is excluded.
+    /// The frame data should look like the following:
+    /// `[MODULE_ADR, STATUS, CMD_N, VALUE3, VALUE2, VALUE1, VALUE0]`
+    pub fn deserialize_can(reply_address: u16, data: &[u8]) -> Result<Self, ParseError> {
+        if data.len() < 7 {
+            return Err(ParseError::ShortFrame);
+        }
+        Self::from_fields(reply_address, &data[..7])
+    }
+
+    /// Build a reply from `[MODULE_ADR, STATUS, CMD_N, VALUE3, VALUE2, VALUE1, VALUE0]`
+    fn from_fields(reply_address: u16, fields: &[u8]) -> Result<Self, ParseError> {
+        Ok(Reply {
+            reply_address,
+            module_address: fields[0],
+            status: Status::try_from_u8(fields[1])
+                .or(Err(ParseError::NonValidStatus(fields[1])))?,
+            command_number: fields[2],
+            operand: [fields[6], fields[5], fields[4], fields[3]],
+        })
+    }
+
+    /// Returns the reply address, or the CAN identifier of the reply
+    pub fn reply_address(&self) -> u16 {
+        self.reply_address
+    }
+
+    /// Returns the address of the module that sent the reply
+    pub fn module_address(&self) -> u8 {
+        self.module_address
+    }
+
+    /// Returns the command number of the `Instruction` this is a reply to
+    pub fn command_number(&self) -> u8 {
+        self.command_number
+    }
+
     pub fn operand(&self) -> [u8; 4] {
         self.operand
     }
@@ -458,10 +672,28 @@ impl Status {
 #[derive(Debug)]
 pub struct NonValidErrorCode;
 
+/// The result of attempting to deserialize a frame that is not a valid `Reply` or firmware version.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum ParseError {
+    /// The frame is shorter than the reply format requires.
+    ShortFrame,
+
+    /// The checksum of the frame does not match its content.
+    WrongChecksum,
+
+    /// The status byte of the frame is not a valid status code.
+    NonValidStatus(u8),
+
+    /// The frame is not a valid ASCII firmware version string.
+    NonValidAscii,
+}
+
+fn calculate_checksum(bytes: &[u8]) -> u8 {
+    bytes.iter().fold(0u8, |sum, &x| sum.overflowing_add(x).0)
+}
+
 impl Return for () {
-    fn from_operand(_operand: [u8; 4]) -> () {
-        ()
-    }
+    fn from_operand(_operand: [u8; 4]) {}
 }
 
 impl Return for [u8; 4] {
@@ -570,4 +802,208 @@ mod tests {
         assert_eq!(bytes[8], 12);
         assert_eq!(bytes[4], 4);
     }
+
+    #[test]
+    fn i2c_frame() {
+        let instruction = TestInstruction {
+            type_number: 1,
+            motor_bank_number: 2,
+            operand: [1, 2, 3, 4],
+        };
+
+        let command = Command::new(3, instruction);
+        assert_eq!(command.serialize_i2c(), [255, 1, 2, 4, 3, 2, 1, 12]);
+    }
+
+    #[test]
+    fn i2c_reply() {
+        let reply = Reply::deserialize_i2c(3, &[2, 100, 6, 0, 0, 1, 2, 111]).unwrap();
+        assert_eq!(
+            reply,
+            Reply::new(3, 2, Status::Ok(OkStatus::Ok), 6, [2, 1, 0, 0])
+        );
+
+        assert_eq!(
+            Reply::deserialize_i2c(3, &[2, 100, 6, 0, 0, 1, 2, 110]),
+            Err(ParseError::WrongChecksum)
+        );
+        assert_eq!(
+            Reply::deserialize_i2c(3, &[2, 99, 6, 0, 0, 1, 2, 110]),
+            Err(ParseError::NonValidStatus(99))
+        );
+        assert_eq!(
+            Reply::deserialize_i2c(3, &[2, 100, 6]),
+            Err(ParseError::ShortFrame)
+        );
+    }
+
+    #[test]
+    fn serial_reply() {
+        let reply = Reply::deserialize(&[2, 1, 100, 6, 0x12, 0x34, 0x56, 0x78, 0x81]).unwrap();
+        assert_eq!(
+            reply,
+            Reply::new(2, 1, Status::Ok(OkStatus::Ok), 6, [0x78, 0x56, 0x34, 0x12])
+        );
+        assert_eq!(<i32 as Return>::from_operand(reply.operand()), 0x12345678);
+
+        assert_eq!(
+            Reply::deserialize(&[2, 1, 100, 6, 0x12, 0x34, 0x56, 0x78, 0x82]),
+            Err(ParseError::WrongChecksum)
+        );
+        assert_eq!(
+            Reply::deserialize(&[2, 1, 7, 6, 0x12, 0x34, 0x56, 0x78, 0x24]),
+            Err(ParseError::NonValidStatus(7))
+        );
+        assert_eq!(
+            Reply::deserialize(&[2, 1, 100, 6, 0x12, 0x34, 0x56, 0x78]),
+            Err(ParseError::ShortFrame)
+        );
+    }
+
+    #[test]
+    fn can_reply() {
+        let reply = Reply::deserialize_can(2, &[1, 3, 5, 0, 0, 0, 0]).unwrap();
+        assert_eq!(
+            reply,
+            Reply::new(2, 1, Status::Err(ErrStatus::WrongType), 5, [0, 0, 0, 0])
+        );
+        assert_eq!(
+            Reply::deserialize_can(2, &[1, 3, 5, 0]),
+            Err(ParseError::ShortFrame)
+        );
+        let reply = Reply::deserialize_can(0x402, &[1, 100, 5, 0, 0, 0, 0]).unwrap();
+        assert_eq!(reply.reply_address(), 0x402);
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn serde() {
+        use modules::tmcm::axis_parameters::MicrostepResolution;
+        use modules::tmcm::instructions::{MoveOperation, MVP, SAP};
+        use serde_test::{assert_tokens, Token};
+
+        assert_tokens(
+            &Command::new(1, MVP::new(0, MoveOperation::Relative(-100))),
+            &[
+                Token::Struct {
+                    name: "Command",
+                    len: 2,
+                },
+                Token::Str("module_address"),
+                Token::U8(1),
+                Token::Str("instruction"),
+                Token::Struct {
+                    name: "MVP",
+                    len: 2,
+                },
+                Token::Str("motor_number"),
+                Token::U8(0),
+                Token::Str("value"),
+                Token::NewtypeVariant {
+                    name: "MoveOperation",
+                    variant: "Relative",
+                },
+                Token::I32(-100),
+                Token::StructEnd,
+                Token::StructEnd,
+            ],
+        );
+        assert_tokens(
+            &SAP::new(0, MicrostepResolution::Half),
+            &[
+                Token::Struct {
+                    name: "SAP",
+                    len: 2,
+                },
+                Token::Str("motor_number"),
+                Token::U8(0),
+                Token::Str("axis_parameter"),
+                Token::UnitVariant {
+                    name: "MicrostepResolution",
+                    variant: "Half",
+                },
+                Token::StructEnd,
+            ],
+        );
+        assert_tokens(
+            &Status::Err(ErrStatus::EEPROMLocked),
+            &[
+                Token::NewtypeVariant {
+                    name: "Status",
+                    variant: "Err",
+                },
+                Token::UnitVariant {
+                    name: "ErrStatus",
+                    variant: "EEPROMLocked",
+                },
+            ],
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn serde_checks_values() {
+        use modules::tmcm::axis_parameters::{MixedDecayThreshold, RampDivisor};
+        use modules::tmcm::global_parameters::CANId;
+        use modules::tmcm::instructions::MVP;
+        use serde_test::{assert_de_tokens_error, assert_tokens, Token};
+
+        assert_tokens(
+            &CANId::new(0x7ff),
+            &[Token::NewtypeStruct { name: "CANId" }, Token::U32(0x7ff)],
+        );
+        assert_de_tokens_error::<CANId>(
+            &[Token::NewtypeStruct { name: "CANId" }, Token::U32(0x800)],
+            "value out of range for CANId",
+        );
+        assert_de_tokens_error::<RampDivisor>(
+            &[
+                Token::NewtypeStruct {
+                    name: "RampDivisor",
+                },
+                Token::U8(14),
+            ],
+            "value out of range for RampDivisor",
+        );
+        assert_tokens(
+            &MixedDecayThreshold::above(2048),
+            &[
+                Token::NewtypeStruct {
+                    name: "MixedDecayThreshold",
+                },
+                Token::Some,
+                Token::U16(2048),
+            ],
+        );
+        assert_de_tokens_error::<MixedDecayThreshold>(
+            &[
+                Token::NewtypeStruct {
+                    name: "MixedDecayThreshold",
+                },
+                Token::Some,
+                Token::U16(2049),
+            ],
+            "value out of range for MixedDecayThreshold",
+        );
+
+        let mvp = |motor_number| {
+            [
+                Token::Struct {
+                    name: "MVP",
+                    len: 2,
+                },
+                Token::Str("motor_number"),
+                Token::U8(motor_number),
+                Token::Str("value"),
+                Token::NewtypeVariant {
+                    name: "MoveOperation",
+                    variant: "Coordinate",
+                },
+                Token::U32(3),
+                Token::StructEnd,
+            ]
+        };
+        assert_tokens(&MVP::interpolated(0x3f, 3), &mvp(0x7f));
+        assert_de_tokens_error::<MVP>(&mvp(0xc1), "motor mask out of range for MVP");
+    }
 }
@@ -0,0 +1,167 @@
+//! Descriptions of parameters available at runtime.
+//!
+//! A `ParameterTable` describes the axis and global parameters of a module with their number,
+//! name, access, value type, unit and valid range. `modules::tmcm::metadata::PARAMETERS`
+//! describes the parameters in `modules::tmcm`, and is used by `GenericModule` to check
+//! writes before sending them.
+//!
+//! ```
+//! extern crate tmcl;
+//!
+//! use tmcl::metadata::Access;
+//! use tmcl::modules::tmcm::metadata::PARAMETERS;
+//!
+//! fn main() {
+//!     let parameter = PARAMETERS.axis_parameter_by_name("PulseDivisor").unwrap();
+//!     assert_eq!(parameter.number, 154);
+//!     assert_eq!(parameter.access, Access::ReadWrite);
+//!     assert!(!parameter.is_valid(14));
+//!
+//!     let parameter = PARAMETERS.global_parameter(0, 66).unwrap();
+//!     assert_eq!(parameter.name, "SerialAddress");
+//! }
+//! ```
+
+/// How a parameter can be accessed.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Access {
+    /// The parameter can only be read
+    Read,
+    /// The parameter can only be written
+    Write,
+    /// The parameter can be read and written
+    ReadWrite,
+}
+
+impl Access {
+    /// Returns true if the parameter can be read
+    pub fn is_readable(self) -> bool {
+        self != Access::Write
+    }
+
+    /// Returns true if the parameter can be written
+    pub fn is_writeable(self) -> bool {
+        self != Access::Read
+    }
+}
+
+/// How the operand of a parameter is interpreted.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum ValueType {
+    Bool,
+    U8,
+    U16,
+    U32,
+    I32,
+    /// One of the values of an enum, or a set of flags
+    Enum,
+}
+
+/// The description of a parameter.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct ParameterInfo {
+    /// The bank of a global parameter, `None` for axis parameters
+    pub bank: Option<u8>,
+    pub number: u8,
+    /// The name of the type representing the parameter
+    pub name: &'static str,
+    pub access: Access,
+    pub value_type: ValueType,
+    /// The unit of the value, `None` for internal units and numbers without unit
+    pub unit: Option<&'static str>,
+    /// The lowest valid value
+    pub min: i64,
+    /// The highest valid value
+    pub max: i64,
+    /// The only valid values, empty if every value from `min` to `max` is valid
+    pub values: &'static [i64],
+}
+
+impl ParameterInfo {
+    /// Returns the operand interpreted as a value of the parameter
+    pub fn value(&self, operand: [u8; 4]) -> i64 {
+        let value = u32::from(operand[0])
+            | u32::from(operand[1]) << 8
+            | u32::from(operand[2]) << 16
+            | u32::from(operand[3]) << 24;
+        match self.value_type {
+            ValueType::U32 => i64::from(value),
+            _ => i64::from(value as i32),
+        }
+    }
+
+    /// Returns true if `value` is in the valid range of the parameter
+    pub fn is_valid(&self, value: i64) -> bool {
+        if self.values.is_empty() {
+            (self.min..=self.max).contains(&value)
+        } else {
+            self.values.contains(&value)
+        }
+    }
+}
+
+/// The access, value type and valid values of a parameter type.
+///
+/// Implemented by the parameter types defined with the `axis_param` and `global_param` macros,
+/// so that a `ParameterTable` describing them is generated from their definitions.
+pub(crate) trait Describe {
+    const ACCESS: Access;
+    const VALUE_TYPE: ValueType;
+    const MIN: i64;
+    const MAX: i64;
+    const VALUES: &'static [i64];
+}
+
+/// A primitive type holding the value of a parameter.
+pub(crate) trait Value {
+    const VALUE_TYPE: ValueType;
+    const MIN: i64;
+    const MAX: i64;
+}
+
+macro_rules! value {
+    ($ty:ty, $value_type:ident, $min:expr, $max:expr) => {
+        impl Value for $ty {
+            const VALUE_TYPE: ValueType = ValueType::$value_type;
+            const MIN: i64 = $min as i64;
+            const MAX: i64 = $max as i64;
+        }
+    };
+}
+
+value!(bool, Bool, 0, 1);
+value!(u8, U8, u8::MIN, u8::MAX);
+value!(u16, U16, u16::MIN, u16::MAX);
+value!(u32, U32, u32::MIN, u32::MAX);
+value!(i32, I32, i32::MIN, i32::MAX);
+
+/// The axis and global parameters of a module.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct ParameterTable {
+    pub axis: &'static [ParameterInfo],
+    pub global: &'static [ParameterInfo],
+}
+
+impl ParameterTable {
+    /// Returns the axis parameter with the given number
+    pub fn axis_parameter(&self, number: u8) -> Option<&'static ParameterInfo> {
+        self.axis.iter().find(|p| p.number == number)
+    }
+
+    /// Returns the axis parameter with the given name
+    pub fn axis_parameter_by_name(&self, name: &str) -> Option<&'static ParameterInfo> {
+        self.axis.iter().find(|p| p.name == name)
+    }
+
+    /// Returns the global parameter with the given bank and number
+    pub fn global_parameter(&self, bank: u8, number: u8) -> Option<&'static ParameterInfo> {
+        self.global
+            .iter()
+            .find(|p| p.bank == Some(bank) && p.number == number)
+    }
+
+    /// Returns the global parameter with the given name
+    pub fn global_parameter_by_name(&self, name: &str) -> Option<&'static ParameterInfo> {
+        self.global.iter().find(|p| p.name == name)
+    }
+}
@@ -4,7 +4,12 @@ use instructions::DirectInstruction;
 use instructions::Instruction;
 
 pub use instructions::{
-    MoveOperation, ReferenceSearchAction, CALC, GIO, MST, MVP, RFS, ROL, ROR, SIO,
+    ApplicationStatus, EnterDownloadMode, ErrorFlag, ExitDownloadMode, FirmwareVersion,
+    GetApplicationStatus, GetFirmwareVersion, GetFirmwareVersionAscii, InterruptVector,
+    JumpCondition, MoveOperation, ProgramMemoryPart, ReadProgramMemory, ReferenceSearchAction,
+    ResetApplication, RestoreFactoryDefaults, RunApplication, StepApplication, StopApplication,
+    WaitEvent, ACO, CALC, CALCX, CCO, CLE, COMP, CSUB, DI, EI, GCO, GIO, JA, JC, MST, MVP, RETI,
+    RFS, ROL, ROR, RSUB, SCO, SIO, STOP, VECT, WAIT,
 };
 
 /// SAP - Set Axis Parameter
@@ -14,6 +19,7 @@ pub use instructions::{
 /// and physical locations (TMC428, TMC453, controller RAM, controller EEPROM),
 /// they all can be set by this function.
 #[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
 pub struct SAP {
     motor_number: u8,
     parameter_number: u8,
@@ -54,6 +60,7 @@ impl DirectInstruction for SAP {
 /// and physical locations (TMC428, TMC453, controller RAM, controller EEPROM),
 /// they all can be read by this function.
 #[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
 pub struct GAP {
     motor_number: u8,
     parameter_number: u8,
@@ -90,6 +97,7 @@ impl DirectInstruction for GAP {
 /// Axis parameters are located in RAM memory, so modifications are lost at power down.
 /// This instruction enables permanent storing.
 #[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
 pub struct STAP {
     motor_number: u8,
     parameter_number: u8,
@@ -127,13 +135,14 @@ impl DirectInstruction for STAP {
 /// By default, most parameters are automatically restored after power up (see axis parameter list in
 /// chapter 4). A single parameter that has been changed before can be reset by this instruction.
 #[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
 pub struct RSAP {
     motor_number: u8,
     parameter_number: u8,
 }
 impl RSAP {
-    pub fn new(motor_number: u8, parameter_number: u8) -> STAP {
-        STAP {
+    pub fn new(motor_number: u8, parameter_number: u8) -> RSAP {
+        RSAP {
             motor_number,
             parameter_number,
         }
@@ -157,3 +166,222 @@ impl Instruction for RSAP {
 impl DirectInstruction for RSAP {
     type Return = ();
 }
+
+/// SGP - Set Global Parameter
+///
+/// Global parameters are related to the host interface, peripherals or application specific
+/// variables. The different groups of these parameters are organized in "banks" to allow a larger
+/// total number for future products. Currently, only bank 0 and 1 are used for global parameters,
+/// and bank 2 is used for user variables.
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub struct SGP {
+    bank_number: u8,
+    parameter_number: u8,
+    operand: [u8; 4],
+}
+impl SGP {
+    pub fn new(bank_number: u8, parameter_number: u8, operand: [u8; 4]) -> SGP {
+        SGP {
+            bank_number,
+            parameter_number,
+            operand,
+        }
+    }
+}
+impl Instruction for SGP {
+    const INSTRUCTION_NUMBER: u8 = 9;
+
+    fn operand(&self) -> [u8; 4] {
+        self.operand
+    }
+
+    fn type_number(&self) -> u8 {
+        self.parameter_number
+    }
+
+    fn motor_bank_number(&self) -> u8 {
+        self.bank_number
+    }
+}
+impl DirectInstruction for SGP {
+    type Return = ();
+}
+
+/// GGP - Get Global Parameter
+///
+/// All global parameters can be read with this function.
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub struct GGP {
+    bank_number: u8,
+    parameter_number: u8,
+}
+impl GGP {
+    pub fn new(bank_number: u8, parameter_number: u8) -> GGP {
+        GGP {
+            bank_number,
+            parameter_number,
+        }
+    }
+}
+impl Instruction for GGP {
+    const INSTRUCTION_NUMBER: u8 = 10;
+
+    fn operand(&self) -> [u8; 4] {
+        [0u8, 0u8, 0u8, 0u8]
+    }
+
+    fn type_number(&self) -> u8 {
+        self.parameter_number
+    }
+
+    fn motor_bank_number(&self) -> u8 {
+        self.bank_number
+    }
+}
+impl DirectInstruction for GGP {
+    type Return = [u8; 4];
+}
+
+/// STGP - Store Global Parameter
+///
+/// Some global parameters are located in RAM memory, so modifications are lost at power down.
+/// This instruction enables permanent storing.
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub struct STGP {
+    bank_number: u8,
+    parameter_number: u8,
+}
+impl STGP {
+    pub fn new(bank_number: u8, parameter_number: u8) -> STGP {
+        STGP {
+            bank_number,
+            parameter_number,
+        }
+    }
+}
+impl Instruction for STGP {
+    const INSTRUCTION_NUMBER: u8 = 11;
+
+    fn operand(&self) -> [u8; 4] {
+        [0u8, 0u8, 0u8, 0u8]
+    }
+
+    fn type_number(&self) -> u8 {
+        self.parameter_number
+    }
+
+    fn motor_bank_number(&self) -> u8 {
+        self.bank_number
+    }
+}
+impl DirectInstruction for STGP {
+    type Return = ();
+}
+
+/// RSGP - Restore Global Parameter
+///
+/// With this command the contents of a global parameter can be restored from its
+/// non-volatile memory location.
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub struct RSGP {
+    bank_number: u8,
+    parameter_number: u8,
+}
+impl RSGP {
+    pub fn new(bank_number: u8, parameter_number: u8) -> RSGP {
+        RSGP {
+            bank_number,
+            parameter_number,
+        }
+    }
+}
+impl Instruction for RSGP {
+    const INSTRUCTION_NUMBER: u8 = 12;
+
+    fn operand(&self) -> [u8; 4] {
+        [0u8, 0u8, 0u8, 0u8]
+    }
+
+    fn type_number(&self) -> u8 {
+        self.parameter_number
+    }
+
+    fn motor_bank_number(&self) -> u8 {
+        self.bank_number
+    }
+}
+impl DirectInstruction for RSGP {
+    type Return = ();
+}
+
+/// AAP - Accumulator to Axis Parameter
+///
+/// The content of the accumulator register is transferred to the specified axis parameter.
+/// This command is intended for use in standalone operation only.
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub struct AAP {
+    motor_number: u8,
+    parameter_number: u8,
+}
+impl AAP {
+    pub fn new(motor_number: u8, parameter_number: u8) -> AAP {
+        AAP {
+            motor_number,
+            parameter_number,
+        }
+    }
+}
+impl Instruction for AAP {
+    const INSTRUCTION_NUMBER: u8 = 34;
+
+    fn operand(&self) -> [u8; 4] {
+        [0u8, 0u8, 0u8, 0u8]
+    }
+
+    fn type_number(&self) -> u8 {
+        self.parameter_number
+    }
+
+    fn motor_bank_number(&self) -> u8 {
+        self.motor_number
+    }
+}
+
+/// AGP - Accumulator to Global Parameter
+///
+/// The content of the accumulator register is transferred to the specified global parameter.
+/// This command is intended for use in standalone operation only.
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub struct AGP {
+    bank_number: u8,
+    parameter_number: u8,
+}
+impl AGP {
+    pub fn new(bank_number: u8, parameter_number: u8) -> AGP {
+        AGP {
+            bank_number,
+            parameter_number,
+        }
+    }
+}
+impl Instruction for AGP {
+    const INSTRUCTION_NUMBER: u8 = 35;
+
+    fn operand(&self) -> [u8; 4] {
+        [0u8, 0u8, 0u8, 0u8]
+    }
+
+    fn type_number(&self) -> u8 {
+        self.parameter_number
+    }
+
+    fn motor_bank_number(&self) -> u8 {
+        self.bank_number
+    }
+}
@@ -4,15 +4,32 @@
 //! It is therefore preferable to use a less generic module that will fail to compile if
 //! it is attempted to write to a read only register and such.
 //! This module is only recommended to use if no such module exists.
+//!
+//! Writes with `SAP` and `SGP` to parameters described by the parameter table of the module are
+//! checked before sending them, see `metadata`. Other parameters are written unchecked.
 
 pub mod instructions;
 
 use lib::marker::PhantomData;
 use lib::ops::Deref;
+use lib::sync::atomic::{AtomicU8, Ordering};
 
 use interior_mut::InteriorMut;
 
+#[cfg(feature = "async")]
+use AsyncInterface;
+#[cfg(feature = "async")]
+use WriteCommand;
+
 use instructions::DirectInstruction;
+use metadata::ParameterTable;
+use modules::readdress;
+#[cfg(feature = "std")]
+use modules::snapshot;
+use modules::tmcm::metadata::PARAMETERS;
+use modules::AddressChange;
+use program;
+use program::ProgramWord;
 use Command;
 use Error;
 use Instruction;
@@ -22,50 +39,271 @@ use Status;
 
 /// This type represents a generic TMCM module.
 #[derive(Debug)]
-pub struct GenericModule<
-    'a,
-    IF: Interface + 'a,
-    Cell: InteriorMut<'a, IF>,
-    T: Deref<Target = Cell> + 'a,
-> {
+pub struct GenericModule<'a, IF: 'a, Cell: InteriorMut<'a, IF>, T: Deref<Target = Cell> + 'a> {
     /// The module address
-    address: u8,
+    address: AtomicU8,
     interface: T,
+    parameters: &'static ParameterTable,
     pd1: PhantomData<&'a IF>,
     pd2: PhantomData<&'a T>,
 }
 
-impl<'a, IF: Interface, Cell: InteriorMut<'a, IF>, T: Deref<Target = Cell>>
-    GenericModule<'a, IF, Cell, T>
-{
-    /// Create a new module
+impl<'a, IF, Cell: InteriorMut<'a, IF>, T: Deref<Target = Cell>> GenericModule<'a, IF, Cell, T> {
+    /// Create a new module, checking writes against `modules::tmcm::metadata::PARAMETERS`
     pub fn new(interface: T, address: u8) -> Self {
+        Self::with_parameters(interface, address, &PARAMETERS)
+    }
+
+    /// Create a new module, checking writes against the given parameter table
+    pub fn with_parameters(interface: T, address: u8, parameters: &'static ParameterTable) -> Self {
         GenericModule {
-            address,
+            address: AtomicU8::new(address),
             interface,
+            parameters,
             pd1: PhantomData {},
             pd2: PhantomData {},
         }
     }
 
+    /// Returns the module address
+    pub fn address(&self) -> u8 {
+        self.address.load(Ordering::Relaxed)
+    }
+
+    /// Returns the parameter table writes are checked against
+    pub fn parameters(&self) -> &'static ParameterTable {
+        self.parameters
+    }
+
+    /// Check a `SAP` or `SGP` against the parameter table
+    fn check_parameter<Inst: Instruction, E>(&self, instruction: &Inst) -> Result<(), Error<E>> {
+        let number = instruction.type_number();
+        let parameter = match Inst::INSTRUCTION_NUMBER {
+            // SAP
+            5 => self.parameters.axis_parameter(number),
+            // SGP
+            9 => self
+                .parameters
+                .global_parameter(instruction.motor_bank_number(), number),
+            _ => None,
+        };
+        match parameter {
+            Some(parameter) if !parameter.access.is_writeable() => {
+                Err(Error::ReadOnlyParameter(number))
+            }
+            Some(parameter) if !parameter.is_valid(parameter.value(instruction.operand())) => {
+                Err(Error::InvalidParameterValue(number))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+impl<'a, IF: Interface, Cell: InteriorMut<'a, IF>, T: Deref<Target = Cell>>
+    GenericModule<'a, IF, Cell, T>
+{
     /// Synchronously write a command and wait for the Reply
+    ///
+    /// Writes to parameters in the parameter table of the module are checked first, returning
+    /// `Error::ReadOnlyParameter` or `Error::InvalidParameterValue` without sending them.
     pub fn write_command<Inst: Instruction + DirectInstruction>(
         &'a self,
         instruction: Inst,
     ) -> Result<Inst::Return, Error<IF::Error>> {
+        self.check_parameter(&instruction)?;
         let mut interface = self
             .interface
             .borrow_int_mut()
             .or(Err(Error::InterfaceUnavailable))?;
         interface
-            .transmit_command(&Command::new(self.address, instruction))
-            .map_err(|e| Error::InterfaceError(e))?;
-        let reply = interface
-            .receive_reply()
-            .map_err(|e| Error::InterfaceError(e))?;
+            .transmit_command(&Command::new(self.address(), instruction))
+            .map_err(Error::InterfaceError)?;
+        let reply = interface.receive_reply().map_err(Error::InterfaceError)?;
         match reply.status() {
             Status::Ok(_) => Ok(<Inst::Return as Return>::from_operand(reply.operand())),
             Status::Err(e) => Err(e.into()),
         }
     }
+
+    /// Write an instruction to the TMCL program memory of the module.
+    ///
+    /// The module must be in download mode, otherwise it will execute the instruction
+    /// instead and `Error::NotLoadedIntoEEPROM` is returned. Returns the stored program word,
+    /// which can be used to verify the download with `verify_program`.
+    pub fn store_instruction<Inst: Instruction>(
+        &'a self,
+        instruction: Inst,
+    ) -> Result<ProgramWord, Error<IF::Error>> {
+        let mut interface = self
+            .interface
+            .borrow_int_mut()
+            .or(Err(Error::InterfaceUnavailable))?;
+        program::store_instruction(&mut *interface, self.address(), instruction)
+    }
+
+    /// Change the address the module is reached at, store it to EEPROM and use it from now on.
+    ///
+    /// The new value is written to the global parameter and must be read back at the new address
+    /// before it is stored to EEPROM. Otherwise the old value is written back and the error
+    /// returned. If another module already answers at the new address `Error::AddressInUse` is
+    /// returned and nothing is written. Use `readdress_with` if the interface has to be
+    /// reconfigured for the change.
+    pub fn readdress(&'a self, change: AddressChange) -> Result<(), Error<IF::Error>> {
+        self.readdress_with(change, |_, _| Ok(()))
+    }
+
+    /// Change the address the module is reached at, reconfiguring the interface with `reopen`.
+    ///
+    /// `reopen` is called with the interface after the new value is written, and again with the
+    /// old value if the module does not answer at the new address.
+    pub fn readdress_with<F>(
+        &'a self,
+        change: AddressChange,
+        reopen: F,
+    ) -> Result<(), Error<IF::Error>>
+    where
+        F: FnMut(&mut IF, AddressChange) -> Result<(), IF::Error>,
+    {
+        let mut interface = self
+            .interface
+            .borrow_int_mut()
+            .or(Err(Error::InterfaceUnavailable))?;
+        let address = readdress::readdress(&mut *interface, self.address(), change, reopen)?;
+        self.address.store(address, Ordering::Relaxed);
+        Ok(())
+    }
+
+    /// Read the parameters in `parameters` of the first `motors` motors into a snapshot.
+    ///
+    /// Parameters the module reports as unknown are left out.
+    #[cfg(feature = "std")]
+    pub fn snapshot(
+        &'a self,
+        motors: u8,
+        parameters: &snapshot::ParameterList,
+    ) -> Result<snapshot::Snapshot, Error<IF::Error>> {
+        let mut interface = self
+            .interface
+            .borrow_int_mut()
+            .or(Err(Error::InterfaceUnavailable))?;
+        snapshot::read(&mut *interface, self.address(), motors, parameters)
+    }
+
+    /// Write the parameters of `snapshot` that `parameters` marks for restoring to the module.
+    ///
+    /// If `store` is set every parameter is also stored to EEPROM with `STAP` or `STGP`. Nothing is
+    /// written if the snapshot holds a motor above the first `motors` motors or a parameter the
+    /// module does not have, and the values written so far are set back if writing one fails.
+    #[cfg(feature = "std")]
+    pub fn restore(
+        &'a self,
+        snapshot: &snapshot::Snapshot,
+        parameters: &snapshot::ParameterList,
+        motors: u8,
+        store: bool,
+    ) -> Result<(), Error<IF::Error>> {
+        let mut interface = self
+            .interface
+            .borrow_int_mut()
+            .or(Err(Error::InterfaceUnavailable))?;
+        snapshot::restore(
+            &mut *interface,
+            self.address(),
+            snapshot,
+            parameters,
+            motors,
+            store,
+        )
+    }
+
+    /// Download a TMCL program to the module, starting at `start_address`.
+    ///
+    /// Puts the module in download mode, calls `f` to store the program with `store_instruction`
+    /// and leaves download mode again. Download mode is left even if `f` returns an error.
+    pub fn download<F, R>(&'a self, start_address: u32, f: F) -> Result<R, Error<IF::Error>>
+    where
+        F: FnOnce(&'a Self) -> Result<R, Error<IF::Error>>,
+    {
+        program::download(&*self.interface, self.address(), start_address, || f(self))
+    }
+
+    /// Read back the instruction stored at `address` in the TMCL program memory.
+    pub fn read_program_word(&'a self, address: u32) -> Result<ProgramWord, Error<IF::Error>> {
+        let mut interface = self
+            .interface
+            .borrow_int_mut()
+            .or(Err(Error::InterfaceUnavailable))?;
+        program::read_program_word(&mut *interface, self.address(), address)
+    }
+
+    /// Compare the TMCL program memory starting at `start_address` with `program`.
+    ///
+    /// Returns `Error::VerificationFailed` with the address of the first instruction that differs.
+    pub fn verify_program(
+        &'a self,
+        start_address: u32,
+        program: &[ProgramWord],
+    ) -> Result<(), Error<IF::Error>> {
+        let mut interface = self
+            .interface
+            .borrow_int_mut()
+            .or(Err(Error::InterfaceUnavailable))?;
+        program::verify_program(&mut *interface, self.address(), start_address, program)
+    }
+}
+
+#[cfg(feature = "async")]
+impl<'a, IF: AsyncInterface, Cell: InteriorMut<'a, IF>, T: Deref<Target = Cell>>
+    GenericModule<'a, IF, Cell, T>
+{
+    /// Write a command and asynchronously wait for the Reply
+    ///
+    /// The interface is only borrowed while the returned future is polled.
+    pub fn write_command_async<Inst: Instruction + DirectInstruction>(
+        &'a self,
+        instruction: Inst,
+    ) -> WriteCommand<'a, IF, Cell, T, Inst> {
+        match self.check_parameter(&instruction) {
+            Ok(()) => WriteCommand::new(&self.interface, self.address(), instruction),
+            Err(e) => WriteCommand::failed(&self.interface, e),
+        }
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+
+    use std::cell::RefCell;
+
+    use modules::generic::instructions::{SAP, SGP};
+    use simulator::SimulatedModule;
+
+    #[test]
+    fn checked_writes() {
+        let interface = RefCell::new(SimulatedModule::new(1, 1));
+        let module = GenericModule::new(&interface, 1);
+
+        assert_eq!(module.write_command(SAP::new(0, 154, [5, 0, 0, 0])), Ok(()));
+        assert_eq!(
+            module.write_command(SAP::new(0, 154, [14, 0, 0, 0])),
+            Err(Error::InvalidParameterValue(154))
+        );
+        assert_eq!(
+            module.write_command(SAP::new(0, 3, [0, 0, 0, 0])),
+            Err(Error::ReadOnlyParameter(3))
+        );
+        assert_eq!(
+            module.write_command(SGP::new(0, 66, [0, 0, 0, 0])),
+            Err(Error::InvalidParameterValue(66))
+        );
+        assert_eq!(interface.borrow().axis_parameter(0, 154), Some(5));
+
+        // Parameters missing in the table are sent unchecked
+        assert_eq!(
+            module.write_command(SGP::new(2, 0, [0xff, 0xff, 0xff, 0xff])),
+            Ok(())
+        );
+        assert_eq!(interface.borrow().global_parameter(2, 0), Some(-1));
+    }
 }
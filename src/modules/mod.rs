@@ -1,5 +1,394 @@
 //! Implementation of functionality special for different hardware modules
 
+use Command;
+use Error;
+use Instruction;
+use Interface;
+use Status;
+
+/// Defines the type representing a single TMCM product.
+///
+/// Product modules only accept instructions implementing `$instruction`, and reject
+/// instructions addressing a motor above `$motors` with `Error::InvalidMotor`.
+macro_rules! product_module {
+    ($(#[$doc:meta])* $name:ident, $instruction:ident, $motors:expr) => {
+        $(#[$doc])*
+        #[derive(Debug)]
+        pub struct $name<
+            'a,
+            IF: 'a,
+            Cell: ::interior_mut::InteriorMut<'a, IF>,
+            T: ::lib::ops::Deref<Target = Cell> + 'a,
+        > {
+            /// The module address
+            address: ::lib::sync::atomic::AtomicU8,
+            interface: T,
+            pd1: ::lib::marker::PhantomData<&'a IF>,
+            pd2: ::lib::marker::PhantomData<&'a T>,
+        }
+
+        impl<'a, IF, Cell: ::interior_mut::InteriorMut<'a, IF>, T: ::lib::ops::Deref<Target = Cell>>
+            $name<'a, IF, Cell, T>
+        {
+            /// The number of motors of the module
+            pub const MOTORS: u8 = $motors;
+
+            /// Create a new module
+            pub fn new(interface: T, address: u8) -> Self {
+                $name {
+                    address: ::lib::sync::atomic::AtomicU8::new(address),
+                    interface,
+                    pd1: ::lib::marker::PhantomData {},
+                    pd2: ::lib::marker::PhantomData {},
+                }
+            }
+
+            /// Returns the module address
+            pub fn address(&self) -> u8 {
+                self.address.load(::lib::sync::atomic::Ordering::Relaxed)
+            }
+        }
+
+        impl<
+                'a,
+                IF: ::Interface,
+                Cell: ::interior_mut::InteriorMut<'a, IF>,
+                T: ::lib::ops::Deref<Target = Cell>,
+            > $name<'a, IF, Cell, T>
+        {
+
+            /// Synchronously write a command and wait for the Reply
+            pub fn write_command<Instruction: $instruction + ::instructions::DirectInstruction>(
+                &'a self,
+                instruction: Instruction,
+            ) -> Result<Instruction::Return, ::Error<IF::Error>> {
+                self.check_motor(&instruction)?;
+                let mut interface = self
+                    .interface
+                    .borrow_int_mut()
+                    .or(Err(::Error::InterfaceUnavailable))?;
+                interface
+                    .transmit_command(&::Command::new(self.address(), instruction))
+                    .map_err(::Error::InterfaceError)?;
+                let reply = interface.receive_reply().map_err(::Error::InterfaceError)?;
+                match reply.status() {
+                    ::Status::Ok(_) => Ok(<Instruction::Return as ::Return>::from_operand(
+                        reply.operand(),
+                    )),
+                    ::Status::Err(e) => Err(e.into()),
+                }
+            }
+
+            /// Write an instruction to the TMCL program memory of the module.
+            ///
+            /// The module must be in download mode, otherwise it will execute the instruction
+            /// instead and `Error::NotLoadedIntoEEPROM` is returned. Returns the stored program
+            /// word, which can be used to verify the download with `verify_program`.
+            pub fn store_instruction<Instruction: $instruction>(
+                &'a self,
+                instruction: Instruction,
+            ) -> Result<::program::ProgramWord, ::Error<IF::Error>> {
+                self.check_motor(&instruction)?;
+                let mut interface = self
+                    .interface
+                    .borrow_int_mut()
+                    .or(Err(::Error::InterfaceUnavailable))?;
+                ::program::store_instruction(&mut *interface, self.address(), instruction)
+            }
+
+            /// Change the address the module is reached at, store it to EEPROM and use it from now on.
+            ///
+            /// The new value is written to the global parameter and must be read back at the new
+            /// address before it is stored to EEPROM. Otherwise the old value is written back and the
+            /// error returned. If another module already answers at the new address
+            /// `Error::AddressInUse` is returned and nothing is written. Use `readdress_with` if the
+            /// interface has to be reconfigured for the change.
+            pub fn readdress(
+                &'a self,
+                change: ::modules::AddressChange,
+            ) -> Result<(), ::Error<IF::Error>> {
+                self.readdress_with(change, |_, _| Ok(()))
+            }
+
+            /// Change the address the module is reached at, reconfiguring the interface with
+            /// `reopen`.
+            ///
+            /// `reopen` is called with the interface after the new value is written, and again with
+            /// the old value if the module does not answer at the new address.
+            pub fn readdress_with<F>(
+                &'a self,
+                change: ::modules::AddressChange,
+                reopen: F,
+            ) -> Result<(), ::Error<IF::Error>>
+            where
+                F: FnMut(&mut IF, ::modules::AddressChange) -> Result<(), IF::Error>,
+            {
+                let mut interface = self
+                    .interface
+                    .borrow_int_mut()
+                    .or(Err(::Error::InterfaceUnavailable))?;
+                let address = ::modules::readdress::readdress(
+                    &mut *interface,
+                    self.address(),
+                    change,
+                    reopen,
+                )?;
+                self.address.store(address, ::lib::sync::atomic::Ordering::Relaxed);
+                Ok(())
+            }
+
+            /// Read all parameters in `snapshot::TMCM_PARAMETERS` of all motors into a snapshot.
+            ///
+            /// Parameters the module does not have are left out.
+            #[cfg(feature = "std")]
+            pub fn snapshot(&'a self) -> Result<::modules::snapshot::Snapshot, ::Error<IF::Error>> {
+                let mut interface = self
+                    .interface
+                    .borrow_int_mut()
+                    .or(Err(::Error::InterfaceUnavailable))?;
+                ::modules::snapshot::read(
+                    &mut *interface,
+                    self.address(),
+                    Self::MOTORS,
+                    &::modules::snapshot::TMCM_PARAMETERS,
+                )
+            }
+
+            /// Write the settings of `snapshot` back to the module.
+            ///
+            /// Only the parameters `snapshot::TMCM_PARAMETERS` marks for restoring are written. If
+            /// `store` is set every parameter is also stored to EEPROM with `STAP` or `STGP`. Nothing
+            /// is written if the snapshot holds a motor or parameter the module does not have, and
+            /// the values written so far are set back if writing one fails.
+            #[cfg(feature = "std")]
+            pub fn restore(
+                &'a self,
+                snapshot: &::modules::snapshot::Snapshot,
+                store: bool,
+            ) -> Result<(), ::Error<IF::Error>> {
+                let mut interface = self
+                    .interface
+                    .borrow_int_mut()
+                    .or(Err(::Error::InterfaceUnavailable))?;
+                ::modules::snapshot::restore(
+                    &mut *interface,
+                    self.address(),
+                    snapshot,
+                    &::modules::snapshot::TMCM_PARAMETERS,
+                    Self::MOTORS,
+                    store,
+                )
+            }
+
+            /// Download a TMCL program to the module, starting at `start_address`.
+            ///
+            /// Puts the module in download mode, calls `f` to store the program with
+            /// `store_instruction` and leaves download mode again. Download mode is left even if
+            /// `f` returns an error.
+            pub fn download<F, R>(
+                &'a self,
+                start_address: u32,
+                f: F,
+            ) -> Result<R, ::Error<IF::Error>>
+            where
+                F: FnOnce(&'a Self) -> Result<R, ::Error<IF::Error>>,
+            {
+                ::program::download(&*self.interface, self.address(), start_address, || f(self))
+            }
+
+            /// Read back the instruction stored at `address` in the TMCL program memory.
+            pub fn read_program_word(
+                &'a self,
+                address: u32,
+            ) -> Result<::program::ProgramWord, ::Error<IF::Error>> {
+                let mut interface = self
+                    .interface
+                    .borrow_int_mut()
+                    .or(Err(::Error::InterfaceUnavailable))?;
+                ::program::read_program_word(&mut *interface, self.address(), address)
+            }
+
+            /// Compare the TMCL program memory starting at `start_address` with `program`.
+            ///
+            /// Returns `Error::VerificationFailed` with the address of the first instruction that
+            /// differs.
+            pub fn verify_program(
+                &'a self,
+                start_address: u32,
+                program: &[::program::ProgramWord],
+            ) -> Result<(), ::Error<IF::Error>> {
+                let mut interface = self
+                    .interface
+                    .borrow_int_mut()
+                    .or(Err(::Error::InterfaceUnavailable))?;
+                ::program::verify_program(&mut *interface, self.address(), start_address, program)
+            }
+
+            fn check_motor<Instruction: ::Instruction>(
+                &self,
+                instruction: &Instruction,
+            ) -> Result<(), ::Error<IF::Error>> {
+                match ::modules::highest_motor(instruction) {
+                    Some(motor) if motor >= Self::MOTORS => Err(::Error::InvalidMotor(
+                        instruction.motor_bank_number(),
+                    )),
+                    _ => Ok(()),
+                }
+            }
+        }
+
+        #[cfg(feature = "async")]
+        impl<
+                'a,
+                IF: ::AsyncInterface,
+                Cell: ::interior_mut::InteriorMut<'a, IF>,
+                T: ::lib::ops::Deref<Target = Cell>,
+            > $name<'a, IF, Cell, T>
+        {
+            /// Write a command and asynchronously wait for the Reply
+            ///
+            /// The interface is only borrowed while the returned future is polled.
+            pub fn write_command_async<
+                Instruction: $instruction + ::instructions::DirectInstruction,
+            >(
+                &'a self,
+                instruction: Instruction,
+            ) -> ::WriteCommand<'a, IF, Cell, T, Instruction> {
+                match ::modules::highest_motor(&instruction) {
+                    Some(motor) if motor >= Self::MOTORS => ::WriteCommand::failed(
+                        &self.interface,
+                        ::Error::InvalidMotor(instruction.motor_bank_number()),
+                    ),
+                    _ => ::WriteCommand::new(&self.interface, self.address(), instruction),
+                }
+            }
+        }
+    };
+}
+
+/// Implements the instruction marker trait of a product for all instructions in
+/// `modules::tmcm::instructions`.
+///
+/// The per-product modules all support the complete TMCL instruction set, so they share this
+/// definition. The instructions must be in scope where the macro is used.
+macro_rules! product_instructions {
+    (
+        $instruction:ident,
+        $readable_axis:ident,
+        $writeable_axis:ident,
+        $readable_global:ident,
+        $writeable_global:ident
+    ) => {
+        impl<T: $writeable_axis> $instruction for SAP<T> {}
+        impl<T: $readable_axis> $instruction for GAP<T> {}
+        impl<T: $writeable_axis> $instruction for STAP<T> {}
+        impl<T: $writeable_axis> $instruction for RSAP<T> {}
+        impl<T: $writeable_global> $instruction for SGP<T> {}
+        impl<T: $readable_global> $instruction for GGP<T> {}
+        impl<T: $writeable_global> $instruction for STGP<T> {}
+        impl<T: $writeable_global> $instruction for RSGP<T> {}
+        impl<T: $writeable_axis> $instruction for AAP<T> {}
+        impl<T: $writeable_global> $instruction for AGP<T> {}
+        product_instructions!(
+            @impl $instruction,
+            ROR, ROL, MST, MVP, RFS, SIO, GIO, CALC, COMP, JC, JA, CSUB, RSUB, EI, DI, WAIT, STOP,
+            SCO, GCO, CCO, CALCX, CLE, VECT, RETI, ACO, StopApplication, RunApplication,
+            StepApplication, ResetApplication, GetApplicationStatus, GetFirmwareVersion,
+            GetFirmwareVersionAscii, RestoreFactoryDefaults, EnterDownloadMode, ExitDownloadMode,
+            ReadProgramMemory
+        );
+    };
+    (@impl $instruction:ident, $($name:ident),*) => {
+        $(impl $instruction for $name {})*
+    };
+}
+
+/// Implements the global parameter marker traits of a product for the bank 0 and bank 3
+/// parameters in `modules::tmcm::global_parameters` and the `UserVariable` of the product.
+///
+/// The bank 0 interface parameters and the bank 3 interrupt configuration are the same on all
+/// per-product modules, so they share this definition. The parameters must be in scope where the
+/// macro is used.
+macro_rules! product_global_parameters {
+    ($parameter:ident, $readable:ident, $writeable:ident) => {
+        impl<const N: u8> $parameter for UserVariable<N> {}
+        impl<const N: u8> $readable for UserVariable<N> {}
+        impl<const N: u8> $writeable for UserVariable<N> {}
+        product_global_parameters!(
+            @impl $parameter, $readable, $writeable,
+            SerialBaudRate, SerialAddress, CANBitRate, CANReplyId, CANId, EEPROMLock,
+            Timer0Period, Timer1Period, Timer2Period, InputChange0Edge, InputChange1Edge
+        );
+    };
+    (@impl $parameter:ident, $readable:ident, $writeable:ident, $($name:ident),*) => {
+        $(
+            impl $parameter for $name {}
+            impl $readable for $name {}
+            impl $writeable for $name {}
+        )*
+    };
+}
+
 pub mod generic;
+pub mod pd42_1240;
+mod readdress;
+#[cfg(feature = "std")]
+pub mod snapshot;
 pub mod tmcm;
 pub mod tmcm100;
+pub mod tmcm1140;
+pub mod tmcm1160;
+pub mod tmcm6110;
+
+pub use self::readdress::AddressChange;
+
+/// Write `instruction` to the module at `address` and wait for the reply.
+pub(crate) fn write_command<IF: Interface, I: Instruction>(
+    interface: &mut IF,
+    address: u8,
+    instruction: I,
+) -> Result<[u8; 4], Error<IF::Error>> {
+    interface
+        .transmit_command(&Command::new(address, instruction))
+        .map_err(Error::InterfaceError)?;
+    let reply = interface.receive_reply().map_err(Error::InterfaceError)?;
+    match reply.status() {
+        Status::Ok(_) => Ok(reply.operand()),
+        Status::Err(e) => Err(e.into()),
+    }
+}
+
+/// The highest motor addressed by `instruction`, or `None` if it does not address a motor.
+fn highest_motor<T: Instruction>(instruction: &T) -> Option<u8> {
+    let motor = instruction.motor_bank_number();
+    match (T::INSTRUCTION_NUMBER, instruction.type_number()) {
+        // MVP COORD with a motor mask for interpolated moves
+        (4, 2) if motor & 0x40 != 0 => match motor & 0x3f {
+            0 => None,
+            mask => Some(7 - mask.leading_zeros() as u8),
+        },
+        // WAIT for a motor event
+        (27, 1..=4) => Some(motor),
+        (1..=8, _) | (13, _) | (30..=32, _) | (34, _) | (39, _) => Some(motor),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use instructions::{WaitEvent, ACO, MVP, ROR, SIO, WAIT};
+
+    #[test]
+    fn addressed_motors() {
+        assert_eq!(highest_motor(&ROR::new(3, 100)), Some(3));
+        assert_eq!(highest_motor(&MVP::interpolated(0b10_0101, 0)), Some(5));
+        assert_eq!(highest_motor(&WAIT::new(WaitEvent::Ticks, 10)), None);
+        assert_eq!(
+            highest_motor(&WAIT::new(WaitEvent::PositionReached(2), 0)),
+            Some(2)
+        );
+        assert_eq!(highest_motor(&SIO::new(2, 7, true)), None);
+        assert_eq!(highest_motor(&ACO::new(0, 5)), Some(5));
+    }
+}
@@ -0,0 +1,250 @@
+//! All axis parameters useable with PD42-1240 modules.
+//!
+//! Velocities are given in microsteps per second (0..7999744) and accelerations in microsteps
+//! per second squared (0..7629278).
+//! Parameters without module specific ranges are shared with `modules::tmcm::axis_parameters`.
+
+pub use modules::tmcm::axis_parameters::{
+    ActualPosition, ActualSpeed, BoostCurrent, EndSwitchDistance, HomeSearchMode, HomeSwitchState,
+    LastReferencePosition, LeftLimitSwitchDisable, LeftLimitSwitchState, MicrostepResolution,
+    PositionReachedFlag, PowerDownDelay, ReferenceSearchMode, RightLimitSwitchDisable,
+    RightLimitSwitchState, SearchMode, TargetPosition,
+};
+
+use AxisParameter;
+use ReadableAxisParameter;
+use Return;
+use WriteableAxisParameter;
+
+use modules::pd42_1240::{
+    Pd42_1240AxisParameter, ReadablePd42_1240AxisParameter, WriteablePd42_1240AxisParameter,
+};
+
+/// The highest velocity of the module.
+const MAX_VELOCITY: u32 = 7999744;
+
+/// The highest acceleration of the module.
+const MAX_ACCELERATION: u32 = 7629278;
+
+axis_param_rw!(
+    /// The target rotation speed.
+    ///
+    /// The desired speed in velocity mode (-7999744..7999744). Not valid in position mode.
+    TargetSpeed,
+    i32,
+    2,
+    -(MAX_VELOCITY as i32)..=MAX_VELOCITY as i32
+);
+impl TargetSpeed {
+    pub fn new(speed: i32) -> Self {
+        assert!(Self::is_valid(speed));
+        TargetSpeed(speed)
+    }
+}
+impl Pd42_1240AxisParameter for TargetSpeed {}
+impl ReadablePd42_1240AxisParameter for TargetSpeed {}
+impl WriteablePd42_1240AxisParameter for TargetSpeed {}
+
+axis_param_rw!(
+    /// The maximum positioning speed.
+    ///
+    /// Should not exceed the physically highest possible value (0..7999744).
+    MaximumPositioningSpeed,
+    u32,
+    4,
+    0..=MAX_VELOCITY
+);
+impl MaximumPositioningSpeed {
+    pub fn new(speed: u32) -> Self {
+        assert!(Self::is_valid(speed));
+        MaximumPositioningSpeed(speed)
+    }
+}
+impl Pd42_1240AxisParameter for MaximumPositioningSpeed {}
+impl ReadablePd42_1240AxisParameter for MaximumPositioningSpeed {}
+impl WriteablePd42_1240AxisParameter for MaximumPositioningSpeed {}
+
+axis_param_rw!(
+    /// The maximum acceleration.
+    ///
+    /// Maximum acceleration during ramp-up (0..7629278).
+    MaximumAcceleration,
+    u32,
+    5,
+    0..=MAX_ACCELERATION
+);
+impl MaximumAcceleration {
+    pub fn new(acc: u32) -> Self {
+        assert!(Self::is_valid(acc));
+        MaximumAcceleration(acc)
+    }
+}
+impl Pd42_1240AxisParameter for MaximumAcceleration {}
+impl ReadablePd42_1240AxisParameter for MaximumAcceleration {}
+impl WriteablePd42_1240AxisParameter for MaximumAcceleration {}
+
+axis_param_rw!(
+    /// The maximum deceleration.
+    ///
+    /// Maximum deceleration during ramp-down (0..7629278).
+    MaximumDeceleration,
+    u32,
+    17,
+    0..=MAX_ACCELERATION
+);
+impl MaximumDeceleration {
+    pub fn new(acc: u32) -> Self {
+        assert!(Self::is_valid(acc));
+        MaximumDeceleration(acc)
+    }
+}
+impl Pd42_1240AxisParameter for MaximumDeceleration {}
+impl ReadablePd42_1240AxisParameter for MaximumDeceleration {}
+impl WriteablePd42_1240AxisParameter for MaximumDeceleration {}
+
+axis_param_rw!(
+    /// The absolute maximum current
+    ///
+    /// The most important motor setting, since too high values might cause motor damage!
+    /// 255 means 100% of the maximum current of the module.
+    AbsoluteMaxCurrent,
+    u8,
+    6
+);
+impl AbsoluteMaxCurrent {
+    pub fn new(current: u8) -> Self {
+        AbsoluteMaxCurrent(current)
+    }
+}
+impl Pd42_1240AxisParameter for AbsoluteMaxCurrent {}
+impl ReadablePd42_1240AxisParameter for AbsoluteMaxCurrent {}
+impl WriteablePd42_1240AxisParameter for AbsoluteMaxCurrent {}
+
+axis_param_rw!(
+    /// The current used when the motor is not running.
+    ///
+    /// Same scaling as the absolute maximum current. Please see also parameter 214
+    /// (PowerDownDelay).
+    StandbyCurrent,
+    u8,
+    7
+);
+impl StandbyCurrent {
+    pub fn new(current: u8) -> Self {
+        StandbyCurrent(current)
+    }
+}
+impl Pd42_1240AxisParameter for StandbyCurrent {}
+impl ReadablePd42_1240AxisParameter for StandbyCurrent {}
+impl WriteablePd42_1240AxisParameter for StandbyCurrent {}
+
+axis_param_rw!(
+    /// Reference search speed
+    ///
+    /// This value specifes the speed for roughly searching the reference switch (0..7999744).
+    ReferenceSearchSpeed,
+    u32,
+    194,
+    0..=MAX_VELOCITY
+);
+impl ReferenceSearchSpeed {
+    pub fn new(speed: u32) -> Self {
+        assert!(Self::is_valid(speed));
+        ReferenceSearchSpeed(speed)
+    }
+}
+impl Pd42_1240AxisParameter for ReferenceSearchSpeed {}
+impl ReadablePd42_1240AxisParameter for ReferenceSearchSpeed {}
+impl WriteablePd42_1240AxisParameter for ReferenceSearchSpeed {}
+
+axis_param_rw!(
+    /// Reference switch speed
+    ///
+    /// This parameter specifes the speed for searching the switching point (0..7999744).
+    /// It should be slower than parameter 194.
+    ReferenceSwitchSpeed,
+    u32,
+    195,
+    0..=MAX_VELOCITY
+);
+impl ReferenceSwitchSpeed {
+    pub fn new(speed: u32) -> Self {
+        assert!(Self::is_valid(speed));
+        ReferenceSwitchSpeed(speed)
+    }
+}
+impl Pd42_1240AxisParameter for ReferenceSwitchSpeed {}
+impl ReadablePd42_1240AxisParameter for ReferenceSwitchSpeed {}
+impl WriteablePd42_1240AxisParameter for ReferenceSwitchSpeed {}
+
+impl Pd42_1240AxisParameter for TargetPosition {}
+impl ReadablePd42_1240AxisParameter for TargetPosition {}
+impl WriteablePd42_1240AxisParameter for TargetPosition {}
+
+impl Pd42_1240AxisParameter for ActualPosition {}
+impl ReadablePd42_1240AxisParameter for ActualPosition {}
+impl WriteablePd42_1240AxisParameter for ActualPosition {}
+
+impl Pd42_1240AxisParameter for ActualSpeed {}
+impl ReadablePd42_1240AxisParameter for ActualSpeed {}
+
+impl Pd42_1240AxisParameter for PositionReachedFlag {}
+impl ReadablePd42_1240AxisParameter for PositionReachedFlag {}
+
+impl Pd42_1240AxisParameter for HomeSwitchState {}
+impl ReadablePd42_1240AxisParameter for HomeSwitchState {}
+
+impl Pd42_1240AxisParameter for RightLimitSwitchState {}
+impl ReadablePd42_1240AxisParameter for RightLimitSwitchState {}
+
+impl Pd42_1240AxisParameter for LeftLimitSwitchState {}
+impl ReadablePd42_1240AxisParameter for LeftLimitSwitchState {}
+
+impl Pd42_1240AxisParameter for RightLimitSwitchDisable {}
+impl ReadablePd42_1240AxisParameter for RightLimitSwitchDisable {}
+impl WriteablePd42_1240AxisParameter for RightLimitSwitchDisable {}
+
+impl Pd42_1240AxisParameter for LeftLimitSwitchDisable {}
+impl ReadablePd42_1240AxisParameter for LeftLimitSwitchDisable {}
+impl WriteablePd42_1240AxisParameter for LeftLimitSwitchDisable {}
+
+impl Pd42_1240AxisParameter for MicrostepResolution {}
+impl ReadablePd42_1240AxisParameter for MicrostepResolution {}
+impl WriteablePd42_1240AxisParameter for MicrostepResolution {}
+
+impl Pd42_1240AxisParameter for ReferenceSearchMode {}
+impl ReadablePd42_1240AxisParameter for ReferenceSearchMode {}
+impl WriteablePd42_1240AxisParameter for ReferenceSearchMode {}
+
+impl Pd42_1240AxisParameter for EndSwitchDistance {}
+impl ReadablePd42_1240AxisParameter for EndSwitchDistance {}
+
+impl Pd42_1240AxisParameter for LastReferencePosition {}
+impl ReadablePd42_1240AxisParameter for LastReferencePosition {}
+
+impl Pd42_1240AxisParameter for BoostCurrent {}
+impl ReadablePd42_1240AxisParameter for BoostCurrent {}
+impl WriteablePd42_1240AxisParameter for BoostCurrent {}
+
+impl Pd42_1240AxisParameter for PowerDownDelay {}
+impl ReadablePd42_1240AxisParameter for PowerDownDelay {}
+impl WriteablePd42_1240AxisParameter for PowerDownDelay {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use modules::pd42_1240::instructions::SAP;
+    use Command;
+
+    #[test]
+    fn sap_maximum_positioning_speed() {
+        let command = Command::new(1, SAP::new(0, MaximumPositioningSpeed::new(7999744)));
+        assert_eq!(command.serialize_can(), [5, 4, 0, 0, 0x7a, 0x11, 0x00]);
+    }
+
+    #[test]
+    fn sap_maximum_deceleration() {
+        let command = Command::new(1, SAP::new(0, MaximumDeceleration::new(1000)));
+        assert_eq!(command.serialize_can(), [5, 17, 0, 0, 0, 0x03, 0xe8]);
+    }
+}
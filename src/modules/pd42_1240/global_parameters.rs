@@ -0,0 +1,41 @@
+//! All global parameters useable with PD42-1240 modules.
+//!
+//! The module has the bank 0 interface parameters for RS485 and CAN, the 256 user variables in
+//! bank 2 and the interrupt configuration in bank 3. The bank 0 and bank 3 parameters are the
+//! same for all per-product modules, only the number of user variables differs.
+
+pub use modules::tmcm::global_parameters::{
+    CANBitRate, CANId, CANReplyId, EEPROMLock, InputChange0Edge, InputChange1Edge, InterruptEdge,
+    SerialAddress, SerialBaudRate, Timer0Period, Timer1Period, Timer2Period,
+};
+
+use GlobalParameter;
+use ReadableGlobalParameter;
+use Return;
+use WriteableGlobalParameter;
+
+use modules::pd42_1240::{
+    Pd42_1240GlobalParameter, ReadablePd42_1240GlobalParameter, WriteablePd42_1240GlobalParameter,
+};
+
+user_variables!(
+    /// User variable
+    ///
+    /// A general purpose variable, `N` is the variable number (0..255). Only the variables 0..55
+    /// can be stored in EEPROM with `STGP`, the others are lost at power down.
+    ///
+    /// ```
+    /// use tmcl::modules::pd42_1240::global_parameters::UserVariable;
+    /// use tmcl::modules::pd42_1240::instructions::SGP;
+    /// use tmcl::Instruction;
+    ///
+    /// assert_eq!(SGP::new(UserVariable::<200>::new(-5)).type_number(), 200);
+    /// ```
+    UserVariable
+);
+
+product_global_parameters!(
+    Pd42_1240GlobalParameter,
+    ReadablePd42_1240GlobalParameter,
+    WriteablePd42_1240GlobalParameter
+);
@@ -0,0 +1,27 @@
+//! All instructions available for PD42-1240 modules.
+//!
+//! The PD42-1240 supports the complete TMCL instruction set of `modules::tmcm`, so the
+//! instructions are the same for all per-product modules.
+
+pub use instructions::{
+    ApplicationStatus, EnterDownloadMode, ErrorFlag, ExitDownloadMode, FirmwareVersion,
+    GetApplicationStatus, GetFirmwareVersion, GetFirmwareVersionAscii, InterruptVector,
+    JumpCondition, MoveOperation, ProgramMemoryPart, ReadProgramMemory, ReferenceSearchAction,
+    ResetApplication, RestoreFactoryDefaults, RunApplication, StepApplication, StopApplication,
+    WaitEvent, AAP, ACO, AGP, CALC, CALCX, CCO, CLE, COMP, CSUB, DI, EI, GAP, GCO, GGP, GIO, JA,
+    JC, MST, MVP, RETI, RFS, ROL, ROR, RSAP, RSGP, RSUB, SAP, SCO, SGP, SIO, STAP, STGP, STOP,
+    VECT, WAIT,
+};
+
+use modules::pd42_1240::{
+    Pd42_1240Instruction, ReadablePd42_1240AxisParameter, ReadablePd42_1240GlobalParameter,
+    WriteablePd42_1240AxisParameter, WriteablePd42_1240GlobalParameter,
+};
+
+product_instructions!(
+    Pd42_1240Instruction,
+    ReadablePd42_1240AxisParameter,
+    WriteablePd42_1240AxisParameter,
+    ReadablePd42_1240GlobalParameter,
+    WriteablePd42_1240GlobalParameter
+);
@@ -0,0 +1,46 @@
+//! A `TMCM` type usable only with PD42-1240 PANdrives.
+//!
+//! The PD42-1240 is a NEMA 17 stepper motor with an integrated TMCM-1240 single axis
+//! controller. Its ramp generator works in microsteps per second, so it has no pulse and ramp
+//! divisors. Only the axis and global parameters the module actually has are available, with
+//! their valid ranges.
+
+pub mod axis_parameters;
+pub mod global_parameters;
+pub mod instructions;
+
+use AxisParameter;
+use GlobalParameter;
+use Instruction;
+use ReadableAxisParameter;
+use ReadableGlobalParameter;
+use WriteableAxisParameter;
+use WriteableGlobalParameter;
+
+product_module!(
+    /// This type represents a PD42-1240 PANdrive.
+    Pd42_1240Module,
+    Pd42_1240Instruction,
+    1
+);
+
+/// An `Instruction` useable with PD42-1240 modules.
+pub trait Pd42_1240Instruction: Instruction {}
+
+/// An `AxisParameter` useable with PD42-1240 modules.
+pub trait Pd42_1240AxisParameter: AxisParameter {}
+
+/// A `ReadableAxisParameter` useable with PD42-1240 modules.
+pub trait ReadablePd42_1240AxisParameter: ReadableAxisParameter {}
+
+/// A `WriteableAxisParameter` useable with PD42-1240 modules.
+pub trait WriteablePd42_1240AxisParameter: WriteableAxisParameter {}
+
+/// A `GlobalParameter` useable with PD42-1240 modules.
+pub trait Pd42_1240GlobalParameter: GlobalParameter {}
+
+/// A `ReadableGlobalParameter` useable with PD42-1240 modules.
+pub trait ReadablePd42_1240GlobalParameter: ReadableGlobalParameter {}
+
+/// A `WriteableGlobalParameter` useable with PD42-1240 modules.
+pub trait WriteablePd42_1240GlobalParameter: WriteableGlobalParameter {}
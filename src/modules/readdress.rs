@@ -0,0 +1,225 @@
+//! Changing the address a module is reached at.
+
+use modules::generic::instructions::{GGP, SGP, STGP};
+use modules::write_command;
+use Error;
+use Interface;
+use Return;
+
+/// A change of the address a module is reached at, see `TmcmModule::readdress`.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum AddressChange {
+    /// The module address for RS232 and RS485 (global parameter 66)
+    SerialAddress(u8),
+    /// The CAN ID the module listens to for commands (global parameter 71)
+    ///
+    /// Commands are sent with the module address as CAN ID, so only IDs up to 255 can be used.
+    CANId(u8),
+    /// The CAN ID the module replies with (global parameter 70, 0..7ff)
+    CANReplyId(u32),
+}
+
+impl AddressChange {
+    /// Returns the number of the global parameter in bank 0 holding the address
+    pub fn parameter_number(&self) -> u8 {
+        match *self {
+            AddressChange::SerialAddress(_) => 66,
+            AddressChange::CANId(_) => 71,
+            AddressChange::CANReplyId(_) => 70,
+        }
+    }
+
+    /// Returns the new value of the global parameter
+    pub fn value(&self) -> u32 {
+        match *self {
+            AddressChange::SerialAddress(address) => u32::from(address),
+            AddressChange::CANId(id) => u32::from(id),
+            AddressChange::CANReplyId(id) => id,
+        }
+    }
+
+    /// Returns the module address commands are sent to after the change, given the current one.
+    pub fn module_address(&self, current: u8) -> u8 {
+        match *self {
+            AddressChange::SerialAddress(address) | AddressChange::CANId(address) => address,
+            AddressChange::CANReplyId(_) => current,
+        }
+    }
+
+    /// The same change with another value of the global parameter
+    fn with_value(&self, value: u32) -> Self {
+        match *self {
+            AddressChange::SerialAddress(_) => AddressChange::SerialAddress(value as u8),
+            AddressChange::CANId(_) => AddressChange::CANId(value as u8),
+            AddressChange::CANReplyId(_) => AddressChange::CANReplyId(value),
+        }
+    }
+}
+
+/// Apply `change` to the module at `address`, returning the new module address.
+///
+/// When the module address changes, the new address is probed first and `Error::AddressInUse`
+/// returned if any module answers there, so that a module answering at the new address later
+/// can only be this one. The new value is written, `reopen` is called, and the module must
+/// answer reading it back at the new address before it is stored to EEPROM. If any of this
+/// fails the old value is written back to the new address, `reopen` is called with the old value
+/// and the error is returned.
+pub(crate) fn readdress<IF, F>(
+    interface: &mut IF,
+    address: u8,
+    change: AddressChange,
+    mut reopen: F,
+) -> Result<u8, Error<IF::Error>>
+where
+    IF: Interface,
+    F: FnMut(&mut IF, AddressChange) -> Result<(), IF::Error>,
+{
+    let number = change.parameter_number();
+    let old = u32::from_operand(write_command(interface, address, GGP::new(0, number))?);
+    let new_address = change.module_address(address);
+
+    // Any reply, even with an error status, means that the address is taken. Interface errors
+    // are taken to be timeouts; if the interface is broken writing the new value fails as well.
+    if new_address != address {
+        match write_command(interface, new_address, GGP::new(0, number)) {
+            Ok(_) | Err(Error::ProtocolError(_)) => return Err(Error::AddressInUse(new_address)),
+            Err(_) => (),
+        }
+    }
+
+    // The reply may get lost when the module answers from its new address, so only an error
+    // status means that nothing changed.
+    let written = match write_command(
+        interface,
+        address,
+        SGP::new(0, number, change.value().to_le_bytes()),
+    ) {
+        Err(Error::ProtocolError(e)) => return Err(Error::ProtocolError(e)),
+        written => written,
+    };
+
+    let result = reopen(interface, change)
+        .map_err(Error::InterfaceError)
+        .and_then(|()| write_command(interface, new_address, GGP::new(0, number)))
+        .and_then(|value| {
+            if u32::from_operand(value) == change.value() {
+                write_command(interface, new_address, STGP::new(0, number))
+            } else {
+                Err(Error::AddressNotApplied(u32::from_operand(value)))
+            }
+        });
+
+    match result {
+        Ok(_) => Ok(new_address),
+        Err(e) => {
+            // Nothing else answered at the new address before the change, so this only reaches
+            // the module being re-addressed.
+            let _ = reopen(interface, change.with_value(old));
+            let _ = write_command(
+                interface,
+                new_address,
+                SGP::new(0, number, old.to_le_bytes()),
+            );
+            Err(written.err().unwrap_or(e))
+        }
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+
+    use std::cell::RefCell;
+
+    use modules::tmcm::global_parameters::{EEPROMLock, SerialAddress};
+    use modules::tmcm::instructions::{GGP, SGP};
+    use modules::tmcm::TmcmModule;
+    use simulator::{SimulatedBus, SimulatedModule, SimulatorError};
+    use ErrStatus;
+
+    #[test]
+    fn serial_address() {
+        let interface = RefCell::new(SimulatedModule::new(1, 1));
+        let module = TmcmModule::new(&interface, 1);
+
+        assert_eq!(module.readdress(AddressChange::SerialAddress(7)), Ok(()));
+        assert_eq!(module.address(), 7);
+        assert_eq!(
+            module.write_command(GGP::<SerialAddress>::new()),
+            Ok(SerialAddress::new(7))
+        );
+
+        // The new address is stored and survives a reset
+        interface.borrow_mut().reset();
+        assert_eq!(interface.borrow().address(), 7);
+    }
+
+    #[test]
+    fn eeprom_locked() {
+        let interface = RefCell::new(SimulatedModule::new(1, 1));
+        let module = TmcmModule::new(&interface, 1);
+        module
+            .write_command(SGP::new(EEPROMLock::locked()))
+            .unwrap();
+
+        assert_eq!(
+            module.readdress(AddressChange::SerialAddress(7)),
+            Err(Error::ProtocolError(ErrStatus::EEPROMLocked))
+        );
+        assert_eq!(module.address(), 1);
+        assert_eq!(interface.borrow().address(), 1);
+    }
+
+    #[test]
+    fn rollback() {
+        let interface = RefCell::new(SimulatedModule::new(1, 1));
+        let module = TmcmModule::new(&interface, 1);
+
+        let mut reopened = Vec::new();
+        let result = module.readdress_with(AddressChange::SerialAddress(7), |_, change| {
+            reopened.push(change);
+            if change == AddressChange::SerialAddress(7) {
+                Err(SimulatorError::Timeout)
+            } else {
+                Ok(())
+            }
+        });
+        assert_eq!(result, Err(Error::InterfaceError(SimulatorError::Timeout)));
+        assert_eq!(
+            reopened,
+            vec![
+                AddressChange::SerialAddress(7),
+                AddressChange::SerialAddress(1)
+            ]
+        );
+        assert_eq!(module.address(), 1);
+        assert_eq!(
+            module.write_command(GGP::<SerialAddress>::new()),
+            Ok(SerialAddress::new(1))
+        );
+    }
+
+    #[test]
+    fn address_in_use() {
+        let bus = RefCell::new(SimulatedBus::new(vec![
+            SimulatedModule::new(1, 1),
+            SimulatedModule::new(7, 1),
+        ]));
+        let module = TmcmModule::new(&bus, 1);
+
+        assert_eq!(
+            module.readdress(AddressChange::SerialAddress(7)),
+            Err(Error::AddressInUse(7))
+        );
+        assert_eq!(module.address(), 1);
+        assert_eq!(
+            module.write_command(GGP::<SerialAddress>::new()),
+            Ok(SerialAddress::new(1))
+        );
+        let other = TmcmModule::new(&bus, 7);
+        assert_eq!(
+            other.write_command(GGP::<SerialAddress>::new()),
+            Ok(SerialAddress::new(7))
+        );
+    }
+}
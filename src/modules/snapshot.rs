@@ -0,0 +1,581 @@
+//! Backup and restore of all parameters of a module.
+//!
+//! A `Snapshot` holds the raw values of the axis parameters of every motor and of the global
+//! parameters of a module. Which parameters are read, and which of them are written back by a
+//! restore, is given by a `ParameterList`. `TMCM_PARAMETERS` lists the parameters in
+//! `modules::tmcm`, it is used by `TmcmModule` and the per-product modules. Parameters a module
+//! reports as unknown with `ErrStatus::WrongType` are left out of the snapshot, so the list
+//! works for all TMCM modules.
+//!
+//! Snapshots are written to and parsed from a line based text format. Every line holds one
+//! parameter, either `axis <motor> <number> <value>` or `global <bank> <number> <value>`.
+//! Empty lines and lines starting with `#` are ignored.
+//!
+//! ```text
+//! # Spindle drive
+//! axis 0 4 1000
+//! axis 0 5 500
+//! global 3 0 100
+//! ```
+//!
+//! ```no_run
+//! extern crate tmcl;
+//!
+//! use std::cell::RefCell;
+//! use std::fs;
+//!
+//! use tmcl::modules::snapshot::Snapshot;
+//! use tmcl::modules::tmcm1140::Tmcm1140Module;
+//! use tmcl::simulator::SimulatedModule;
+//!
+//! fn main() {
+//!     let interface = RefCell::new(SimulatedModule::new(1, 1));
+//!     let drive = Tmcm1140Module::new(&interface, 1);
+//!     fs::write("drive.txt", drive.snapshot().unwrap().to_string()).unwrap();
+//!
+//!     // Clone the configuration to the replacement drive and store it to EEPROM
+//!     let interface = RefCell::new(SimulatedModule::new(1, 1));
+//!     let replacement = Tmcm1140Module::new(&interface, 1);
+//!     let snapshot: Snapshot = fs::read_to_string("drive.txt").unwrap().parse().unwrap();
+//!     replacement.restore(&snapshot, true).unwrap();
+//! }
+//! ```
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+
+use modules::generic::instructions::{GAP, GGP, SAP, SGP, STAP, STGP};
+use modules::tmcm::axis_parameters::*;
+use modules::tmcm::global_parameters::*;
+use modules::write_command;
+use ErrStatus;
+use Error;
+use Interface;
+use ReadableAxisParameter;
+use ReadableGlobalParameter;
+use Return;
+use WriteableAxisParameter;
+use WriteableGlobalParameter;
+
+/// A parameter read into a `Snapshot`.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct SnapshotParameter {
+    bank: u8,
+    number: u8,
+    restore: bool,
+}
+
+impl SnapshotParameter {
+    /// A raw parameter, `bank` is ignored for axis parameters.
+    ///
+    /// Only parameters with `restore` set are written back by a restore.
+    pub const fn new(bank: u8, number: u8, restore: bool) -> Self {
+        SnapshotParameter {
+            bank,
+            number,
+            restore,
+        }
+    }
+
+    /// An axis parameter that is written back by a restore
+    pub const fn axis_setting<P: WriteableAxisParameter>() -> Self {
+        Self::new(0, P::NUMBER, true)
+    }
+
+    /// An axis parameter that is only read, e.g. because it reflects the state of the motor
+    pub const fn axis_status<P: ReadableAxisParameter>() -> Self {
+        Self::new(0, P::NUMBER, false)
+    }
+
+    /// A global parameter that is written back by a restore
+    pub const fn global_setting<P: WriteableGlobalParameter>() -> Self {
+        Self::new(P::BANK, P::NUMBER, true)
+    }
+
+    /// A global parameter that is only read
+    pub const fn global_status<P: ReadableGlobalParameter>() -> Self {
+        Self::new(P::BANK, P::NUMBER, false)
+    }
+
+    /// Returns the bank number of a global parameter
+    pub fn bank(&self) -> u8 {
+        self.bank
+    }
+
+    /// Returns the parameter number
+    pub fn number(&self) -> u8 {
+        self.number
+    }
+
+    /// Returns true if the parameter is written back by a restore
+    pub fn restore(&self) -> bool {
+        self.restore
+    }
+}
+
+/// The axis and global parameters read into a `Snapshot`.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct ParameterList<'p> {
+    /// Read for every motor
+    pub axis: &'p [SnapshotParameter],
+    pub global: &'p [SnapshotParameter],
+}
+
+/// The parameters in `modules::tmcm`.
+///
+/// Positions, speeds and flags describe the state of the motor and are not restored. Neither
+/// are the interface settings in bank 0, use `readdress` for changing the address of a module.
+/// The user variables 0..55 in bank 2 are restored.
+pub const TMCM_PARAMETERS: ParameterList<'static> = ParameterList {
+    axis: &[
+        SnapshotParameter::axis_status::<TargetPosition>(),
+        SnapshotParameter::axis_status::<ActualPosition>(),
+        SnapshotParameter::axis_status::<TargetSpeed>(),
+        SnapshotParameter::axis_status::<ActualSpeed>(),
+        SnapshotParameter::axis_setting::<MaximumPositioningSpeed>(),
+        SnapshotParameter::axis_setting::<MaximumAcceleration>(),
+        SnapshotParameter::axis_setting::<AbsoluteMaxCurrent>(),
+        SnapshotParameter::axis_setting::<StandbyCurrent>(),
+        SnapshotParameter::axis_status::<PositionReachedFlag>(),
+        SnapshotParameter::axis_status::<HomeSwitchState>(),
+        SnapshotParameter::axis_status::<RightLimitSwitchState>(),
+        SnapshotParameter::axis_status::<LeftLimitSwitchState>(),
+        SnapshotParameter::axis_setting::<RightLimitSwitchDisable>(),
+        SnapshotParameter::axis_setting::<LeftLimitSwitchDisable>(),
+        SnapshotParameter::axis_setting::<MaximumDeceleration>(),
+        SnapshotParameter::axis_setting::<MicrostepResolution>(),
+        SnapshotParameter::axis_setting::<RampDivisor>(),
+        SnapshotParameter::axis_setting::<PulseDivisor>(),
+        SnapshotParameter::axis_setting::<ChopperBlankTime>(),
+        SnapshotParameter::axis_setting::<ChopperMode>(),
+        SnapshotParameter::axis_setting::<HysteresisDecrement>(),
+        SnapshotParameter::axis_setting::<HysteresisEnd>(),
+        SnapshotParameter::axis_setting::<HysteresisStart>(),
+        SnapshotParameter::axis_setting::<ChopperOffTime>(),
+        SnapshotParameter::axis_setting::<SmartEnergyCurrentMinimum>(),
+        SnapshotParameter::axis_setting::<CurrentDownStep>(),
+        SnapshotParameter::axis_setting::<SmartEnergyHysteresis>(),
+        SnapshotParameter::axis_setting::<CurrentUpStep>(),
+        SnapshotParameter::axis_setting::<SmartEnergyHysteresisStart>(),
+        SnapshotParameter::axis_setting::<StallGuard2FilterEnable>(),
+        SnapshotParameter::axis_setting::<StallGuard2Threshold>(),
+        SnapshotParameter::axis_status::<Vsense>(),
+        SnapshotParameter::axis_status::<SmartEnergyActualCurrent>(),
+        SnapshotParameter::axis_setting::<StopOnStall>(),
+        SnapshotParameter::axis_setting::<SmartEnergyThresholdSpeed>(),
+        SnapshotParameter::axis_setting::<SlowRunCurrent>(),
+        SnapshotParameter::axis_setting::<ReferenceSearchMode>(),
+        SnapshotParameter::axis_setting::<ReferenceSearchSpeed>(),
+        SnapshotParameter::axis_setting::<ReferenceSwitchSpeed>(),
+        SnapshotParameter::axis_status::<EndSwitchDistance>(),
+        SnapshotParameter::axis_status::<LastReferencePosition>(),
+        SnapshotParameter::axis_setting::<BoostCurrent>(),
+        SnapshotParameter::axis_setting::<MixedDecayThreshold>(),
+        SnapshotParameter::axis_setting::<FreewheelingDelay>(),
+        SnapshotParameter::axis_status::<ActualLoadValue>(),
+        SnapshotParameter::axis_status::<DriverErrorFlags>(),
+        SnapshotParameter::axis_status::<EncoderPosition>(),
+        SnapshotParameter::axis_setting::<EncoderResolution>(),
+        SnapshotParameter::axis_setting::<MaximumEncoderDeviation>(),
+        SnapshotParameter::axis_setting::<PowerDownDelay>(),
+    ],
+    global: &TMCM_GLOBAL_PARAMETERS,
+};
+
+/// The global parameters of `TMCM_PARAMETERS`, followed by the user variables in bank 2.
+const TMCM_GLOBAL_PARAMETERS: [SnapshotParameter; 67] = {
+    const SETTINGS: [SnapshotParameter; 11] = [
+        SnapshotParameter::global_status::<SerialBaudRate>(),
+        SnapshotParameter::global_status::<SerialAddress>(),
+        SnapshotParameter::global_status::<CANBitRate>(),
+        SnapshotParameter::global_status::<CANReplyId>(),
+        SnapshotParameter::global_status::<CANId>(),
+        SnapshotParameter::global_status::<EEPROMLock>(),
+        SnapshotParameter::global_setting::<Timer0Period>(),
+        SnapshotParameter::global_setting::<Timer1Period>(),
+        SnapshotParameter::global_setting::<Timer2Period>(),
+        SnapshotParameter::global_setting::<InputChange0Edge>(),
+        SnapshotParameter::global_setting::<InputChange1Edge>(),
+    ];
+    let mut parameters = [SnapshotParameter::new(2, 0, true); 67];
+    let mut i = 0;
+    while i < parameters.len() {
+        parameters[i] = if i < SETTINGS.len() {
+            SETTINGS[i]
+        } else {
+            SnapshotParameter::new(2, (i - SETTINGS.len()) as u8, true)
+        };
+        i += 1;
+    }
+    parameters
+};
+
+/// The raw parameter values of a module.
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct Snapshot {
+    /// Indexed by motor and parameter number
+    axis_parameters: BTreeMap<(u8, u8), i32>,
+    /// Indexed by bank and parameter number
+    global_parameters: BTreeMap<(u8, u8), i32>,
+}
+
+impl Snapshot {
+    /// Create an empty snapshot
+    pub fn new() -> Self {
+        Snapshot::default()
+    }
+
+    /// Returns the value of an axis parameter, if it is part of the snapshot
+    pub fn axis_parameter(&self, motor: u8, number: u8) -> Option<i32> {
+        self.axis_parameters.get(&(motor, number)).cloned()
+    }
+
+    /// Set the value of an axis parameter
+    pub fn set_axis_parameter(&mut self, motor: u8, number: u8, value: i32) {
+        self.axis_parameters.insert((motor, number), value);
+    }
+
+    /// Returns the value of a global parameter, if it is part of the snapshot
+    pub fn global_parameter(&self, bank: u8, number: u8) -> Option<i32> {
+        self.global_parameters.get(&(bank, number)).cloned()
+    }
+
+    /// Set the value of a global parameter
+    pub fn set_global_parameter(&mut self, bank: u8, number: u8, value: i32) {
+        self.global_parameters.insert((bank, number), value);
+    }
+
+    /// Returns the motor, parameter number and value of all axis parameters
+    pub fn axis_parameters(&self) -> impl Iterator<Item = (u8, u8, i32)> + '_ {
+        self.axis_parameters
+            .iter()
+            .map(|(&(motor, number), &value)| (motor, number, value))
+    }
+
+    /// Returns the bank, parameter number and value of all global parameters
+    pub fn global_parameters(&self) -> impl Iterator<Item = (u8, u8, i32)> + '_ {
+        self.global_parameters
+            .iter()
+            .map(|(&(bank, number), &value)| (bank, number, value))
+    }
+}
+
+impl fmt::Display for Snapshot {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (motor, number, value) in self.axis_parameters() {
+            writeln!(f, "axis {} {} {}", motor, number, value)?;
+        }
+        for (bank, number, value) in self.global_parameters() {
+            writeln!(f, "global {} {} {}", bank, number, value)?;
+        }
+        Ok(())
+    }
+}
+
+/// A line of a snapshot that is not a parameter.
+///
+/// Contains the number of the line, starting at 1.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct ParseSnapshotError(pub usize);
+
+impl FromStr for Snapshot {
+    type Err = ParseSnapshotError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut snapshot = Snapshot::new();
+        for (index, line) in s.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let error = ParseSnapshotError(index + 1);
+            let words: Vec<&str> = line.split_whitespace().collect();
+            if words.len() != 4 {
+                return Err(error);
+            }
+            let index = words[1].parse().or(Err(error))?;
+            let number = words[2].parse().or(Err(error))?;
+            let value = words[3].parse().or(Err(error))?;
+            match words[0] {
+                "axis" => snapshot.set_axis_parameter(index, number, value),
+                "global" => snapshot.set_global_parameter(index, number, value),
+                _ => return Err(error),
+            }
+        }
+        Ok(snapshot)
+    }
+}
+
+/// Read the parameters in `parameters` from the module at `address`.
+pub(crate) fn read<IF: Interface>(
+    interface: &mut IF,
+    address: u8,
+    motors: u8,
+    parameters: &ParameterList,
+) -> Result<Snapshot, Error<IF::Error>> {
+    let mut snapshot = Snapshot::new();
+    for motor in 0..motors {
+        for parameter in parameters.axis {
+            match write_command(interface, address, GAP::new(motor, parameter.number)) {
+                Ok(value) => {
+                    snapshot.set_axis_parameter(motor, parameter.number, i32::from_operand(value))
+                }
+                Err(Error::ProtocolError(ErrStatus::WrongType)) => (),
+                Err(e) => return Err(e),
+            }
+        }
+    }
+    for parameter in parameters.global {
+        match write_command(
+            interface,
+            address,
+            GGP::new(parameter.bank, parameter.number),
+        ) {
+            Ok(value) => snapshot.set_global_parameter(
+                parameter.bank,
+                parameter.number,
+                i32::from_operand(value),
+            ),
+            Err(Error::ProtocolError(ErrStatus::WrongType)) => (),
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(snapshot)
+}
+
+/// A parameter written by `restore`.
+#[derive(Debug, Clone, Copy)]
+enum Target {
+    /// Motor and parameter number
+    Axis(u8, u8),
+    /// Bank and parameter number
+    Global(u8, u8),
+}
+
+impl Target {
+    fn read<IF: Interface>(self, interface: &mut IF, address: u8) -> Result<i32, Error<IF::Error>> {
+        let value = match self {
+            Target::Axis(motor, number) => {
+                write_command(interface, address, GAP::new(motor, number))?
+            }
+            Target::Global(bank, number) => {
+                write_command(interface, address, GGP::new(bank, number))?
+            }
+        };
+        Ok(i32::from_operand(value))
+    }
+
+    fn write<IF: Interface>(
+        self,
+        interface: &mut IF,
+        address: u8,
+        value: i32,
+        store: bool,
+    ) -> Result<(), Error<IF::Error>> {
+        match self {
+            Target::Axis(motor, number) => {
+                write_command(
+                    interface,
+                    address,
+                    SAP::new(motor, number, value.to_le_bytes()),
+                )?;
+                if store {
+                    write_command(interface, address, STAP::new(motor, number))?;
+                }
+            }
+            Target::Global(bank, number) => {
+                write_command(
+                    interface,
+                    address,
+                    SGP::new(bank, number, value.to_le_bytes()),
+                )?;
+                if store {
+                    write_command(interface, address, STGP::new(bank, number))?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Write the parameters of `snapshot` to be restored according to `parameters` to the module
+/// at `address` with `motors` motors, storing them to EEPROM if `store` is set.
+///
+/// Nothing is written if the snapshot holds a motor the module does not have, or a parameter
+/// the module can not read. The current values are read first, and if writing a value fails
+/// the values written so far are set back to them.
+pub(crate) fn restore<IF: Interface>(
+    interface: &mut IF,
+    address: u8,
+    snapshot: &Snapshot,
+    parameters: &ParameterList,
+    motors: u8,
+    store: bool,
+) -> Result<(), Error<IF::Error>> {
+    let mut targets = Vec::new();
+    for parameter in parameters.axis.iter().filter(|p| p.restore) {
+        let number = parameter.number;
+        for (motor, _, value) in snapshot.axis_parameters().filter(|&(_, n, _)| n == number) {
+            if motor >= motors {
+                return Err(Error::InvalidMotor(motor));
+            }
+            targets.push((Target::Axis(motor, number), value));
+        }
+    }
+    for parameter in parameters.global.iter().filter(|p| p.restore) {
+        let (bank, number) = (parameter.bank, parameter.number);
+        if let Some(value) = snapshot.global_parameter(bank, number) {
+            targets.push((Target::Global(bank, number), value));
+        }
+    }
+
+    let previous = targets
+        .iter()
+        .map(|&(target, _)| target.read(interface, address))
+        .collect::<Result<Vec<_>, _>>()?;
+    for (i, &(target, value)) in targets.iter().enumerate() {
+        if let Err(e) = target.write(interface, address, value, store) {
+            for (&(target, _), &value) in targets[..=i].iter().zip(&previous) {
+                let _ = target.write(interface, address, value, store);
+            }
+            return Err(e);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::cell::RefCell;
+
+    use modules::generic::GenericModule;
+    use modules::tmcm::instructions::SGP;
+    use modules::tmcm::TmcmModule;
+    use modules::tmcm1140::Tmcm1140Module;
+    use simulator::SimulatedModule;
+
+    #[test]
+    fn text_format() {
+        let mut snapshot = Snapshot::new();
+        snapshot.set_global_parameter(3, 0, 100);
+        snapshot.set_axis_parameter(1, 4, 1000);
+        snapshot.set_axis_parameter(0, 174, -10);
+        let text = snapshot.to_string();
+        assert_eq!(text, "axis 0 174 -10\naxis 1 4 1000\nglobal 3 0 100\n");
+        assert_eq!(text.parse(), Ok(snapshot));
+
+        let parsed: Snapshot = "# Comment\n\n  axis 0 4 5  \n".parse().unwrap();
+        assert_eq!(parsed.axis_parameter(0, 4), Some(5));
+        assert_eq!(
+            "axis 0 4 5\nmotor 0 4 5".parse::<Snapshot>(),
+            Err(ParseSnapshotError(2))
+        );
+        assert_eq!("axis 0 4".parse::<Snapshot>(), Err(ParseSnapshotError(1)));
+        assert_eq!(
+            "axis 0 256 1".parse::<Snapshot>(),
+            Err(ParseSnapshotError(1))
+        );
+    }
+
+    #[test]
+    fn clone_configuration() {
+        let mut simulated = SimulatedModule::new(1, 1);
+        simulated.set_axis_parameter(0, 4, 1500);
+        simulated.set_axis_parameter(0, 1, 12345);
+        let interface = RefCell::new(simulated);
+        let snapshot = Tmcm1140Module::new(&interface, 1).snapshot().unwrap();
+        assert_eq!(snapshot.axis_parameter(0, 4), Some(1500));
+        assert_eq!(snapshot.axis_parameter(0, 1), Some(12345));
+        // The simulated module has no separate maximum deceleration
+        assert_eq!(snapshot.axis_parameter(0, 17), None);
+        assert_eq!(snapshot.global_parameter(0, 66), Some(1));
+
+        let interface = RefCell::new(SimulatedModule::new(1, 1));
+        let replacement = Tmcm1140Module::new(&interface, 1);
+        replacement.restore(&snapshot, true).unwrap();
+        interface.borrow_mut().reset();
+        assert_eq!(interface.borrow().axis_parameter(0, 4), Some(1500));
+        // The motor state is not restored
+        assert_eq!(interface.borrow().axis_parameter(0, 1), Some(0));
+    }
+
+    #[test]
+    fn raw_parameters() {
+        let interface = RefCell::new(SimulatedModule::new(1, 2));
+        interface.borrow_mut().set_axis_parameter(1, 6, 200);
+        let module = GenericModule::new(&interface, 1);
+        let parameters = ParameterList {
+            axis: &[SnapshotParameter::new(0, 6, true)],
+            global: &[
+                SnapshotParameter::new(2, 0, true),
+                SnapshotParameter::new(3, 0, false),
+            ],
+        };
+
+        let mut snapshot = module.snapshot(2, &parameters).unwrap();
+        assert_eq!(
+            snapshot.axis_parameters().collect::<Vec<_>>(),
+            vec![(0, 6, 128), (1, 6, 200)]
+        );
+        snapshot.set_global_parameter(2, 0, -7);
+        snapshot.set_global_parameter(3, 0, 1000);
+        module.restore(&snapshot, &parameters, 2, false).unwrap();
+        assert_eq!(interface.borrow().global_parameter(2, 0), Some(-7));
+        assert_eq!(interface.borrow().global_parameter(3, 0), Some(0));
+    }
+
+    #[test]
+    fn user_variables() {
+        let interface = RefCell::new(SimulatedModule::new(1, 1));
+        TmcmModule::new(&interface, 1)
+            .write_command(SGP::new(UserVariable::<55>::new(-3)))
+            .unwrap();
+        let snapshot = Tmcm1140Module::new(&interface, 1).snapshot().unwrap();
+        assert_eq!(snapshot.global_parameter(2, 55), Some(-3));
+
+        let interface = RefCell::new(SimulatedModule::new(1, 1));
+        Tmcm1140Module::new(&interface, 1)
+            .restore(&snapshot, false)
+            .unwrap();
+        assert_eq!(interface.borrow().global_parameter(2, 55), Some(-3));
+    }
+
+    #[test]
+    fn nothing_written_on_error() {
+        let interface = RefCell::new(SimulatedModule::new(1, 1));
+        let module = Tmcm1140Module::new(&interface, 1);
+
+        // The module only has motor 0
+        let mut snapshot = Snapshot::new();
+        snapshot.set_axis_parameter(0, 4, 1500);
+        snapshot.set_axis_parameter(1, 4, 1500);
+        assert_eq!(
+            module.restore(&snapshot, false),
+            Err(Error::InvalidMotor(1))
+        );
+        assert_eq!(interface.borrow().axis_parameter(0, 4), Some(1000));
+
+        // The simulated module has no separate maximum deceleration
+        let mut snapshot = Snapshot::new();
+        snapshot.set_axis_parameter(0, 4, 1500);
+        snapshot.set_axis_parameter(0, 17, 1500);
+        assert_eq!(
+            module.restore(&snapshot, false),
+            Err(Error::ProtocolError(ErrStatus::WrongType))
+        );
+        assert_eq!(interface.borrow().axis_parameter(0, 4), Some(1000));
+
+        // Values already written are set back when a later one is invalid
+        let mut snapshot = Snapshot::new();
+        snapshot.set_axis_parameter(0, 4, 1500);
+        snapshot.set_axis_parameter(0, 140, 100);
+        assert_eq!(
+            module.restore(&snapshot, false),
+            Err(Error::ProtocolError(ErrStatus::InvalidValue))
+        );
+        assert_eq!(interface.borrow().axis_parameter(0, 4), Some(1000));
+    }
+}
@@ -10,6 +10,7 @@
 //! - LLSD - LeftLimitSwitchDisable (13)
 //! - MSR - MicrostepResolution (140)
 
+use metadata::{Access, Describe, ValueType};
 use AxisParameter;
 use ReadableAxisParameter;
 use Return;
@@ -121,10 +122,12 @@ axis_param_rw!(
     /// On all other modules the maximum value is 255 (which means 100% of the maximum current of the module).
     AbsoluteMaxCurrent,
     u16,
-    6
+    6,
+    0..=1500
 );
 impl AbsoluteMaxCurrent {
     pub fn new(current: u16) -> Self {
+        assert!(Self::is_valid(current));
         AbsoluteMaxCurrent(current)
     }
 }
@@ -145,10 +148,12 @@ axis_param_rw!(
     /// not moving. Please see also parameter 214 (PowerDownDelay).
     StandbyCurrent,
     u16,
-    7
+    7,
+    0..=1500
 );
 impl StandbyCurrent {
     pub fn new(current: u16) -> Self {
+        assert!(Self::is_valid(current));
         StandbyCurrent(current)
     }
 }
@@ -158,7 +163,7 @@ impl WriteableTmcmAxisParameter for StandbyCurrent {}
 
 axis_param_r!(
     /// Position reached flag
-    /// 
+    ///
     /// This flag is always set when target position and actual position are equal.
     PositionReachedFlag,
     bool,
@@ -169,7 +174,7 @@ impl ReadableTmcmAxisParameter for PositionReachedFlag {}
 
 axis_param_r!(
     /// Home switch state
-    /// 
+    ///
     /// The logical state of the home switch input.
     HomeSwitchState,
     bool,
@@ -180,7 +185,7 @@ impl ReadableTmcmAxisParameter for HomeSwitchState {}
 
 axis_param_r!(
     /// Right limit switch state
-    /// 
+    ///
     /// The logical state of the right limit switch input.
     RightLimitSwitchState,
     bool,
@@ -191,7 +196,7 @@ impl ReadableTmcmAxisParameter for RightLimitSwitchState {}
 
 axis_param_r!(
     /// Left limit switch state
-    /// 
+    ///
     /// The logical state of the left limit switch input.
     LeftLimitSwitchState,
     bool,
@@ -269,6 +274,7 @@ impl WriteableTmcmAxisParameter for MaximumDeceleration {}
 /// in steps of 64 respectively 32. To get real full stepping use axis parameter 211 or load an
 /// adapted microstepping table.
 #[derive(Debug, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
 pub enum MicrostepResolution {
     /// Fullstep
     Full = 0,
@@ -290,6 +296,7 @@ pub enum MicrostepResolution {
     Micro256 = 8,
 }
 impl MicrostepResolution {
+    #[allow(clippy::result_unit_err)]
     pub fn try_from_u8(v: u8) -> Result<Self, ()> {
         match v {
             0 => Ok(MicrostepResolution::Full),
@@ -304,7 +311,8 @@ impl MicrostepResolution {
             _ => Err(()),
         }
     }
-    
+
+    #[allow(clippy::result_unit_err)]
     pub fn try_from_scaled(v: u16) -> Result<Self, ()> {
         match v {
             1 => Ok(MicrostepResolution::Full),
@@ -347,11 +355,12 @@ axis_param_rw!(
     /// specifes the relation between internal and real world acceleration units.
     RampDivisor,
     u8,
-    153
+    153,
+    0..=13
 );
 impl RampDivisor {
     pub fn new(divisor: u8) -> Self {
-        assert!(divisor <= 13);
+        assert!(Self::is_valid(divisor));
         RampDivisor(divisor)
     }
 }
@@ -368,11 +377,12 @@ axis_param_rw!(
     /// specifes the relation between internal and real world velocity units.
     PulseDivisor,
     u8,
-    154
+    154,
+    0..=13
 );
 impl PulseDivisor {
     pub fn new(divisor: u8) -> Self {
-        assert!(divisor <= 13);
+        assert!(Self::is_valid(divisor));
         PulseDivisor(divisor)
     }
 }
@@ -380,15 +390,435 @@ impl TmcmAxisParameter for PulseDivisor {}
 impl ReadableTmcmAxisParameter for PulseDivisor {}
 impl WriteableTmcmAxisParameter for PulseDivisor {}
 
+/// Chopper blank time
+///
+/// Selects the comparator blank time. This time needs to safely cover the switching event and
+/// the duration of the ringing on the sense resistor. Low current drivers normally need a
+/// setting of 1 or 2.
+#[derive(Debug, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub enum ChopperBlankTime {
+    /// 16 system clock cycles
+    Clocks16 = 0,
+    /// 24 system clock cycles
+    Clocks24 = 1,
+    /// 36 system clock cycles
+    Clocks36 = 2,
+    /// 54 system clock cycles
+    Clocks54 = 3,
+}
+impl ChopperBlankTime {
+    #[allow(clippy::result_unit_err)]
+    pub fn try_from_u8(v: u8) -> Result<Self, ()> {
+        match v {
+            0 => Ok(ChopperBlankTime::Clocks16),
+            1 => Ok(ChopperBlankTime::Clocks24),
+            2 => Ok(ChopperBlankTime::Clocks36),
+            3 => Ok(ChopperBlankTime::Clocks54),
+            _ => Err(()),
+        }
+    }
+}
+impl AxisParameter for ChopperBlankTime {
+    const NUMBER: u8 = 162;
+}
+impl Return for ChopperBlankTime {
+    fn from_operand(array: [u8; 4]) -> Self {
+        // The field is 2 bits wide, ignore whatever else the module sends
+        match array[0] & 0x3 {
+            0 => ChopperBlankTime::Clocks16,
+            1 => ChopperBlankTime::Clocks24,
+            2 => ChopperBlankTime::Clocks36,
+            _ => ChopperBlankTime::Clocks54,
+        }
+    }
+}
+impl TmcmAxisParameter for ChopperBlankTime {}
+impl ReadableAxisParameter for ChopperBlankTime {}
+impl ReadableTmcmAxisParameter for ChopperBlankTime {}
+impl WriteableAxisParameter for ChopperBlankTime {
+    fn operand(&self) -> [u8; 4] {
+        [*self as u8, 0u8, 0u8, 0u8]
+    }
+}
+impl WriteableTmcmAxisParameter for ChopperBlankTime {}
+
+/// Chopper mode
+#[derive(Debug, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub enum ChopperMode {
+    /// spreadCycle, the hysteresis is set with parameters 164..166.
+    SpreadCycle = 0,
+    /// Classic constant off time chopper.
+    ConstantOffTime = 1,
+}
+impl ChopperMode {
+    #[allow(clippy::result_unit_err)]
+    pub fn try_from_u8(v: u8) -> Result<Self, ()> {
+        match v {
+            0 => Ok(ChopperMode::SpreadCycle),
+            1 => Ok(ChopperMode::ConstantOffTime),
+            _ => Err(()),
+        }
+    }
+}
+impl AxisParameter for ChopperMode {
+    const NUMBER: u8 = 163;
+}
+impl Return for ChopperMode {
+    fn from_operand(array: [u8; 4]) -> Self {
+        // The field is 1 bit wide, ignore whatever else the module sends
+        match array[0] & 0x1 {
+            0 => ChopperMode::SpreadCycle,
+            _ => ChopperMode::ConstantOffTime,
+        }
+    }
+}
+impl TmcmAxisParameter for ChopperMode {}
+impl ReadableAxisParameter for ChopperMode {}
+impl ReadableTmcmAxisParameter for ChopperMode {}
+impl WriteableAxisParameter for ChopperMode {
+    fn operand(&self) -> [u8; 4] {
+        [*self as u8, 0u8, 0u8, 0u8]
+    }
+}
+impl WriteableTmcmAxisParameter for ChopperMode {}
+
+/// Chopper hysteresis decrement
+///
+/// The hysteresis decrement period of the spreadCycle chopper, in system clock cycles. Together
+/// with the hysteresis start and end it sets how fast the hysteresis falls.
+#[derive(Debug, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub enum HysteresisDecrement {
+    /// Fast decrement, every 16 clocks
+    Clocks16 = 0,
+    /// Every 32 clocks
+    Clocks32 = 1,
+    /// Every 48 clocks
+    Clocks48 = 2,
+    /// Slow decrement, every 64 clocks
+    Clocks64 = 3,
+}
+impl HysteresisDecrement {
+    #[allow(clippy::result_unit_err)]
+    pub fn try_from_u8(v: u8) -> Result<Self, ()> {
+        match v {
+            0 => Ok(HysteresisDecrement::Clocks16),
+            1 => Ok(HysteresisDecrement::Clocks32),
+            2 => Ok(HysteresisDecrement::Clocks48),
+            3 => Ok(HysteresisDecrement::Clocks64),
+            _ => Err(()),
+        }
+    }
+}
+impl AxisParameter for HysteresisDecrement {
+    const NUMBER: u8 = 164;
+}
+impl Return for HysteresisDecrement {
+    fn from_operand(array: [u8; 4]) -> Self {
+        // The field is 2 bits wide, ignore whatever else the module sends
+        match array[0] & 0x3 {
+            0 => HysteresisDecrement::Clocks16,
+            1 => HysteresisDecrement::Clocks32,
+            2 => HysteresisDecrement::Clocks48,
+            _ => HysteresisDecrement::Clocks64,
+        }
+    }
+}
+impl TmcmAxisParameter for HysteresisDecrement {}
+impl ReadableAxisParameter for HysteresisDecrement {}
+impl ReadableTmcmAxisParameter for HysteresisDecrement {}
+impl WriteableAxisParameter for HysteresisDecrement {
+    fn operand(&self) -> [u8; 4] {
+        [*self as u8, 0u8, 0u8, 0u8]
+    }
+}
+impl WriteableTmcmAxisParameter for HysteresisDecrement {}
+
+axis_param_rw!(
+    /// Chopper hysteresis end
+    ///
+    /// The hysteresis end value after a number of decrements (-3..12). Negative values are
+    /// only useful for motors with high resistance.
+    HysteresisEnd,
+    i32,
+    165,
+    -3..=12
+);
+impl HysteresisEnd {
+    pub fn new(end: i32) -> Self {
+        assert!(Self::is_valid(end));
+        HysteresisEnd(end)
+    }
+}
+impl TmcmAxisParameter for HysteresisEnd {}
+impl ReadableTmcmAxisParameter for HysteresisEnd {}
+impl WriteableTmcmAxisParameter for HysteresisEnd {}
+
+axis_param_rw!(
+    /// Chopper hysteresis start
+    ///
+    /// The hysteresis start offset from the hysteresis end value (0..8).
+    HysteresisStart,
+    u8,
+    166,
+    0..=8
+);
+impl HysteresisStart {
+    pub fn new(start: u8) -> Self {
+        assert!(Self::is_valid(start));
+        HysteresisStart(start)
+    }
+}
+impl TmcmAxisParameter for HysteresisStart {}
+impl ReadableTmcmAxisParameter for HysteresisStart {}
+impl WriteableTmcmAxisParameter for HysteresisStart {}
+
+/// Chopper off time
+///
+/// The duration of the slow decay phase (2..15). 0 disables the driver.
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize))]
+pub struct ChopperOffTime(u8);
+param_is_valid!(ChopperOffTime, u8, |off_time| off_time != 1
+    && off_time <= 15);
+deserialize_checked!(ChopperOffTime, u8);
+describe!(
+    ChopperOffTime,
+    ReadWrite,
+    u8,
+    0,
+    15,
+    &[0, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15]
+);
+axis_param_define_read!(ChopperOffTime, u8);
+axis_param_define_write!(ChopperOffTime, u8);
+impl From<ChopperOffTime> for u8 {
+    fn from(v: ChopperOffTime) -> u8 {
+        v.0
+    }
+}
+impl AxisParameter for ChopperOffTime {
+    const NUMBER: u8 = 167;
+}
+impl ChopperOffTime {
+    pub fn new(off_time: u8) -> Self {
+        assert!(Self::is_valid(off_time));
+        ChopperOffTime(off_time)
+    }
+    pub fn disabled() -> Self {
+        ChopperOffTime(0)
+    }
+}
+impl TmcmAxisParameter for ChopperOffTime {}
+impl ReadableTmcmAxisParameter for ChopperOffTime {}
+impl WriteableTmcmAxisParameter for ChopperOffTime {}
+
+/// smartEnergy current minimum
+///
+/// The lower limit for the motor current reduction by coolStep, relative to the run current.
+#[derive(Debug, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub enum SmartEnergyCurrentMinimum {
+    /// 1/2 of the run current
+    Half = 0,
+    /// 1/4 of the run current
+    Quarter = 1,
+}
+impl SmartEnergyCurrentMinimum {
+    #[allow(clippy::result_unit_err)]
+    pub fn try_from_u8(v: u8) -> Result<Self, ()> {
+        match v {
+            0 => Ok(SmartEnergyCurrentMinimum::Half),
+            1 => Ok(SmartEnergyCurrentMinimum::Quarter),
+            _ => Err(()),
+        }
+    }
+}
+impl AxisParameter for SmartEnergyCurrentMinimum {
+    const NUMBER: u8 = 168;
+}
+impl Return for SmartEnergyCurrentMinimum {
+    fn from_operand(array: [u8; 4]) -> Self {
+        // The field is 1 bit wide, ignore whatever else the module sends
+        match array[0] & 0x1 {
+            0 => SmartEnergyCurrentMinimum::Half,
+            _ => SmartEnergyCurrentMinimum::Quarter,
+        }
+    }
+}
+impl TmcmAxisParameter for SmartEnergyCurrentMinimum {}
+impl ReadableAxisParameter for SmartEnergyCurrentMinimum {}
+impl ReadableTmcmAxisParameter for SmartEnergyCurrentMinimum {}
+impl WriteableAxisParameter for SmartEnergyCurrentMinimum {
+    fn operand(&self) -> [u8; 4] {
+        [*self as u8, 0u8, 0u8, 0u8]
+    }
+}
+impl WriteableTmcmAxisParameter for SmartEnergyCurrentMinimum {}
+
+/// smartEnergy current down step
+///
+/// The number of stallGuard2 measurements above the upper threshold before the current is
+/// reduced by one step.
+#[derive(Debug, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub enum CurrentDownStep {
+    /// One step for every 32 measurements, the slowest reduction
+    Every32 = 0,
+    /// One step for every 8 measurements
+    Every8 = 1,
+    /// One step for every 2 measurements
+    Every2 = 2,
+    /// One step for every measurement, the fastest reduction
+    Every1 = 3,
+}
+impl CurrentDownStep {
+    #[allow(clippy::result_unit_err)]
+    pub fn try_from_u8(v: u8) -> Result<Self, ()> {
+        match v {
+            0 => Ok(CurrentDownStep::Every32),
+            1 => Ok(CurrentDownStep::Every8),
+            2 => Ok(CurrentDownStep::Every2),
+            3 => Ok(CurrentDownStep::Every1),
+            _ => Err(()),
+        }
+    }
+}
+impl AxisParameter for CurrentDownStep {
+    const NUMBER: u8 = 169;
+}
+impl Return for CurrentDownStep {
+    fn from_operand(array: [u8; 4]) -> Self {
+        // The field is 2 bits wide, ignore whatever else the module sends
+        match array[0] & 0x3 {
+            0 => CurrentDownStep::Every32,
+            1 => CurrentDownStep::Every8,
+            2 => CurrentDownStep::Every2,
+            _ => CurrentDownStep::Every1,
+        }
+    }
+}
+impl TmcmAxisParameter for CurrentDownStep {}
+impl ReadableAxisParameter for CurrentDownStep {}
+impl ReadableTmcmAxisParameter for CurrentDownStep {}
+impl WriteableAxisParameter for CurrentDownStep {
+    fn operand(&self) -> [u8; 4] {
+        [*self as u8, 0u8, 0u8, 0u8]
+    }
+}
+impl WriteableTmcmAxisParameter for CurrentDownStep {}
+
+axis_param_rw!(
+    /// smartEnergy hysteresis
+    ///
+    /// Sets the distance between the lower and the upper coolStep threshold (0..15). The upper
+    /// threshold is (smartEnergy hysteresis start + smartEnergy hysteresis + 1) * 32.
+    SmartEnergyHysteresis,
+    u8,
+    170,
+    0..=15
+);
+impl SmartEnergyHysteresis {
+    pub fn new(hysteresis: u8) -> Self {
+        assert!(Self::is_valid(hysteresis));
+        SmartEnergyHysteresis(hysteresis)
+    }
+}
+impl TmcmAxisParameter for SmartEnergyHysteresis {}
+impl ReadableTmcmAxisParameter for SmartEnergyHysteresis {}
+impl WriteableTmcmAxisParameter for SmartEnergyHysteresis {}
+
+axis_param_rw!(
+    /// smartEnergy current up step
+    ///
+    /// The current increment when the stallGuard2 value falls below the lower threshold (1..3).
+    CurrentUpStep,
+    u8,
+    171,
+    1..=3
+);
+impl CurrentUpStep {
+    pub fn new(step: u8) -> Self {
+        assert!(Self::is_valid(step));
+        CurrentUpStep(step)
+    }
+}
+impl TmcmAxisParameter for CurrentUpStep {}
+impl ReadableTmcmAxisParameter for CurrentUpStep {}
+impl WriteableTmcmAxisParameter for CurrentUpStep {}
+
+axis_param_rw!(
+    /// smartEnergy hysteresis start
+    ///
+    /// The lower coolStep threshold (0..15), the stallGuard2 value below which the current is
+    /// increased is (smartEnergy hysteresis start * 32). 0 disables coolStep.
+    SmartEnergyHysteresisStart,
+    u8,
+    172,
+    0..=15
+);
+impl SmartEnergyHysteresisStart {
+    pub fn new(start: u8) -> Self {
+        assert!(Self::is_valid(start));
+        SmartEnergyHysteresisStart(start)
+    }
+}
+impl TmcmAxisParameter for SmartEnergyHysteresisStart {}
+impl ReadableTmcmAxisParameter for SmartEnergyHysteresisStart {}
+impl WriteableTmcmAxisParameter for SmartEnergyHysteresisStart {}
+
+axis_param_rw!(
+    /// stallGuard2 filter enable
+    ///
+    /// If set, the stallGuard2 value is only updated every four fullsteps, which gives a more
+    /// precise value at the cost of a slower response.
+    StallGuard2FilterEnable,
+    bool,
+    173
+);
+impl StallGuard2FilterEnable {
+    pub fn enabled() -> Self {
+        StallGuard2FilterEnable(true)
+    }
+    pub fn disabled() -> Self {
+        StallGuard2FilterEnable(false)
+    }
+}
+impl TmcmAxisParameter for StallGuard2FilterEnable {}
+impl ReadableTmcmAxisParameter for StallGuard2FilterEnable {}
+impl WriteableTmcmAxisParameter for StallGuard2FilterEnable {}
+
+axis_param_rw!(
+    /// stallGuard2 threshold
+    ///
+    /// The sensitivity of the stall detection (-64..63). Lower values are more sensitive, 0 is
+    /// a good starting point for most motors.
+    StallGuard2Threshold,
+    i32,
+    174,
+    -64..=63
+);
+impl StallGuard2Threshold {
+    pub fn new(threshold: i32) -> Self {
+        assert!(Self::is_valid(threshold));
+        StallGuard2Threshold(threshold)
+    }
+}
+impl TmcmAxisParameter for StallGuard2Threshold {}
+impl ReadableTmcmAxisParameter for StallGuard2Threshold {}
+impl WriteableTmcmAxisParameter for StallGuard2Threshold {}
+
 axis_param_r!(
     /// Vsense
     ///
     /// Sense resistor voltage based current scaling.
-    /// 
+    ///
     /// 0 - Full scale sense resistor voltage is 1/18 VDD
-    /// 
+    ///
     /// 1 - Full scale sense resistor voltage is 1/36 VDD
-    /// 
+    ///
     /// Leave at default value. Do not change!
     Vsense,
     bool,
@@ -397,8 +827,76 @@ axis_param_r!(
 impl TmcmAxisParameter for Vsense {}
 impl ReadableTmcmAxisParameter for Vsense {}
 
+axis_param_r!(
+    /// smartEnergy actual current
+    ///
+    /// The actual motor current as scaled by coolStep (0..31).
+    SmartEnergyActualCurrent,
+    u8,
+    180,
+    0..=31
+);
+impl TmcmAxisParameter for SmartEnergyActualCurrent {}
+impl ReadableTmcmAxisParameter for SmartEnergyActualCurrent {}
+
+axis_param_rw!(
+    /// Stop on stall
+    ///
+    /// The motor is stopped when a stall is detected above this speed. 0 disables stop on
+    /// stall.
+    StopOnStall,
+    u32,
+    181
+);
+impl StopOnStall {
+    pub fn above(speed: u32) -> Self {
+        StopOnStall(speed)
+    }
+    pub fn disabled() -> Self {
+        StopOnStall(0)
+    }
+}
+impl TmcmAxisParameter for StopOnStall {}
+impl ReadableTmcmAxisParameter for StopOnStall {}
+impl WriteableTmcmAxisParameter for StopOnStall {}
+
+axis_param_rw!(
+    /// smartEnergy threshold speed
+    ///
+    /// coolStep is only active above this speed. Below it the slow run current is used.
+    SmartEnergyThresholdSpeed,
+    u32,
+    182
+);
+impl SmartEnergyThresholdSpeed {
+    pub fn new(speed: u32) -> Self {
+        SmartEnergyThresholdSpeed(speed)
+    }
+}
+impl TmcmAxisParameter for SmartEnergyThresholdSpeed {}
+impl ReadableTmcmAxisParameter for SmartEnergyThresholdSpeed {}
+impl WriteableTmcmAxisParameter for SmartEnergyThresholdSpeed {}
+
+axis_param_rw!(
+    /// smartEnergy slow run current
+    ///
+    /// The current used below the smartEnergy threshold speed. Same scaling as the absolute
+    /// maximum current.
+    SlowRunCurrent,
+    u8,
+    183
+);
+impl SlowRunCurrent {
+    pub fn new(current: u8) -> Self {
+        SlowRunCurrent(current)
+    }
+}
+impl TmcmAxisParameter for SlowRunCurrent {}
+impl ReadableTmcmAxisParameter for SlowRunCurrent {}
+impl WriteableTmcmAxisParameter for SlowRunCurrent {}
+
 /// Reference search mode
-/// 
+///
 /// 1. Search left stop switch only.
 /// 2. Search right stop switch, then search left stop switch.
 /// 3. Search right stop switch, then search left stop switch from both sides.
@@ -409,13 +907,14 @@ impl ReadableTmcmAxisParameter for Vsense {}
 ///    right stop switch reached.
 /// 7. Search home switch in positive direction, ignore end switches.
 /// 8. Search home switch in negative direction, ignore end switches.
-/// 
+///
 /// Additional functions
 /// - Add 128 to a mode value for inverting the home switch (can be used
 ///   with mode 5...8).
 /// - Add 64 to a mode for searching the right instead of the left reference
 ///   switch (can be used with mode 1...4).
 #[derive(Debug, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
 pub enum ReferenceSearchMode {
     LimitSwitchSearch {
         search_mode: SearchMode,
@@ -428,6 +927,7 @@ pub enum ReferenceSearchMode {
 }
 
 #[derive(Debug, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
 pub enum SearchMode {
     /// Search left stop switch only.
     LeftSwitch = 1,
@@ -439,6 +939,7 @@ pub enum SearchMode {
     LeftFromBothSides = 4,
 }
 #[derive(Debug, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
 pub enum HomeSearchMode {
     /// Search home switch in negative direction, reverse the direction when
     /// left stop switch reached.
@@ -531,7 +1032,7 @@ axis_param_rw!(
     /// It should be slower than parameter 194.
     ReferenceSwitchSpeed,
     u32, // PD42-1240 supports a range of 0..7999744
-    195 
+    195
 );
 impl ReferenceSwitchSpeed {
     pub fn new(speed: u32) -> Self {
@@ -549,7 +1050,7 @@ axis_param_r!(
     /// executing the RFS command (with reference search mode 2 or 3).
     EndSwitchDistance,
     i32,
-    196 
+    196
 );
 impl TmcmAxisParameter for EndSwitchDistance {}
 impl ReadableTmcmAxisParameter for EndSwitchDistance {}
@@ -585,22 +1086,313 @@ impl TmcmAxisParameter for BoostCurrent {}
 impl ReadableTmcmAxisParameter for BoostCurrent {}
 impl WriteableTmcmAxisParameter for BoostCurrent {}
 
+/// Mixed decay threshold
+///
+/// If the actual velocity is above this threshold, mixed decay will be used.
+#[derive(Debug, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize))]
+pub struct MixedDecayThreshold(Option<u16>);
+param_is_valid!(MixedDecayThreshold, Option<u16>, |velocity| {
+    velocity.is_none_or(|v| v <= MixedDecayThreshold::MAX)
+});
+deserialize_checked!(MixedDecayThreshold, Option<u16>);
+impl Describe for MixedDecayThreshold {
+    const ACCESS: Access = Access::ReadWrite;
+    const VALUE_TYPE: ValueType = ValueType::Enum;
+    const MIN: i64 = -1;
+    const MAX: i64 = MixedDecayThreshold::MAX as i64;
+    const VALUES: &'static [i64] = &[];
+}
+impl MixedDecayThreshold {
+    /// The highest velocity threshold
+    pub const MAX: u16 = 2048;
+
+    /// Mixed decay is always on.
+    pub fn always() -> Self {
+        MixedDecayThreshold(None)
+    }
+    /// Mixed decay is used above `velocity` (0..2048).
+    pub fn above(velocity: u16) -> Self {
+        assert!(Self::is_valid(Some(velocity)));
+        MixedDecayThreshold(Some(velocity))
+    }
+    /// Returns the velocity threshold, or `None` if mixed decay is always on.
+    pub fn velocity(&self) -> Option<u16> {
+        self.0
+    }
+}
+impl AxisParameter for MixedDecayThreshold {
+    const NUMBER: u8 = 203;
+}
+impl Return for MixedDecayThreshold {
+    /// Negative values mean always on, values above 2048 are clamped to 2048.
+    fn from_operand(array: [u8; 4]) -> Self {
+        match <i32 as Return>::from_operand(array) {
+            v if v < 0 => MixedDecayThreshold::always(),
+            v => MixedDecayThreshold::above(v.min(i32::from(Self::MAX)) as u16),
+        }
+    }
+}
+impl TmcmAxisParameter for MixedDecayThreshold {}
+impl ReadableAxisParameter for MixedDecayThreshold {}
+impl ReadableTmcmAxisParameter for MixedDecayThreshold {}
+impl WriteableAxisParameter for MixedDecayThreshold {
+    fn operand(&self) -> [u8; 4] {
+        match self.0 {
+            None => param_operand!(-1i32, i32),
+            Some(v) => param_operand!(v, u16),
+        }
+    }
+}
+impl WriteableTmcmAxisParameter for MixedDecayThreshold {}
+
+axis_param_rw!(
+    /// Freewheeling delay
+    ///
+    /// Time after which the power to the motor will be cut when its velocity has reached zero.
+    /// 0 means never.
+    ///
+    /// Units are 10 ms
+    FreewheelingDelay,
+    u16,
+    204
+);
+impl FreewheelingDelay {
+    pub fn new(delay: u16) -> Self {
+        FreewheelingDelay(delay)
+    }
+    pub fn never() -> Self {
+        FreewheelingDelay(0)
+    }
+}
+impl TmcmAxisParameter for FreewheelingDelay {}
+impl ReadableTmcmAxisParameter for FreewheelingDelay {}
+impl WriteableTmcmAxisParameter for FreewheelingDelay {}
+
+axis_param_r!(
+    /// Actual load value
+    ///
+    /// The stallGuard2 load measurement (0..1023). Lower values mean a higher load, 0 means
+    /// the motor is stalled.
+    ActualLoadValue,
+    u16,
+    206,
+    0..=1023
+);
+impl TmcmAxisParameter for ActualLoadValue {}
+impl ReadableTmcmAxisParameter for ActualLoadValue {}
+
+/// Driver error flags
+///
+/// The status flags of the motor driver. Reading them clears the flags in the module.
+#[derive(Debug, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub struct DriverErrorFlags(u8);
+impl DriverErrorFlags {
+    /// Returns true if stallGuard2 detected a stall.
+    pub fn stall_guard(&self) -> bool {
+        self.0 & (1 << 0) != 0
+    }
+    /// Returns true if the driver has shut down due to overtemperature.
+    pub fn overtemperature(&self) -> bool {
+        self.0 & (1 << 1) != 0
+    }
+    /// Returns true if the overtemperature pre-warning threshold is exceeded.
+    pub fn overtemperature_warning(&self) -> bool {
+        self.0 & (1 << 2) != 0
+    }
+    /// Returns true if a short to ground was detected on coil A.
+    pub fn short_to_ground_a(&self) -> bool {
+        self.0 & (1 << 3) != 0
+    }
+    /// Returns true if a short to ground was detected on coil B.
+    pub fn short_to_ground_b(&self) -> bool {
+        self.0 & (1 << 4) != 0
+    }
+    /// Returns true if an open load was detected on coil A.
+    pub fn open_load_a(&self) -> bool {
+        self.0 & (1 << 5) != 0
+    }
+    /// Returns true if an open load was detected on coil B.
+    pub fn open_load_b(&self) -> bool {
+        self.0 & (1 << 6) != 0
+    }
+    /// Returns true if the motor is standing still.
+    pub fn standstill(&self) -> bool {
+        self.0 & (1 << 7) != 0
+    }
+}
+impl From<DriverErrorFlags> for u8 {
+    fn from(v: DriverErrorFlags) -> u8 {
+        v.0
+    }
+}
+impl AxisParameter for DriverErrorFlags {
+    const NUMBER: u8 = 208;
+}
+impl Return for DriverErrorFlags {
+    fn from_operand(array: [u8; 4]) -> Self {
+        DriverErrorFlags(array[0])
+    }
+}
+impl TmcmAxisParameter for DriverErrorFlags {}
+impl ReadableAxisParameter for DriverErrorFlags {}
+impl ReadableTmcmAxisParameter for DriverErrorFlags {}
+
+axis_param_rw!(
+    /// Encoder position
+    ///
+    /// The value of the encoder position counter, scaled by the encoder resolution.
+    EncoderPosition,
+    i32,
+    209
+);
+impl EncoderPosition {
+    pub fn new(position: i32) -> Self {
+        EncoderPosition(position)
+    }
+}
+impl TmcmAxisParameter for EncoderPosition {}
+impl ReadableTmcmAxisParameter for EncoderPosition {}
+impl WriteableTmcmAxisParameter for EncoderPosition {}
+
+axis_param_rw!(
+    /// Encoder resolution
+    ///
+    /// The number of encoder steps per motor rotation. The encoder position is scaled so that
+    /// it matches the microstep position of the motor.
+    EncoderResolution,
+    u32,
+    210
+);
+impl EncoderResolution {
+    pub fn new(steps: u32) -> Self {
+        EncoderResolution(steps)
+    }
+}
+impl TmcmAxisParameter for EncoderResolution {}
+impl ReadableTmcmAxisParameter for EncoderResolution {}
+impl WriteableTmcmAxisParameter for EncoderResolution {}
+
+axis_param_rw!(
+    /// Maximum encoder deviation
+    ///
+    /// When the actual position and the encoder position differ by more than this value the
+    /// motor is stopped and the deviation error flag is set. 0 disables the check.
+    MaximumEncoderDeviation,
+    u32,
+    212
+);
+impl MaximumEncoderDeviation {
+    pub fn new(deviation: u32) -> Self {
+        MaximumEncoderDeviation(deviation)
+    }
+    pub fn disabled() -> Self {
+        MaximumEncoderDeviation(0)
+    }
+}
+impl TmcmAxisParameter for MaximumEncoderDeviation {}
+impl ReadableTmcmAxisParameter for MaximumEncoderDeviation {}
+impl WriteableTmcmAxisParameter for MaximumEncoderDeviation {}
+
 axis_param_rw!(
     /// Power down delay
     ///
     /// Standstill period before the motor current will be switched to standby
     /// current. The default value is 200 which means 2000ms.
-    /// 
+    ///
     /// Units are 10 ms
     PowerDownDelay,
     u16,
-    214 
+    214,
+    1..=65535
 );
 impl PowerDownDelay {
     pub fn new(delay: u16) -> Self {
+        assert!(Self::is_valid(delay));
         PowerDownDelay(delay)
     }
 }
 impl TmcmAxisParameter for PowerDownDelay {}
 impl ReadableTmcmAxisParameter for PowerDownDelay {}
 impl WriteableTmcmAxisParameter for PowerDownDelay {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use modules::tmcm::instructions::{GAP, SAP};
+    use Command;
+
+    #[test]
+    fn sap_stall_guard_threshold() {
+        let command = Command::new(1, SAP::new(0, StallGuard2Threshold::new(-10)));
+        assert_eq!(command.serialize_can(), [5, 174, 0, 255, 255, 255, 0xf6]);
+        assert_eq!(
+            i32::from(StallGuard2Threshold::from_operand([0xf6, 0xff, 0xff, 0xff])),
+            -10
+        );
+    }
+
+    #[test]
+    fn chopper_settings() {
+        let command = Command::new(1, SAP::new(0, ChopperBlankTime::Clocks36));
+        assert_eq!(command.serialize_can(), [5, 162, 0, 0, 0, 0, 2]);
+        let command = Command::new(1, GAP::<HysteresisDecrement>::new(0));
+        assert_eq!(command.serialize_can(), [6, 164, 0, 0, 0, 0, 0]);
+        assert_eq!(
+            HysteresisDecrement::from_operand([3, 0, 0, 0]),
+            HysteresisDecrement::Clocks64
+        );
+        assert_eq!(
+            ChopperBlankTime::from_operand([0xfe, 0, 0, 0]),
+            ChopperBlankTime::Clocks36
+        );
+        assert_eq!(
+            ChopperMode::from_operand([0xff, 0xff, 0xff, 0xff]),
+            ChopperMode::ConstantOffTime
+        );
+        assert_eq!(
+            SmartEnergyCurrentMinimum::from_operand([2, 0, 0, 0]),
+            SmartEnergyCurrentMinimum::Half
+        );
+        assert_eq!(
+            CurrentDownStep::from_operand([7, 0, 0, 0]),
+            CurrentDownStep::Every1
+        );
+        assert_eq!(MixedDecayThreshold::always().operand(), [255; 4]);
+        assert_eq!(
+            MixedDecayThreshold::from_operand([0x00, 0x08, 0, 0]),
+            MixedDecayThreshold::above(2048)
+        );
+        assert_eq!(
+            MixedDecayThreshold::from_operand([0xb8, 0x0b, 0, 0]).velocity(),
+            Some(2048)
+        );
+        assert_eq!(
+            MixedDecayThreshold::from_operand([0xfe, 0xff, 0xff, 0xff]),
+            MixedDecayThreshold::always()
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn mixed_decay_threshold_range() {
+        MixedDecayThreshold::above(3000);
+    }
+
+    #[test]
+    fn driver_error_flags() {
+        let flags = DriverErrorFlags::from_operand([0b1010_0010, 0, 0, 0]);
+        assert!(flags.overtemperature());
+        assert!(flags.open_load_a());
+        assert!(flags.standstill());
+        assert!(!flags.stall_guard());
+        assert!(!flags.short_to_ground_b());
+    }
+
+    #[test]
+    #[should_panic]
+    fn chopper_off_time_out_of_range() {
+        ChopperOffTime::new(1);
+    }
+}
@@ -0,0 +1,472 @@
+//! All global parameters useable with TMCM modules other than TMCM-100 and Monopack 2.
+//!
+//! The parameters in bank 0 configure the host interfaces of the module. They are
+//! stored in EEPROM automatically when written with SGP, and most of them only take
+//! effect after the module has been reset.
+//!
+//! Bank 2 holds the user variables, general purpose variables for exchanging values with the
+//! TMCL program. They are lost at power down unless stored with STGP.
+//!
+//! The parameters in bank 3 configure the interrupts of the TMCL program.
+
+use metadata::{Access, Describe, ValueType};
+use GlobalParameter;
+use ReadableGlobalParameter;
+use Return;
+use WriteableGlobalParameter;
+
+use modules::tmcm::{
+    ReadableTmcmGlobalParameter, TmcmGlobalParameter, WriteableTmcmGlobalParameter,
+};
+
+/// Serial baud rate
+///
+/// The baud rate used on the RS232/RS485 interface.
+/// Not all rates are supported by all modules.
+#[derive(Debug, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub enum SerialBaudRate {
+    /// 9600 baud
+    Baud9600 = 0,
+    /// 14400 baud
+    Baud14400 = 1,
+    /// 19200 baud
+    Baud19200 = 2,
+    /// 28800 baud
+    Baud28800 = 3,
+    /// 38400 baud
+    Baud38400 = 4,
+    /// 57600 baud
+    Baud57600 = 5,
+    /// 76800 baud
+    Baud76800 = 6,
+    /// 115200 baud
+    Baud115200 = 7,
+    /// 230400 baud
+    Baud230400 = 8,
+    /// 250000 baud
+    Baud250000 = 9,
+    /// 500000 baud
+    Baud500000 = 10,
+    /// 1000000 baud
+    Baud1000000 = 11,
+}
+impl SerialBaudRate {
+    #[allow(clippy::result_unit_err)]
+    pub fn try_from_u8(v: u8) -> Result<Self, ()> {
+        match v {
+            0 => Ok(SerialBaudRate::Baud9600),
+            1 => Ok(SerialBaudRate::Baud14400),
+            2 => Ok(SerialBaudRate::Baud19200),
+            3 => Ok(SerialBaudRate::Baud28800),
+            4 => Ok(SerialBaudRate::Baud38400),
+            5 => Ok(SerialBaudRate::Baud57600),
+            6 => Ok(SerialBaudRate::Baud76800),
+            7 => Ok(SerialBaudRate::Baud115200),
+            8 => Ok(SerialBaudRate::Baud230400),
+            9 => Ok(SerialBaudRate::Baud250000),
+            10 => Ok(SerialBaudRate::Baud500000),
+            11 => Ok(SerialBaudRate::Baud1000000),
+            _ => Err(()),
+        }
+    }
+
+    /// The baud rate in bits per second.
+    pub fn bits_per_second(self) -> u32 {
+        match self {
+            SerialBaudRate::Baud9600 => 9600,
+            SerialBaudRate::Baud14400 => 14400,
+            SerialBaudRate::Baud19200 => 19200,
+            SerialBaudRate::Baud28800 => 28800,
+            SerialBaudRate::Baud38400 => 38400,
+            SerialBaudRate::Baud57600 => 57600,
+            SerialBaudRate::Baud76800 => 76800,
+            SerialBaudRate::Baud115200 => 115200,
+            SerialBaudRate::Baud230400 => 230400,
+            SerialBaudRate::Baud250000 => 250000,
+            SerialBaudRate::Baud500000 => 500000,
+            SerialBaudRate::Baud1000000 => 1000000,
+        }
+    }
+}
+impl GlobalParameter for SerialBaudRate {
+    const BANK: u8 = 0;
+    const NUMBER: u8 = 65;
+}
+impl Return for SerialBaudRate {
+    fn from_operand(array: [u8; 4]) -> Self {
+        SerialBaudRate::try_from_u8(array[0]).unwrap()
+    }
+}
+impl TmcmGlobalParameter for SerialBaudRate {}
+impl ReadableGlobalParameter for SerialBaudRate {}
+impl ReadableTmcmGlobalParameter for SerialBaudRate {}
+impl WriteableGlobalParameter for SerialBaudRate {
+    fn operand(&self) -> [u8; 4] {
+        [*self as u8, 0u8, 0u8, 0u8]
+    }
+}
+impl WriteableTmcmGlobalParameter for SerialBaudRate {}
+
+global_param_rw!(
+    /// Serial address
+    ///
+    /// The module (target) address for RS232 and RS485.
+    SerialAddress,
+    u8,
+    0,
+    66,
+    1..=255
+);
+impl SerialAddress {
+    pub fn new(address: u8) -> Self {
+        assert!(Self::is_valid(address));
+        SerialAddress(address)
+    }
+}
+impl TmcmGlobalParameter for SerialAddress {}
+impl ReadableTmcmGlobalParameter for SerialAddress {}
+impl WriteableTmcmGlobalParameter for SerialAddress {}
+
+/// CAN bit rate
+///
+/// The bit rate used on the CAN interface.
+#[derive(Debug, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub enum CANBitRate {
+    /// 20 kbit/s
+    Kbps20 = 2,
+    /// 50 kbit/s
+    Kbps50 = 3,
+    /// 100 kbit/s
+    Kbps100 = 4,
+    /// 125 kbit/s
+    Kbps125 = 5,
+    /// 250 kbit/s
+    Kbps250 = 6,
+    /// 500 kbit/s
+    Kbps500 = 7,
+    /// 1000 kbit/s
+    Kbps1000 = 8,
+}
+impl CANBitRate {
+    #[allow(clippy::result_unit_err)]
+    pub fn try_from_u8(v: u8) -> Result<Self, ()> {
+        match v {
+            2 => Ok(CANBitRate::Kbps20),
+            3 => Ok(CANBitRate::Kbps50),
+            4 => Ok(CANBitRate::Kbps100),
+            5 => Ok(CANBitRate::Kbps125),
+            6 => Ok(CANBitRate::Kbps250),
+            7 => Ok(CANBitRate::Kbps500),
+            8 => Ok(CANBitRate::Kbps1000),
+            _ => Err(()),
+        }
+    }
+}
+impl GlobalParameter for CANBitRate {
+    const BANK: u8 = 0;
+    const NUMBER: u8 = 69;
+}
+impl Return for CANBitRate {
+    fn from_operand(array: [u8; 4]) -> Self {
+        CANBitRate::try_from_u8(array[0]).unwrap()
+    }
+}
+impl TmcmGlobalParameter for CANBitRate {}
+impl ReadableGlobalParameter for CANBitRate {}
+impl ReadableTmcmGlobalParameter for CANBitRate {}
+impl WriteableGlobalParameter for CANBitRate {
+    fn operand(&self) -> [u8; 4] {
+        [*self as u8, 0u8, 0u8, 0u8]
+    }
+}
+impl WriteableTmcmGlobalParameter for CANBitRate {}
+
+global_param_rw!(
+    /// CAN reply ID
+    ///
+    /// The CAN ID used by the module when replying to the host (0..7ff).
+    CANReplyId,
+    u32,
+    0,
+    70,
+    0..=0x7ff
+);
+impl CANReplyId {
+    pub fn new(id: u32) -> Self {
+        assert!(Self::is_valid(id));
+        CANReplyId(id)
+    }
+}
+impl TmcmGlobalParameter for CANReplyId {}
+impl ReadableTmcmGlobalParameter for CANReplyId {}
+impl WriteableTmcmGlobalParameter for CANReplyId {}
+
+global_param_rw!(
+    /// CAN ID
+    ///
+    /// The CAN ID the module listens to for commands from the host (0..7ff).
+    CANId,
+    u32,
+    0,
+    71,
+    0..=0x7ff
+);
+impl CANId {
+    pub fn new(id: u32) -> Self {
+        assert!(Self::is_valid(id));
+        CANId(id)
+    }
+}
+impl TmcmGlobalParameter for CANId {}
+impl ReadableTmcmGlobalParameter for CANId {}
+impl WriteableTmcmGlobalParameter for CANId {}
+
+/// Configuration EEPROM lock flag
+///
+/// While the configuration EEPROM is locked, attempts to store parameters are answered
+/// with `ErrStatus::EEPROMLocked`.
+#[derive(Debug, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub struct EEPROMLock(bool);
+impl EEPROMLock {
+    /// Magic value that locks the EEPROM when written.
+    const LOCK: u16 = 1234;
+
+    /// Magic value that unlocks the EEPROM when written.
+    const UNLOCK: u16 = 4321;
+
+    pub fn locked() -> Self {
+        EEPROMLock(true)
+    }
+    pub fn unlocked() -> Self {
+        EEPROMLock(false)
+    }
+
+    /// Returns true if the configuration EEPROM is locked.
+    pub fn is_locked(&self) -> bool {
+        self.0
+    }
+}
+impl From<EEPROMLock> for bool {
+    fn from(v: EEPROMLock) -> bool {
+        v.0
+    }
+}
+impl GlobalParameter for EEPROMLock {
+    const BANK: u8 = 0;
+    const NUMBER: u8 = 73;
+}
+impl Return for EEPROMLock {
+    fn from_operand(array: [u8; 4]) -> Self {
+        EEPROMLock(<bool as Return>::from_operand(array))
+    }
+}
+impl TmcmGlobalParameter for EEPROMLock {}
+impl ReadableGlobalParameter for EEPROMLock {}
+impl ReadableTmcmGlobalParameter for EEPROMLock {}
+impl WriteableGlobalParameter for EEPROMLock {
+    fn operand(&self) -> [u8; 4] {
+        let magic = if self.0 {
+            EEPROMLock::LOCK
+        } else {
+            EEPROMLock::UNLOCK
+        };
+        param_operand!(magic, u16)
+    }
+}
+impl WriteableTmcmGlobalParameter for EEPROMLock {}
+impl Describe for EEPROMLock {
+    const ACCESS: Access = Access::ReadWrite;
+    const VALUE_TYPE: ValueType = ValueType::Enum;
+    const MIN: i64 = EEPROMLock::LOCK as i64;
+    const MAX: i64 = EEPROMLock::UNLOCK as i64;
+    const VALUES: &'static [i64] = &[EEPROMLock::LOCK as i64, EEPROMLock::UNLOCK as i64];
+}
+
+user_variables!(
+    /// User variable
+    ///
+    /// A general purpose variable, `N` is the variable number (0..55). Use `STGP` to store it in
+    /// EEPROM, so it is restored at power up.
+    ///
+    /// ```compile_fail
+    /// use tmcl::modules::tmcm::instructions::GGP;
+    /// use tmcl::modules::tmcm::global_parameters::UserVariable;
+    /// use tmcl::Instruction;
+    ///
+    /// GGP::<UserVariable<56>>::new().type_number();
+    /// ```
+    UserVariable,
+    55
+);
+impl<const N: u8> TmcmGlobalParameter for UserVariable<N> {}
+impl<const N: u8> ReadableTmcmGlobalParameter for UserVariable<N> {}
+impl<const N: u8> WriteableTmcmGlobalParameter for UserVariable<N> {}
+
+macro_rules! timer_period {
+    ($(#[$doc:meta])* $name:ident, $number:expr) => {
+        global_param_rw!(
+            $(#[$doc])*
+            $name,
+            u32,
+            3,
+            $number
+        );
+        impl $name {
+            pub fn new(milliseconds: u32) -> Self {
+                $name(milliseconds)
+            }
+        }
+        impl TmcmGlobalParameter for $name {}
+        impl ReadableTmcmGlobalParameter for $name {}
+        impl WriteableTmcmGlobalParameter for $name {}
+    };
+}
+
+timer_period!(
+    /// Timer 0 period
+    ///
+    /// The period of the timer 0 interrupt in milliseconds.
+    Timer0Period,
+    0
+);
+timer_period!(
+    /// Timer 1 period
+    ///
+    /// The period of the timer 1 interrupt in milliseconds.
+    Timer1Period,
+    1
+);
+timer_period!(
+    /// Timer 2 period
+    ///
+    /// The period of the timer 2 interrupt in milliseconds.
+    Timer2Period,
+    2
+);
+
+/// The edge of an input that triggers an input change interrupt
+#[derive(Debug, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub enum InterruptEdge {
+    /// The interrupt is off
+    Off = 0,
+    /// Low to high transition
+    Rising = 1,
+    /// High to low transition
+    Falling = 2,
+    /// Both transitions
+    Both = 3,
+}
+impl InterruptEdge {
+    #[allow(clippy::result_unit_err)]
+    pub fn try_from_u8(v: u8) -> Result<Self, ()> {
+        match v {
+            0 => Ok(InterruptEdge::Off),
+            1 => Ok(InterruptEdge::Rising),
+            2 => Ok(InterruptEdge::Falling),
+            3 => Ok(InterruptEdge::Both),
+            _ => Err(()),
+        }
+    }
+}
+
+macro_rules! input_edge {
+    ($(#[$doc:meta])* $name:ident, $number:expr) => {
+        $(#[$doc])*
+        #[derive(Debug, PartialEq, Clone, Copy)]
+        #[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+        pub struct $name(InterruptEdge);
+        impl $name {
+            pub fn new(edge: InterruptEdge) -> Self {
+                $name(edge)
+            }
+        }
+        impl From<$name> for InterruptEdge {
+            fn from(v: $name) -> InterruptEdge {
+                v.0
+            }
+        }
+        impl GlobalParameter for $name {
+            const BANK: u8 = 3;
+            const NUMBER: u8 = $number;
+        }
+        impl Return for $name {
+            fn from_operand(array: [u8; 4]) -> Self {
+                $name(InterruptEdge::try_from_u8(array[0]).unwrap())
+            }
+        }
+        impl TmcmGlobalParameter for $name {}
+        impl ReadableGlobalParameter for $name {}
+        impl ReadableTmcmGlobalParameter for $name {}
+        impl WriteableGlobalParameter for $name {
+            fn operand(&self) -> [u8; 4] {
+                [self.0 as u8, 0u8, 0u8, 0u8]
+            }
+        }
+        impl WriteableTmcmGlobalParameter for $name {}
+    };
+}
+
+input_edge!(
+    /// Input 0 interrupt edge
+    ///
+    /// The edge of input 0 that triggers `InterruptVector::InputChange0`.
+    InputChange0Edge,
+    39
+);
+input_edge!(
+    /// Input 1 interrupt edge
+    ///
+    /// The edge of input 1 that triggers `InterruptVector::InputChange1`.
+    InputChange1Edge,
+    40
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use modules::tmcm::instructions::{GGP, SGP, STGP};
+    use Command;
+
+    #[test]
+    fn sgp_serial_address() {
+        let command = Command::new(1, SGP::new(SerialAddress::new(3)));
+        assert_eq!(command.serialize_can(), [9, 66, 0, 0, 0, 0, 3]);
+    }
+
+    #[test]
+    fn ggp_can_id() {
+        let command = Command::new(1, GGP::<CANId>::new());
+        assert_eq!(command.serialize_can(), [10, 71, 0, 0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn eeprom_lock_magic() {
+        assert_eq!(EEPROMLock::locked().operand(), [0xd2, 0x04, 0, 0]);
+        assert_eq!(EEPROMLock::unlocked().operand(), [0xe1, 0x10, 0, 0]);
+        assert!(EEPROMLock::from_operand([1, 0, 0, 0]).is_locked());
+    }
+
+    #[test]
+    fn interrupt_configuration() {
+        let command = Command::new(1, SGP::new(Timer1Period::new(1000)));
+        assert_eq!(command.serialize_can(), [9, 1, 3, 0, 0, 0x03, 0xe8]);
+        let command = Command::new(1, SGP::new(InputChange1Edge::new(InterruptEdge::Both)));
+        assert_eq!(command.serialize_can(), [9, 40, 3, 0, 0, 0, 3]);
+    }
+
+    #[test]
+    fn user_variables() {
+        let command = Command::new(1, SGP::new(UserVariable::<55>::new(-2)));
+        assert_eq!(command.serialize_can(), [9, 55, 2, 255, 255, 255, 254]);
+        let command = Command::new(1, STGP::<UserVariable<3>>::new());
+        assert_eq!(command.serialize_can(), [11, 3, 2, 0, 0, 0, 0]);
+        assert_eq!(
+            i32::from(UserVariable::<0>::from_operand([0x10, 0x27, 0, 0])),
+            10000
+        );
+    }
+}
@@ -1,13 +1,19 @@
 //! All instructions available for TMCM modules other than TMCM-100 and Monopack 2.
 
 pub use instructions::{
-    MoveOperation, ReferenceSearchAction, CALC, GAP, GIO, MST, MVP, RFS, ROL, ROR, RSAP, SAP, SIO,
-    STAP,
+    ApplicationStatus, EnterDownloadMode, ErrorFlag, ExitDownloadMode, FirmwareVersion,
+    GetApplicationStatus, GetFirmwareVersion, GetFirmwareVersionAscii, InterruptVector,
+    JumpCondition, MoveOperation, ProgramMemoryPart, ReadProgramMemory, ReferenceSearchAction,
+    ResetApplication, RestoreFactoryDefaults, RunApplication, StepApplication, StopApplication,
+    WaitEvent, AAP, ACO, AGP, CALC, CALCX, CCO, CLE, COMP, CSUB, DI, EI, GAP, GCO, GGP, GIO, JA,
+    JC, MST, MVP, RETI, RFS, ROL, ROR, RSAP, RSGP, RSUB, SAP, SCO, SGP, SIO, STAP, STGP, STOP,
+    VECT, WAIT,
 };
 
 use modules::tmcm::TmcmInstruction;
 
 use modules::tmcm::{ReadableTmcmAxisParameter, WriteableTmcmAxisParameter};
+use modules::tmcm::{ReadableTmcmGlobalParameter, WriteableTmcmGlobalParameter};
 
 impl TmcmInstruction for ROR {}
 impl TmcmInstruction for ROL {}
@@ -17,7 +23,41 @@ impl<T: WriteableTmcmAxisParameter> TmcmInstruction for SAP<T> {}
 impl<T: ReadableTmcmAxisParameter> TmcmInstruction for GAP<T> {}
 impl<T: WriteableTmcmAxisParameter> TmcmInstruction for STAP<T> {}
 impl<T: WriteableTmcmAxisParameter> TmcmInstruction for RSAP<T> {}
+impl<T: WriteableTmcmGlobalParameter> TmcmInstruction for SGP<T> {}
+impl<T: ReadableTmcmGlobalParameter> TmcmInstruction for GGP<T> {}
+impl<T: WriteableTmcmGlobalParameter> TmcmInstruction for STGP<T> {}
+impl<T: WriteableTmcmGlobalParameter> TmcmInstruction for RSGP<T> {}
 impl TmcmInstruction for RFS {}
 impl TmcmInstruction for SIO {}
 impl TmcmInstruction for GIO {}
 impl TmcmInstruction for CALC {}
+impl TmcmInstruction for COMP {}
+impl TmcmInstruction for JC {}
+impl TmcmInstruction for JA {}
+impl TmcmInstruction for CSUB {}
+impl TmcmInstruction for RSUB {}
+impl TmcmInstruction for EI {}
+impl TmcmInstruction for DI {}
+impl TmcmInstruction for WAIT {}
+impl TmcmInstruction for STOP {}
+impl TmcmInstruction for SCO {}
+impl TmcmInstruction for GCO {}
+impl TmcmInstruction for CCO {}
+impl TmcmInstruction for CALCX {}
+impl<T: WriteableTmcmAxisParameter> TmcmInstruction for AAP<T> {}
+impl<T: WriteableTmcmGlobalParameter> TmcmInstruction for AGP<T> {}
+impl TmcmInstruction for CLE {}
+impl TmcmInstruction for VECT {}
+impl TmcmInstruction for RETI {}
+impl TmcmInstruction for ACO {}
+impl TmcmInstruction for StopApplication {}
+impl TmcmInstruction for RunApplication {}
+impl TmcmInstruction for StepApplication {}
+impl TmcmInstruction for ResetApplication {}
+impl TmcmInstruction for GetApplicationStatus {}
+impl TmcmInstruction for GetFirmwareVersion {}
+impl TmcmInstruction for GetFirmwareVersionAscii {}
+impl TmcmInstruction for RestoreFactoryDefaults {}
+impl TmcmInstruction for EnterDownloadMode {}
+impl TmcmInstruction for ExitDownloadMode {}
+impl TmcmInstruction for ReadProgramMemory {}
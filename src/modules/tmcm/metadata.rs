@@ -0,0 +1,215 @@
+//! Descriptions of the parameters in `modules::tmcm`.
+//!
+//! The ranges are the widest valid on any TMCM module, the per-product modules check the exact
+//! ranges of their product.
+
+use metadata::{Access, Describe, ParameterInfo, ParameterTable, ValueType};
+use modules::tmcm::axis_parameters::*;
+use modules::tmcm::global_parameters::*;
+use AxisParameter;
+use GlobalParameter;
+
+/// Describes a typed parameter, named after its type.
+///
+/// The access, value type and range of parameters defined with the `axis_param` and
+/// `global_param` macros are taken from their definitions, only the unit is given here.
+macro_rules! info {
+    (axis $name:ident, $access:ident, $ty:ident, $unit:expr, $min:expr, $max:expr) => {
+        ParameterInfo {
+            bank: None,
+            number: <$name as AxisParameter>::NUMBER,
+            name: stringify!($name),
+            access: Access::$access,
+            value_type: ValueType::$ty,
+            unit: $unit,
+            min: $min as i64,
+            max: $max as i64,
+            values: &[],
+        }
+    };
+    (global $name:ident, $access:ident, $ty:ident, $unit:expr, $min:expr, $max:expr) => {
+        ParameterInfo {
+            bank: Some(<$name as GlobalParameter>::BANK),
+            number: <$name as GlobalParameter>::NUMBER,
+            name: stringify!($name),
+            access: Access::$access,
+            value_type: ValueType::$ty,
+            unit: $unit,
+            min: $min as i64,
+            max: $max as i64,
+            values: &[],
+        }
+    };
+    (axis $name:ident, $unit:expr) => {
+        ParameterInfo {
+            bank: None,
+            number: <$name as AxisParameter>::NUMBER,
+            ..info!(@describe $name, $unit)
+        }
+    };
+    (global $name:ident, $unit:expr) => {
+        ParameterInfo {
+            bank: Some(<$name as GlobalParameter>::BANK),
+            number: <$name as GlobalParameter>::NUMBER,
+            ..info!(@describe $name, $unit)
+        }
+    };
+    (axis $name:ident) => {
+        info!(axis $name, None)
+    };
+    (global $name:ident) => {
+        info!(global $name, None)
+    };
+    (@describe $name:ident, $unit:expr) => {
+        ParameterInfo {
+            bank: None,
+            number: 0,
+            name: stringify!($name),
+            access: <$name as Describe>::ACCESS,
+            value_type: <$name as Describe>::VALUE_TYPE,
+            unit: $unit,
+            min: <$name as Describe>::MIN,
+            max: <$name as Describe>::MAX,
+            values: <$name as Describe>::VALUES,
+        }
+    };
+}
+
+const MICROSTEPS: Option<&str> = Some("microsteps");
+
+/// The axis and global parameters in `modules::tmcm`.
+pub const PARAMETERS: ParameterTable = ParameterTable {
+    axis: &[
+        info!(axis TargetPosition, MICROSTEPS),
+        info!(axis ActualPosition, MICROSTEPS),
+        info!(axis TargetSpeed),
+        info!(axis ActualSpeed),
+        info!(axis MaximumPositioningSpeed),
+        info!(axis MaximumAcceleration),
+        info!(axis AbsoluteMaxCurrent),
+        info!(axis StandbyCurrent),
+        info!(axis PositionReachedFlag),
+        info!(axis HomeSwitchState),
+        info!(axis RightLimitSwitchState),
+        info!(axis LeftLimitSwitchState),
+        info!(axis RightLimitSwitchDisable),
+        info!(axis LeftLimitSwitchDisable),
+        info!(axis MaximumDeceleration),
+        info!(axis MicrostepResolution, ReadWrite, Enum, None, 0, 8),
+        info!(axis RampDivisor),
+        info!(axis PulseDivisor),
+        info!(axis ChopperBlankTime, ReadWrite, Enum, None, 0, 3),
+        info!(axis ChopperMode, ReadWrite, Enum, None, 0, 1),
+        info!(axis HysteresisDecrement, ReadWrite, Enum, None, 0, 3),
+        info!(axis HysteresisEnd),
+        info!(axis HysteresisStart),
+        info!(axis ChopperOffTime),
+        info!(axis SmartEnergyCurrentMinimum, ReadWrite, Enum, None, 0, 1),
+        info!(axis CurrentDownStep, ReadWrite, Enum, None, 0, 3),
+        info!(axis SmartEnergyHysteresis),
+        info!(axis CurrentUpStep),
+        info!(axis SmartEnergyHysteresisStart),
+        info!(axis StallGuard2FilterEnable),
+        info!(axis StallGuard2Threshold),
+        info!(axis Vsense),
+        info!(axis SmartEnergyActualCurrent),
+        info!(axis StopOnStall),
+        info!(axis SmartEnergyThresholdSpeed),
+        info!(axis SlowRunCurrent),
+        info!(axis ReferenceSearchMode, ReadWrite, Enum, None, 1, 255),
+        info!(axis ReferenceSearchSpeed),
+        info!(axis ReferenceSwitchSpeed),
+        info!(axis EndSwitchDistance, MICROSTEPS),
+        info!(axis LastReferencePosition, MICROSTEPS),
+        info!(axis BoostCurrent),
+        info!(axis MixedDecayThreshold),
+        info!(axis FreewheelingDelay, Some("10 ms")),
+        info!(axis ActualLoadValue),
+        info!(axis DriverErrorFlags, Read, Enum, None, 0, 255),
+        info!(axis EncoderPosition),
+        info!(axis EncoderResolution),
+        info!(axis MaximumEncoderDeviation),
+        info!(axis PowerDownDelay, Some("10 ms")),
+    ],
+    global: &[
+        info!(global SerialBaudRate, ReadWrite, Enum, None, 0, 11),
+        info!(global SerialAddress),
+        info!(global CANBitRate, ReadWrite, Enum, None, 2, 8),
+        info!(global CANReplyId),
+        info!(global CANId),
+        info!(global EEPROMLock),
+        info!(global Timer0Period, Some("ms")),
+        info!(global Timer1Period, Some("ms")),
+        info!(global Timer2Period, Some("ms")),
+        info!(global InputChange0Edge, ReadWrite, Enum, None, 0, 3),
+        info!(global InputChange1Edge, ReadWrite, Enum, None, 0, 3),
+    ],
+};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unique_parameters() {
+        for (i, parameter) in PARAMETERS.axis.iter().enumerate() {
+            assert_eq!(PARAMETERS.axis_parameter(parameter.number), Some(parameter));
+            assert_eq!(
+                PARAMETERS.axis_parameter_by_name(parameter.name),
+                Some(parameter)
+            );
+            assert!(PARAMETERS.axis[..i]
+                .iter()
+                .all(|p| p.number != parameter.number));
+        }
+        for parameter in PARAMETERS.global {
+            let bank = parameter.bank.unwrap();
+            assert_eq!(
+                PARAMETERS.global_parameter(bank, parameter.number),
+                Some(parameter)
+            );
+            assert_eq!(
+                PARAMETERS.global_parameter_by_name(parameter.name),
+                Some(parameter)
+            );
+        }
+        assert_eq!(PARAMETERS.axis_parameter_by_name("Unknown"), None);
+    }
+
+    #[test]
+    fn values() {
+        let position = PARAMETERS.axis_parameter(0).unwrap();
+        assert_eq!(position.value([0xff, 0xff, 0xff, 0xff]), -1);
+        let speed = PARAMETERS.axis_parameter(4).unwrap();
+        assert_eq!(speed.value([0xff, 0xff, 0xff, 0xff]), i64::from(u32::MAX));
+        let threshold = PARAMETERS.axis_parameter(174).unwrap();
+        assert!(threshold.is_valid(-64));
+        assert!(!threshold.is_valid(64));
+    }
+
+    #[test]
+    fn generated_from_types() {
+        let divisor = PARAMETERS.axis_parameter_by_name("RampDivisor").unwrap();
+        for value in 0..=255 {
+            assert_eq!(
+                divisor.is_valid(i64::from(value)),
+                RampDivisor::is_valid(value)
+            );
+        }
+        let off_time = PARAMETERS.axis_parameter_by_name("ChopperOffTime").unwrap();
+        for value in 0..=255 {
+            assert_eq!(
+                off_time.is_valid(i64::from(value)),
+                ChopperOffTime::is_valid(value)
+            );
+        }
+        let current = PARAMETERS.axis_parameter(6).unwrap();
+        assert_eq!((current.min, current.max), (0, 1500));
+
+        let lock = PARAMETERS.global_parameter(0, 73).unwrap();
+        assert!(lock.is_valid(1234));
+        assert!(lock.is_valid(4321));
+        assert!(!lock.is_valid(5));
+        assert!(!lock.is_valid(2000));
+    }
+}
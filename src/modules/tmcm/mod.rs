@@ -2,51 +2,70 @@
 
 use lib::marker::PhantomData;
 use lib::ops::Deref;
+use lib::sync::atomic::{AtomicU8, Ordering};
 
 pub mod axis_parameters;
+pub mod global_parameters;
 pub mod instructions;
+pub mod metadata;
 
 use interior_mut::InteriorMut;
 
+#[cfg(feature = "async")]
+use AsyncInterface;
+#[cfg(feature = "async")]
+use WriteCommand;
+
 use instructions::DirectInstruction;
+use modules::readdress;
+#[cfg(feature = "std")]
+use modules::snapshot;
+use modules::AddressChange;
+use program;
+use program::ProgramWord;
 use AxisParameter;
 use Command;
 use Error;
+use GlobalParameter;
 use Instruction;
 use Interface;
 use ReadableAxisParameter;
+use ReadableGlobalParameter;
 use Return;
 use Status;
 use WriteableAxisParameter;
+use WriteableGlobalParameter;
 
 /// This type represennts a TMCM module other than TMCM-100 and Monopack 2.
 #[derive(Debug)]
-pub struct TmcmModule<
-    'a,
-    IF: Interface + 'a,
-    Cell: InteriorMut<'a, IF>,
-    T: Deref<Target = Cell> + 'a,
-> {
+pub struct TmcmModule<'a, IF: 'a, Cell: InteriorMut<'a, IF>, T: Deref<Target = Cell> + 'a> {
     /// The module address
-    address: u8,
+    address: AtomicU8,
     interface: T,
     pd1: PhantomData<&'a IF>,
     pd2: PhantomData<&'a T>,
 }
 
-impl<'a, IF: Interface, Cell: InteriorMut<'a, IF>, T: Deref<Target = Cell>>
-    TmcmModule<'a, IF, Cell, T>
-{
+impl<'a, IF, Cell: InteriorMut<'a, IF>, T: Deref<Target = Cell>> TmcmModule<'a, IF, Cell, T> {
     /// Create a new module
     pub fn new(interface: T, address: u8) -> Self {
         TmcmModule {
-            address,
+            address: AtomicU8::new(address),
             interface,
             pd1: PhantomData {},
             pd2: PhantomData {},
         }
     }
 
+    /// Returns the module address
+    pub fn address(&self) -> u8 {
+        self.address.load(Ordering::Relaxed)
+    }
+}
+
+impl<'a, IF: Interface, Cell: InteriorMut<'a, IF>, T: Deref<Target = Cell>>
+    TmcmModule<'a, IF, Cell, T>
+{
     /// Synchronously write a command and wait for the Reply
     pub fn write_command<Instruction: TmcmInstruction + DirectInstruction>(
         &'a self,
@@ -57,11 +76,9 @@ impl<'a, IF: Interface, Cell: InteriorMut<'a, IF>, T: Deref<Target = Cell>>
             .borrow_int_mut()
             .or(Err(Error::InterfaceUnavailable))?;
         interface
-            .transmit_command(&Command::new(self.address, instruction))
-            .map_err(|e| Error::InterfaceError(e))?;
-        let reply = interface
-            .receive_reply()
-            .map_err(|e| Error::InterfaceError(e))?;
+            .transmit_command(&Command::new(self.address(), instruction))
+            .map_err(Error::InterfaceError)?;
+        let reply = interface.receive_reply().map_err(Error::InterfaceError)?;
         match reply.status() {
             Status::Ok(_) => Ok(<Instruction::Return as Return>::from_operand(
                 reply.operand(),
@@ -69,6 +86,179 @@ impl<'a, IF: Interface, Cell: InteriorMut<'a, IF>, T: Deref<Target = Cell>>
             Status::Err(e) => Err(e.into()),
         }
     }
+
+    /// Write an instruction to the TMCL program memory of the module.
+    ///
+    /// The module must be in download mode, otherwise it will execute the instruction
+    /// instead and `Error::NotLoadedIntoEEPROM` is returned. Returns the stored program word,
+    /// which can be used to verify the download with `verify_program`.
+    pub fn store_instruction<Instruction: TmcmInstruction>(
+        &'a self,
+        instruction: Instruction,
+    ) -> Result<ProgramWord, Error<IF::Error>> {
+        let mut interface = self
+            .interface
+            .borrow_int_mut()
+            .or(Err(Error::InterfaceUnavailable))?;
+        program::store_instruction(&mut *interface, self.address(), instruction)
+    }
+
+    /// Change the address the module is reached at, store it to EEPROM and use it from now on.
+    ///
+    /// The new value is written to the global parameter and must be read back at the new address
+    /// before it is stored to EEPROM. Otherwise the old value is written back and the error
+    /// returned. If another module already answers at the new address `Error::AddressInUse` is
+    /// returned and nothing is written. Use `readdress_with` if the interface has to be
+    /// reconfigured for the change.
+    pub fn readdress(&'a self, change: AddressChange) -> Result<(), Error<IF::Error>> {
+        self.readdress_with(change, |_, _| Ok(()))
+    }
+
+    /// Change the address the module is reached at, reconfiguring the interface with `reopen`.
+    ///
+    /// `reopen` is called with the interface after the new value is written, and again with the
+    /// old value if the module does not answer at the new address.
+    ///
+    /// ```no_run
+    /// extern crate tmcl;
+    /// # #[cfg(feature = "socketcan")]
+    /// extern crate socketcan;
+    ///
+    /// use std::cell::RefCell;
+    ///
+    /// use tmcl::modules::tmcm::TmcmModule as Module;
+    /// use tmcl::modules::AddressChange;
+    /// # #[cfg(feature = "socketcan")]
+    /// use tmcl::CanInterface;
+    ///
+    /// # #[cfg(feature = "socketcan")]
+    /// fn main() {
+    ///     let socket = socketcan::CANSocket::open("vcan0").unwrap();
+    ///     let interface = RefCell::new(CanInterface::with_reply_id(socket, 2).unwrap());
+    ///     let module = Module::new(&interface, 1);
+    ///
+    ///     // Only accept replies from the new reply ID
+    ///     module
+    ///         .readdress_with(AddressChange::CANReplyId(3), |interface, change| match change {
+    ///             AddressChange::CANReplyId(id) => interface.set_reply_id(id),
+    ///             _ => Ok(()),
+    ///         })
+    ///         .unwrap();
+    /// }
+    /// # #[cfg(not(feature = "socketcan"))]
+    /// # fn main() {}
+    /// ```
+    pub fn readdress_with<F>(
+        &'a self,
+        change: AddressChange,
+        reopen: F,
+    ) -> Result<(), Error<IF::Error>>
+    where
+        F: FnMut(&mut IF, AddressChange) -> Result<(), IF::Error>,
+    {
+        let mut interface = self
+            .interface
+            .borrow_int_mut()
+            .or(Err(Error::InterfaceUnavailable))?;
+        let address = readdress::readdress(&mut *interface, self.address(), change, reopen)?;
+        self.address.store(address, Ordering::Relaxed);
+        Ok(())
+    }
+
+    /// Read all parameters in `snapshot::TMCM_PARAMETERS` of the first `motors` motors into a
+    /// snapshot.
+    ///
+    /// Parameters the module does not have are left out.
+    #[cfg(feature = "std")]
+    pub fn snapshot(&'a self, motors: u8) -> Result<snapshot::Snapshot, Error<IF::Error>> {
+        let mut interface = self
+            .interface
+            .borrow_int_mut()
+            .or(Err(Error::InterfaceUnavailable))?;
+        snapshot::read(
+            &mut *interface,
+            self.address(),
+            motors,
+            &snapshot::TMCM_PARAMETERS,
+        )
+    }
+
+    /// Write the settings of `snapshot` back to the module.
+    ///
+    /// Only the parameters `snapshot::TMCM_PARAMETERS` marks for restoring are written. If `store`
+    /// is set every parameter is also stored to EEPROM with `STAP` or `STGP`. Nothing is written
+    /// if the snapshot holds a motor above the first `motors` motors or a parameter the module
+    /// does not have, and the values written so far are set back if writing one fails.
+    #[cfg(feature = "std")]
+    pub fn restore(
+        &'a self,
+        snapshot: &snapshot::Snapshot,
+        motors: u8,
+        store: bool,
+    ) -> Result<(), Error<IF::Error>> {
+        let mut interface = self
+            .interface
+            .borrow_int_mut()
+            .or(Err(Error::InterfaceUnavailable))?;
+        snapshot::restore(
+            &mut *interface,
+            self.address(),
+            snapshot,
+            &snapshot::TMCM_PARAMETERS,
+            motors,
+            store,
+        )
+    }
+
+    /// Download a TMCL program to the module, starting at `start_address`.
+    ///
+    /// Puts the module in download mode, calls `f` to store the program with `store_instruction`
+    /// and leaves download mode again. Download mode is left even if `f` returns an error.
+    pub fn download<F, R>(&'a self, start_address: u32, f: F) -> Result<R, Error<IF::Error>>
+    where
+        F: FnOnce(&'a Self) -> Result<R, Error<IF::Error>>,
+    {
+        program::download(&*self.interface, self.address(), start_address, || f(self))
+    }
+
+    /// Read back the instruction stored at `address` in the TMCL program memory.
+    pub fn read_program_word(&'a self, address: u32) -> Result<ProgramWord, Error<IF::Error>> {
+        let mut interface = self
+            .interface
+            .borrow_int_mut()
+            .or(Err(Error::InterfaceUnavailable))?;
+        program::read_program_word(&mut *interface, self.address(), address)
+    }
+
+    /// Compare the TMCL program memory starting at `start_address` with `program`.
+    ///
+    /// Returns `Error::VerificationFailed` with the address of the first instruction that differs.
+    pub fn verify_program(
+        &'a self,
+        start_address: u32,
+        program: &[ProgramWord],
+    ) -> Result<(), Error<IF::Error>> {
+        let mut interface = self
+            .interface
+            .borrow_int_mut()
+            .or(Err(Error::InterfaceUnavailable))?;
+        program::verify_program(&mut *interface, self.address(), start_address, program)
+    }
+}
+
+#[cfg(feature = "async")]
+impl<'a, IF: AsyncInterface, Cell: InteriorMut<'a, IF>, T: Deref<Target = Cell>>
+    TmcmModule<'a, IF, Cell, T>
+{
+    /// Write a command and asynchronously wait for the Reply
+    ///
+    /// The interface is only borrowed while the returned future is polled.
+    pub fn write_command_async<Instruction: TmcmInstruction + DirectInstruction>(
+        &'a self,
+        instruction: Instruction,
+    ) -> WriteCommand<'a, IF, Cell, T, Instruction> {
+        WriteCommand::new(&self.interface, self.address(), instruction)
+    }
 }
 
 /// An `AxisParameter` useable with all TMCM modules other than TMCM-100 and Monopack 2.
@@ -82,3 +272,12 @@ pub trait ReadableTmcmAxisParameter: ReadableAxisParameter {}
 
 /// A `WriteableAxisParamtere` useable with all TMCM modules other than TMCM-100 and Monopack 2.
 pub trait WriteableTmcmAxisParameter: WriteableAxisParameter {}
+
+/// A `GlobalParameter` useable with all TMCM modules other than TMCM-100 and Monopack 2.
+pub trait TmcmGlobalParameter: GlobalParameter {}
+
+/// A `ReadableGlobalParameter` useable with all TMCM modules other than TMCM-100 and Monopack 2.
+pub trait ReadableTmcmGlobalParameter: ReadableGlobalParameter {}
+
+/// A `WriteableGlobalParameter` useable with all TMCM modules other than TMCM-100 and Monopack 2.
+pub trait WriteableTmcmGlobalParameter: WriteableGlobalParameter {}
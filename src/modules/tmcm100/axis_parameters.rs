@@ -0,0 +1,453 @@
+//! All axis parameters useable with TMCM-100 and Monopack 2 modules.
+//!
+//! Velocities and accelerations are 11 bit values of the TMC428 motion controller (0..2047).
+
+use AxisParameter;
+use ReadableAxisParameter;
+use Return;
+use WriteableAxisParameter;
+
+use modules::tmcm100::{
+    ReadableTmcm100AxisParameter, Tmcm100AxisParameter, WriteableTmcm100AxisParameter,
+};
+
+/// The highest velocity or acceleration of the TMC428 motion controller.
+const MAX_VELOCITY: u16 = 2047;
+
+axis_param_rw!(
+    /// The desired target position in position mode
+    TargetPosition,
+    i32,
+    0
+);
+impl TargetPosition {
+    pub fn new(position: i32) -> Self {
+        TargetPosition(position)
+    }
+}
+impl Tmcm100AxisParameter for TargetPosition {}
+impl ReadableTmcm100AxisParameter for TargetPosition {}
+impl WriteableTmcm100AxisParameter for TargetPosition {}
+
+axis_param_rw!(
+    /// The actual position of the motor.
+    ///
+    /// Stop the motor before overwriting it. Should normally only be
+    /// overwritten for reference position setting.
+    ActualPosition,
+    i32,
+    1
+);
+impl ActualPosition {
+    pub fn new(position: i32) -> Self {
+        ActualPosition(position)
+    }
+}
+impl Tmcm100AxisParameter for ActualPosition {}
+impl ReadableTmcm100AxisParameter for ActualPosition {}
+impl WriteableTmcm100AxisParameter for ActualPosition {}
+
+axis_param_rw!(
+    /// The target rotation speed.
+    ///
+    /// The desired speed in velocity mode (-2047..2047). Not valid in position mode.
+    TargetSpeed,
+    i32,
+    2,
+    -(MAX_VELOCITY as i32)..=MAX_VELOCITY as i32
+);
+impl TargetSpeed {
+    pub fn new(speed: i32) -> Self {
+        assert!(Self::is_valid(speed));
+        TargetSpeed(speed)
+    }
+}
+impl Tmcm100AxisParameter for TargetSpeed {}
+impl ReadableTmcm100AxisParameter for TargetSpeed {}
+impl WriteableTmcm100AxisParameter for TargetSpeed {}
+
+axis_param_r!(
+    /// The current rotation speed.
+    ActualSpeed,
+    i32,
+    3
+);
+impl Tmcm100AxisParameter for ActualSpeed {}
+impl ReadableTmcm100AxisParameter for ActualSpeed {}
+
+axis_param_rw!(
+    /// The maximum positioning speed.
+    ///
+    /// Adjust the pulse divisor (no. 154), if the speed value is very low (<50) or above the
+    /// upper limit (2047).
+    MaximumPositioningSpeed,
+    u16,
+    4,
+    0..=MAX_VELOCITY
+);
+impl MaximumPositioningSpeed {
+    pub fn new(speed: u16) -> Self {
+        assert!(Self::is_valid(speed));
+        MaximumPositioningSpeed(speed)
+    }
+}
+impl Tmcm100AxisParameter for MaximumPositioningSpeed {}
+impl ReadableTmcm100AxisParameter for MaximumPositioningSpeed {}
+impl WriteableTmcm100AxisParameter for MaximumPositioningSpeed {}
+
+axis_param_rw!(
+    /// The maximum acceleration.
+    ///
+    /// Maximum acceleration during ramp-up and maximum deceleration during ramp-down (0..2047).
+    MaximumAcceleration,
+    u16,
+    5,
+    0..=MAX_VELOCITY
+);
+impl MaximumAcceleration {
+    pub fn new(acc: u16) -> Self {
+        assert!(Self::is_valid(acc));
+        MaximumAcceleration(acc)
+    }
+}
+impl Tmcm100AxisParameter for MaximumAcceleration {}
+impl ReadableTmcm100AxisParameter for MaximumAcceleration {}
+impl WriteableTmcm100AxisParameter for MaximumAcceleration {}
+
+axis_param_rw!(
+    /// The absolute maximum current
+    ///
+    /// The most important motor setting, since too high values might cause motor damage!
+    /// 255 means 100% of the maximum current of the module.
+    AbsoluteMaxCurrent,
+    u8,
+    6
+);
+impl AbsoluteMaxCurrent {
+    pub fn new(current: u8) -> Self {
+        AbsoluteMaxCurrent(current)
+    }
+}
+impl Tmcm100AxisParameter for AbsoluteMaxCurrent {}
+impl ReadableTmcm100AxisParameter for AbsoluteMaxCurrent {}
+impl WriteableTmcm100AxisParameter for AbsoluteMaxCurrent {}
+
+axis_param_rw!(
+    /// The current used when the motor is not running.
+    ///
+    /// Same scaling as the absolute maximum current.
+    StandbyCurrent,
+    u8,
+    7
+);
+impl StandbyCurrent {
+    pub fn new(current: u8) -> Self {
+        StandbyCurrent(current)
+    }
+}
+impl Tmcm100AxisParameter for StandbyCurrent {}
+impl ReadableTmcm100AxisParameter for StandbyCurrent {}
+impl WriteableTmcm100AxisParameter for StandbyCurrent {}
+
+axis_param_r!(
+    /// Position reached flag
+    ///
+    /// This flag is always set when target position and actual position are equal.
+    PositionReachedFlag,
+    bool,
+    8
+);
+impl Tmcm100AxisParameter for PositionReachedFlag {}
+impl ReadableTmcm100AxisParameter for PositionReachedFlag {}
+
+axis_param_r!(
+    /// Reference switch state
+    ///
+    /// The logical state of the reference (left) switch.
+    ReferenceSwitchState,
+    bool,
+    9
+);
+impl Tmcm100AxisParameter for ReferenceSwitchState {}
+impl ReadableTmcm100AxisParameter for ReferenceSwitchState {}
+
+axis_param_r!(
+    /// Right limit switch state
+    ///
+    /// The logical state of the right limit switch.
+    RightLimitSwitchState,
+    bool,
+    10
+);
+impl Tmcm100AxisParameter for RightLimitSwitchState {}
+impl ReadableTmcm100AxisParameter for RightLimitSwitchState {}
+
+axis_param_r!(
+    /// Left limit switch state
+    ///
+    /// The logical state of the left limit switch (in three switch mode).
+    LeftLimitSwitchState,
+    bool,
+    11
+);
+impl Tmcm100AxisParameter for LeftLimitSwitchState {}
+impl ReadableTmcm100AxisParameter for LeftLimitSwitchState {}
+
+axis_param_rw!(
+    /// If set, deactivates the stop function of the right switch
+    RightLimitSwitchDisable,
+    bool,
+    12
+);
+impl RightLimitSwitchDisable {
+    pub fn disabled() -> Self {
+        RightLimitSwitchDisable(true)
+    }
+    pub fn enabled() -> Self {
+        RightLimitSwitchDisable(false)
+    }
+}
+impl Tmcm100AxisParameter for RightLimitSwitchDisable {}
+impl ReadableTmcm100AxisParameter for RightLimitSwitchDisable {}
+impl WriteableTmcm100AxisParameter for RightLimitSwitchDisable {}
+
+axis_param_rw!(
+    /// Deactivates the stop function of the left switch resp. reference switch if set.
+    LeftLimitSwitchDisable,
+    bool,
+    13
+);
+impl LeftLimitSwitchDisable {
+    pub fn disabled() -> Self {
+        LeftLimitSwitchDisable(true)
+    }
+    pub fn enabled() -> Self {
+        LeftLimitSwitchDisable(false)
+    }
+}
+impl Tmcm100AxisParameter for LeftLimitSwitchDisable {}
+impl ReadableTmcm100AxisParameter for LeftLimitSwitchDisable {}
+impl WriteableTmcm100AxisParameter for LeftLimitSwitchDisable {}
+
+/// Microstep Resolution
+///
+/// Note that modifying this parameter will affect the rotation speed in the same relation.
+/// The TMC428 supports up to 64 microsteps.
+#[derive(Debug, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub enum MicrostepResolution {
+    /// Fullstep
+    Full = 0,
+    /// Halfstep
+    Half = 1,
+    /// 4 microsteps
+    Micro4 = 2,
+    /// 8 microsteps
+    Micro8 = 3,
+    /// 16 microsteps
+    Micro16 = 4,
+    /// 32 microsteps
+    Micro32 = 5,
+    /// 64 microsteps
+    Micro64 = 6,
+}
+impl MicrostepResolution {
+    #[allow(clippy::result_unit_err)]
+    pub fn try_from_u8(v: u8) -> Result<Self, ()> {
+        match v {
+            0 => Ok(MicrostepResolution::Full),
+            1 => Ok(MicrostepResolution::Half),
+            2 => Ok(MicrostepResolution::Micro4),
+            3 => Ok(MicrostepResolution::Micro8),
+            4 => Ok(MicrostepResolution::Micro16),
+            5 => Ok(MicrostepResolution::Micro32),
+            6 => Ok(MicrostepResolution::Micro64),
+            _ => Err(()),
+        }
+    }
+}
+impl AxisParameter for MicrostepResolution {
+    const NUMBER: u8 = 140;
+}
+impl Return for MicrostepResolution {
+    fn from_operand(array: [u8; 4]) -> Self {
+        MicrostepResolution::try_from_u8(array[0]).unwrap()
+    }
+}
+impl Tmcm100AxisParameter for MicrostepResolution {}
+impl ReadableAxisParameter for MicrostepResolution {}
+impl ReadableTmcm100AxisParameter for MicrostepResolution {}
+impl WriteableAxisParameter for MicrostepResolution {
+    fn operand(&self) -> [u8; 4] {
+        [*self as u8, 0u8, 0u8, 0u8]
+    }
+}
+impl WriteableTmcm100AxisParameter for MicrostepResolution {}
+
+axis_param_rw!(
+    /// Ramp divisor
+    ///
+    /// The exponent of the scaling factor for the ramp generator. Change this
+    /// parameter carefully (in steps of one) and only while the motor is not
+    /// moving. Lower values lead to higher accelerations.
+    RampDivisor,
+    u8,
+    153,
+    0..=13
+);
+impl RampDivisor {
+    pub fn new(divisor: u8) -> Self {
+        assert!(Self::is_valid(divisor));
+        RampDivisor(divisor)
+    }
+}
+impl Tmcm100AxisParameter for RampDivisor {}
+impl ReadableTmcm100AxisParameter for RampDivisor {}
+impl WriteableTmcm100AxisParameter for RampDivisor {}
+
+axis_param_rw!(
+    /// Pulse divisor
+    ///
+    /// The exponent of the scaling factor for the pulse (step) generator.
+    /// Change this parameter carefully (in steps of one) and only while the
+    /// motor is not moving. Lower values lead to higher speeds.
+    PulseDivisor,
+    u8,
+    154,
+    0..=13
+);
+impl PulseDivisor {
+    pub fn new(divisor: u8) -> Self {
+        assert!(Self::is_valid(divisor));
+        PulseDivisor(divisor)
+    }
+}
+impl Tmcm100AxisParameter for PulseDivisor {}
+impl ReadableTmcm100AxisParameter for PulseDivisor {}
+impl WriteableTmcm100AxisParameter for PulseDivisor {}
+
+/// Reference search mode
+#[derive(Debug, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub enum ReferenceSearchMode {
+    /// Search left stop switch only.
+    LeftSwitch = 1,
+    /// Search right stop switch, then search left stop switch.
+    RightThenLeftSwitch = 2,
+    /// Search right stop switch, then search left stop switch from both sides.
+    RightThenLeftFromBothSides = 3,
+}
+impl ReferenceSearchMode {
+    #[allow(clippy::result_unit_err)]
+    pub fn try_from_u8(v: u8) -> Result<Self, ()> {
+        match v {
+            1 => Ok(ReferenceSearchMode::LeftSwitch),
+            2 => Ok(ReferenceSearchMode::RightThenLeftSwitch),
+            3 => Ok(ReferenceSearchMode::RightThenLeftFromBothSides),
+            _ => Err(()),
+        }
+    }
+}
+impl AxisParameter for ReferenceSearchMode {
+    const NUMBER: u8 = 193;
+}
+impl Return for ReferenceSearchMode {
+    fn from_operand(array: [u8; 4]) -> Self {
+        ReferenceSearchMode::try_from_u8(array[0]).unwrap()
+    }
+}
+impl Tmcm100AxisParameter for ReferenceSearchMode {}
+impl ReadableAxisParameter for ReferenceSearchMode {}
+impl ReadableTmcm100AxisParameter for ReferenceSearchMode {}
+impl WriteableAxisParameter for ReferenceSearchMode {
+    fn operand(&self) -> [u8; 4] {
+        [*self as u8, 0u8, 0u8, 0u8]
+    }
+}
+impl WriteableTmcm100AxisParameter for ReferenceSearchMode {}
+
+axis_param_rw!(
+    /// Reference search speed
+    ///
+    /// This value specifes the speed for roughly searching the reference switch (0..2047).
+    ReferenceSearchSpeed,
+    u16,
+    194,
+    0..=MAX_VELOCITY
+);
+impl ReferenceSearchSpeed {
+    pub fn new(speed: u16) -> Self {
+        assert!(Self::is_valid(speed));
+        ReferenceSearchSpeed(speed)
+    }
+}
+impl Tmcm100AxisParameter for ReferenceSearchSpeed {}
+impl ReadableTmcm100AxisParameter for ReferenceSearchSpeed {}
+impl WriteableTmcm100AxisParameter for ReferenceSearchSpeed {}
+
+axis_param_rw!(
+    /// Reference switch speed
+    ///
+    /// This parameter specifes the speed for searching the switching point (0..2047).
+    /// It should be slower than parameter 194.
+    ReferenceSwitchSpeed,
+    u16,
+    195,
+    0..=MAX_VELOCITY
+);
+impl ReferenceSwitchSpeed {
+    pub fn new(speed: u16) -> Self {
+        assert!(Self::is_valid(speed));
+        ReferenceSwitchSpeed(speed)
+    }
+}
+impl Tmcm100AxisParameter for ReferenceSwitchSpeed {}
+impl ReadableTmcm100AxisParameter for ReferenceSwitchSpeed {}
+impl WriteableTmcm100AxisParameter for ReferenceSwitchSpeed {}
+
+axis_param_r!(
+    /// End switch distance
+    ///
+    /// This parameter provides the distance between the end switches after
+    /// executing the RFS command (with reference search mode 2 or 3).
+    EndSwitchDistance,
+    i32,
+    196
+);
+impl Tmcm100AxisParameter for EndSwitchDistance {}
+impl ReadableTmcm100AxisParameter for EndSwitchDistance {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use modules::tmcm100::instructions::{GAP, SAP};
+    use Command;
+
+    #[test]
+    fn sap_maximum_positioning_speed() {
+        let command = Command::new(1, SAP::new(0, MaximumPositioningSpeed::new(2047)));
+        assert_eq!(command.serialize_can(), [5, 4, 0, 0, 0, 0x07, 0xff]);
+    }
+
+    #[test]
+    fn negative_target_speed() {
+        let command = Command::new(1, SAP::new(1, TargetSpeed::new(-1)));
+        assert_eq!(command.serialize_can(), [5, 2, 1, 255, 255, 255, 255]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn speed_out_of_range() {
+        MaximumPositioningSpeed::new(2048);
+    }
+
+    #[test]
+    fn gap_reference_search_mode() {
+        let command = Command::new(1, GAP::<ReferenceSearchMode>::new(0));
+        assert_eq!(command.serialize_can(), [6, 193, 0, 0, 0, 0, 0]);
+        assert_eq!(
+            ReferenceSearchMode::from_operand([3, 0, 0, 0]),
+            ReferenceSearchMode::RightThenLeftFromBothSides
+        );
+    }
+}
@@ -0,0 +1,49 @@
+//! All instructions available for TMCM-100 and Monopack 2 modules.
+
+pub use instructions::{
+    ApplicationStatus, EnterDownloadMode, ExitDownloadMode, FirmwareVersion, GetApplicationStatus,
+    GetFirmwareVersion, GetFirmwareVersionAscii, JumpCondition, MoveOperation, ProgramMemoryPart,
+    ReadProgramMemory, ReferenceSearchAction, ResetApplication, RunApplication, StepApplication,
+    StopApplication, WaitEvent, AAP, ACO, CALC, CALCX, CCO, COMP, CSUB, GAP, GCO, GIO, JA, JC, MST,
+    MVP, RFS, ROL, ROR, RSAP, RSUB, SAP, SCO, SIO, STAP, STOP, WAIT,
+};
+
+use modules::tmcm100::Tmcm100Instruction;
+
+use modules::tmcm100::{ReadableTmcm100AxisParameter, WriteableTmcm100AxisParameter};
+
+impl Tmcm100Instruction for ROR {}
+impl Tmcm100Instruction for ROL {}
+impl Tmcm100Instruction for MST {}
+impl Tmcm100Instruction for MVP {}
+impl<T: WriteableTmcm100AxisParameter> Tmcm100Instruction for SAP<T> {}
+impl<T: ReadableTmcm100AxisParameter> Tmcm100Instruction for GAP<T> {}
+impl<T: WriteableTmcm100AxisParameter> Tmcm100Instruction for STAP<T> {}
+impl<T: WriteableTmcm100AxisParameter> Tmcm100Instruction for RSAP<T> {}
+impl Tmcm100Instruction for RFS {}
+impl Tmcm100Instruction for SIO {}
+impl Tmcm100Instruction for GIO {}
+impl Tmcm100Instruction for CALC {}
+impl Tmcm100Instruction for COMP {}
+impl Tmcm100Instruction for JC {}
+impl Tmcm100Instruction for JA {}
+impl Tmcm100Instruction for CSUB {}
+impl Tmcm100Instruction for RSUB {}
+impl Tmcm100Instruction for WAIT {}
+impl Tmcm100Instruction for STOP {}
+impl Tmcm100Instruction for SCO {}
+impl Tmcm100Instruction for GCO {}
+impl Tmcm100Instruction for CCO {}
+impl Tmcm100Instruction for CALCX {}
+impl<T: WriteableTmcm100AxisParameter> Tmcm100Instruction for AAP<T> {}
+impl Tmcm100Instruction for ACO {}
+impl Tmcm100Instruction for StopApplication {}
+impl Tmcm100Instruction for RunApplication {}
+impl Tmcm100Instruction for StepApplication {}
+impl Tmcm100Instruction for ResetApplication {}
+impl Tmcm100Instruction for EnterDownloadMode {}
+impl Tmcm100Instruction for ExitDownloadMode {}
+impl Tmcm100Instruction for ReadProgramMemory {}
+impl Tmcm100Instruction for GetApplicationStatus {}
+impl Tmcm100Instruction for GetFirmwareVersion {}
+impl Tmcm100Instruction for GetFirmwareVersionAscii {}
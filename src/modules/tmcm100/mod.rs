@@ -1,4 +1,161 @@
 //! A `TMCM` type usable only with TMCM-100 and Monopack 2 modules.
 //!
-//! The specific interface for TMCM-100 and Monopack 2 is not implemented yet.
-//! For now you should use the `generic` module instead.
+//! These modules are based on the TMC428 motion controller, with 11 bit velocities and
+//! accelerations and their own axis parameter numbering.
+
+use lib::marker::PhantomData;
+use lib::ops::Deref;
+use lib::sync::atomic::{AtomicU8, Ordering};
+
+pub mod axis_parameters;
+pub mod instructions;
+
+use interior_mut::InteriorMut;
+
+#[cfg(feature = "async")]
+use AsyncInterface;
+#[cfg(feature = "async")]
+use WriteCommand;
+
+use instructions::DirectInstruction;
+use program;
+use program::ProgramWord;
+use AxisParameter;
+use Command;
+use Error;
+use Instruction;
+use Interface;
+use ReadableAxisParameter;
+use Return;
+use Status;
+use WriteableAxisParameter;
+
+/// This type represents a TMCM-100 or Monopack 2 module.
+#[derive(Debug)]
+pub struct Tmcm100Module<'a, IF: 'a, Cell: InteriorMut<'a, IF>, T: Deref<Target = Cell> + 'a> {
+    /// The module address
+    address: AtomicU8,
+    interface: T,
+    pd1: PhantomData<&'a IF>,
+    pd2: PhantomData<&'a T>,
+}
+
+impl<'a, IF, Cell: InteriorMut<'a, IF>, T: Deref<Target = Cell>> Tmcm100Module<'a, IF, Cell, T> {
+    /// Create a new module
+    pub fn new(interface: T, address: u8) -> Self {
+        Tmcm100Module {
+            address: AtomicU8::new(address),
+            interface,
+            pd1: PhantomData {},
+            pd2: PhantomData {},
+        }
+    }
+
+    /// Returns the module address
+    pub fn address(&self) -> u8 {
+        self.address.load(Ordering::Relaxed)
+    }
+}
+
+impl<'a, IF: Interface, Cell: InteriorMut<'a, IF>, T: Deref<Target = Cell>>
+    Tmcm100Module<'a, IF, Cell, T>
+{
+    /// Synchronously write a command and wait for the Reply
+    pub fn write_command<Instruction: Tmcm100Instruction + DirectInstruction>(
+        &'a self,
+        instruction: Instruction,
+    ) -> Result<Instruction::Return, Error<IF::Error>> {
+        let mut interface = self
+            .interface
+            .borrow_int_mut()
+            .or(Err(Error::InterfaceUnavailable))?;
+        interface
+            .transmit_command(&Command::new(self.address(), instruction))
+            .map_err(Error::InterfaceError)?;
+        let reply = interface.receive_reply().map_err(Error::InterfaceError)?;
+        match reply.status() {
+            Status::Ok(_) => Ok(<Instruction::Return as Return>::from_operand(
+                reply.operand(),
+            )),
+            Status::Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Write an instruction to the TMCL program memory of the module.
+    ///
+    /// The module must be in download mode, otherwise it will execute the instruction
+    /// instead and `Error::NotLoadedIntoEEPROM` is returned. Returns the stored program word,
+    /// which can be used to verify the download with `verify_program`.
+    pub fn store_instruction<Instruction: Tmcm100Instruction>(
+        &'a self,
+        instruction: Instruction,
+    ) -> Result<ProgramWord, Error<IF::Error>> {
+        let mut interface = self
+            .interface
+            .borrow_int_mut()
+            .or(Err(Error::InterfaceUnavailable))?;
+        program::store_instruction(&mut *interface, self.address(), instruction)
+    }
+
+    /// Download a TMCL program to the module, starting at `start_address`.
+    ///
+    /// Puts the module in download mode, calls `f` to store the program with `store_instruction`
+    /// and leaves download mode again. Download mode is left even if `f` returns an error.
+    pub fn download<F, R>(&'a self, start_address: u32, f: F) -> Result<R, Error<IF::Error>>
+    where
+        F: FnOnce(&'a Self) -> Result<R, Error<IF::Error>>,
+    {
+        program::download(&*self.interface, self.address(), start_address, || f(self))
+    }
+
+    /// Read back the instruction stored at `address` in the TMCL program memory.
+    pub fn read_program_word(&'a self, address: u32) -> Result<ProgramWord, Error<IF::Error>> {
+        let mut interface = self
+            .interface
+            .borrow_int_mut()
+            .or(Err(Error::InterfaceUnavailable))?;
+        program::read_program_word(&mut *interface, self.address(), address)
+    }
+
+    /// Compare the TMCL program memory starting at `start_address` with `program`.
+    ///
+    /// Returns `Error::VerificationFailed` with the address of the first instruction that differs.
+    pub fn verify_program(
+        &'a self,
+        start_address: u32,
+        program: &[ProgramWord],
+    ) -> Result<(), Error<IF::Error>> {
+        let mut interface = self
+            .interface
+            .borrow_int_mut()
+            .or(Err(Error::InterfaceUnavailable))?;
+        program::verify_program(&mut *interface, self.address(), start_address, program)
+    }
+}
+
+#[cfg(feature = "async")]
+impl<'a, IF: AsyncInterface, Cell: InteriorMut<'a, IF>, T: Deref<Target = Cell>>
+    Tmcm100Module<'a, IF, Cell, T>
+{
+    /// Write a command and asynchronously wait for the Reply
+    ///
+    /// The interface is only borrowed while the returned future is polled.
+    pub fn write_command_async<Instruction: Tmcm100Instruction + DirectInstruction>(
+        &'a self,
+        instruction: Instruction,
+    ) -> WriteCommand<'a, IF, Cell, T, Instruction> {
+        WriteCommand::new(&self.interface, self.address(), instruction)
+    }
+}
+
+/// An `Instruction` useable with TMCM-100 and Monopack 2 modules.
+pub trait Tmcm100Instruction: Instruction {}
+
+/// An `AxisParameter` useable with TMCM-100 and Monopack 2 modules.
+pub trait Tmcm100AxisParameter: AxisParameter {}
+
+/// A `ReadableAxisParameter` useable with TMCM-100 and Monopack 2 modules.
+pub trait ReadableTmcm100AxisParameter: ReadableAxisParameter {}
+
+/// A `WriteableAxisParameter` useable with TMCM-100 and Monopack 2 modules.
+pub trait WriteableTmcm100AxisParameter: WriteableAxisParameter {}
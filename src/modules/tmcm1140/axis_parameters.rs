@@ -0,0 +1,337 @@
+//! All axis parameters useable with TMCM-1140 modules.
+//!
+//! Velocities and accelerations are 11 bit values of the TMC429 motion controller (0..2047).
+//! Parameters without module specific ranges are shared with `modules::tmcm::axis_parameters`.
+
+pub use modules::tmcm::axis_parameters::{
+    ActualLoadValue, ActualPosition, ActualSpeed, BoostCurrent, ChopperBlankTime, ChopperMode,
+    ChopperOffTime, CurrentDownStep, CurrentUpStep, DriverErrorFlags, EncoderPosition,
+    EncoderResolution, EndSwitchDistance, FreewheelingDelay, HomeSearchMode, HomeSwitchState,
+    HysteresisDecrement, HysteresisEnd, HysteresisStart, LastReferencePosition,
+    LeftLimitSwitchDisable, LeftLimitSwitchState, MaximumEncoderDeviation, MicrostepResolution,
+    PositionReachedFlag, PowerDownDelay, PulseDivisor, RampDivisor, ReferenceSearchMode,
+    RightLimitSwitchDisable, RightLimitSwitchState, SearchMode, SlowRunCurrent,
+    SmartEnergyActualCurrent, SmartEnergyCurrentMinimum, SmartEnergyHysteresis,
+    SmartEnergyHysteresisStart, SmartEnergyThresholdSpeed, StallGuard2FilterEnable,
+    StallGuard2Threshold, StopOnStall, TargetPosition, Vsense,
+};
+
+use AxisParameter;
+use ReadableAxisParameter;
+use Return;
+use WriteableAxisParameter;
+
+use modules::tmcm1140::{
+    ReadableTmcm1140AxisParameter, Tmcm1140AxisParameter, WriteableTmcm1140AxisParameter,
+};
+
+/// The highest velocity of the module.
+const MAX_VELOCITY: u32 = 2047;
+
+/// The highest acceleration of the module.
+const MAX_ACCELERATION: u32 = 2047;
+
+axis_param_rw!(
+    /// The target rotation speed.
+    ///
+    /// The desired speed in velocity mode (-2047..2047). Not valid in position mode.
+    TargetSpeed,
+    i32,
+    2,
+    -(MAX_VELOCITY as i32)..=MAX_VELOCITY as i32
+);
+impl TargetSpeed {
+    pub fn new(speed: i32) -> Self {
+        assert!(Self::is_valid(speed));
+        TargetSpeed(speed)
+    }
+}
+impl Tmcm1140AxisParameter for TargetSpeed {}
+impl ReadableTmcm1140AxisParameter for TargetSpeed {}
+impl WriteableTmcm1140AxisParameter for TargetSpeed {}
+
+axis_param_rw!(
+    /// The maximum positioning speed.
+    ///
+    /// Adjust the pulse divisor (no. 154), if the speed value is very low (<50) or above the
+    /// upper limit (2047).
+    MaximumPositioningSpeed,
+    u32,
+    4,
+    0..=MAX_VELOCITY
+);
+impl MaximumPositioningSpeed {
+    pub fn new(speed: u32) -> Self {
+        assert!(Self::is_valid(speed));
+        MaximumPositioningSpeed(speed)
+    }
+}
+impl Tmcm1140AxisParameter for MaximumPositioningSpeed {}
+impl ReadableTmcm1140AxisParameter for MaximumPositioningSpeed {}
+impl WriteableTmcm1140AxisParameter for MaximumPositioningSpeed {}
+
+axis_param_rw!(
+    /// The maximum acceleration.
+    ///
+    /// Maximum acceleration during ramp-up and maximum deceleration during ramp-down (0..2047).
+    MaximumAcceleration,
+    u32,
+    5,
+    0..=MAX_ACCELERATION
+);
+impl MaximumAcceleration {
+    pub fn new(acc: u32) -> Self {
+        assert!(Self::is_valid(acc));
+        MaximumAcceleration(acc)
+    }
+}
+impl Tmcm1140AxisParameter for MaximumAcceleration {}
+impl ReadableTmcm1140AxisParameter for MaximumAcceleration {}
+impl WriteableTmcm1140AxisParameter for MaximumAcceleration {}
+
+axis_param_rw!(
+    /// The absolute maximum current
+    ///
+    /// The most important motor setting, since too high values might cause motor damage!
+    /// 255 means 100% of the maximum current of the module.
+    AbsoluteMaxCurrent,
+    u8,
+    6
+);
+impl AbsoluteMaxCurrent {
+    pub fn new(current: u8) -> Self {
+        AbsoluteMaxCurrent(current)
+    }
+}
+impl Tmcm1140AxisParameter for AbsoluteMaxCurrent {}
+impl ReadableTmcm1140AxisParameter for AbsoluteMaxCurrent {}
+impl WriteableTmcm1140AxisParameter for AbsoluteMaxCurrent {}
+
+axis_param_rw!(
+    /// The current used when the motor is not running.
+    ///
+    /// Same scaling as the absolute maximum current. Please see also parameter 214
+    /// (PowerDownDelay).
+    StandbyCurrent,
+    u8,
+    7
+);
+impl StandbyCurrent {
+    pub fn new(current: u8) -> Self {
+        StandbyCurrent(current)
+    }
+}
+impl Tmcm1140AxisParameter for StandbyCurrent {}
+impl ReadableTmcm1140AxisParameter for StandbyCurrent {}
+impl WriteableTmcm1140AxisParameter for StandbyCurrent {}
+
+axis_param_rw!(
+    /// Reference search speed
+    ///
+    /// This value specifes the speed for roughly searching the reference switch (0..2047).
+    ReferenceSearchSpeed,
+    u32,
+    194,
+    0..=MAX_VELOCITY
+);
+impl ReferenceSearchSpeed {
+    pub fn new(speed: u32) -> Self {
+        assert!(Self::is_valid(speed));
+        ReferenceSearchSpeed(speed)
+    }
+}
+impl Tmcm1140AxisParameter for ReferenceSearchSpeed {}
+impl ReadableTmcm1140AxisParameter for ReferenceSearchSpeed {}
+impl WriteableTmcm1140AxisParameter for ReferenceSearchSpeed {}
+
+axis_param_rw!(
+    /// Reference switch speed
+    ///
+    /// This parameter specifes the speed for searching the switching point (0..2047).
+    /// It should be slower than parameter 194.
+    ReferenceSwitchSpeed,
+    u32,
+    195,
+    0..=MAX_VELOCITY
+);
+impl ReferenceSwitchSpeed {
+    pub fn new(speed: u32) -> Self {
+        assert!(Self::is_valid(speed));
+        ReferenceSwitchSpeed(speed)
+    }
+}
+impl Tmcm1140AxisParameter for ReferenceSwitchSpeed {}
+impl ReadableTmcm1140AxisParameter for ReferenceSwitchSpeed {}
+impl WriteableTmcm1140AxisParameter for ReferenceSwitchSpeed {}
+
+impl Tmcm1140AxisParameter for TargetPosition {}
+impl ReadableTmcm1140AxisParameter for TargetPosition {}
+impl WriteableTmcm1140AxisParameter for TargetPosition {}
+
+impl Tmcm1140AxisParameter for ActualPosition {}
+impl ReadableTmcm1140AxisParameter for ActualPosition {}
+impl WriteableTmcm1140AxisParameter for ActualPosition {}
+
+impl Tmcm1140AxisParameter for ActualSpeed {}
+impl ReadableTmcm1140AxisParameter for ActualSpeed {}
+
+impl Tmcm1140AxisParameter for PositionReachedFlag {}
+impl ReadableTmcm1140AxisParameter for PositionReachedFlag {}
+
+impl Tmcm1140AxisParameter for HomeSwitchState {}
+impl ReadableTmcm1140AxisParameter for HomeSwitchState {}
+
+impl Tmcm1140AxisParameter for RightLimitSwitchState {}
+impl ReadableTmcm1140AxisParameter for RightLimitSwitchState {}
+
+impl Tmcm1140AxisParameter for LeftLimitSwitchState {}
+impl ReadableTmcm1140AxisParameter for LeftLimitSwitchState {}
+
+impl Tmcm1140AxisParameter for RightLimitSwitchDisable {}
+impl ReadableTmcm1140AxisParameter for RightLimitSwitchDisable {}
+impl WriteableTmcm1140AxisParameter for RightLimitSwitchDisable {}
+
+impl Tmcm1140AxisParameter for LeftLimitSwitchDisable {}
+impl ReadableTmcm1140AxisParameter for LeftLimitSwitchDisable {}
+impl WriteableTmcm1140AxisParameter for LeftLimitSwitchDisable {}
+
+impl Tmcm1140AxisParameter for MicrostepResolution {}
+impl ReadableTmcm1140AxisParameter for MicrostepResolution {}
+impl WriteableTmcm1140AxisParameter for MicrostepResolution {}
+
+impl Tmcm1140AxisParameter for RampDivisor {}
+impl ReadableTmcm1140AxisParameter for RampDivisor {}
+impl WriteableTmcm1140AxisParameter for RampDivisor {}
+
+impl Tmcm1140AxisParameter for PulseDivisor {}
+impl ReadableTmcm1140AxisParameter for PulseDivisor {}
+impl WriteableTmcm1140AxisParameter for PulseDivisor {}
+
+impl Tmcm1140AxisParameter for Vsense {}
+impl ReadableTmcm1140AxisParameter for Vsense {}
+
+impl Tmcm1140AxisParameter for ReferenceSearchMode {}
+impl ReadableTmcm1140AxisParameter for ReferenceSearchMode {}
+impl WriteableTmcm1140AxisParameter for ReferenceSearchMode {}
+
+impl Tmcm1140AxisParameter for EndSwitchDistance {}
+impl ReadableTmcm1140AxisParameter for EndSwitchDistance {}
+
+impl Tmcm1140AxisParameter for LastReferencePosition {}
+impl ReadableTmcm1140AxisParameter for LastReferencePosition {}
+
+impl Tmcm1140AxisParameter for BoostCurrent {}
+impl ReadableTmcm1140AxisParameter for BoostCurrent {}
+impl WriteableTmcm1140AxisParameter for BoostCurrent {}
+
+impl Tmcm1140AxisParameter for PowerDownDelay {}
+impl ReadableTmcm1140AxisParameter for PowerDownDelay {}
+impl WriteableTmcm1140AxisParameter for PowerDownDelay {}
+
+impl Tmcm1140AxisParameter for ChopperBlankTime {}
+impl ReadableTmcm1140AxisParameter for ChopperBlankTime {}
+impl WriteableTmcm1140AxisParameter for ChopperBlankTime {}
+
+impl Tmcm1140AxisParameter for ChopperMode {}
+impl ReadableTmcm1140AxisParameter for ChopperMode {}
+impl WriteableTmcm1140AxisParameter for ChopperMode {}
+
+impl Tmcm1140AxisParameter for HysteresisDecrement {}
+impl ReadableTmcm1140AxisParameter for HysteresisDecrement {}
+impl WriteableTmcm1140AxisParameter for HysteresisDecrement {}
+
+impl Tmcm1140AxisParameter for HysteresisEnd {}
+impl ReadableTmcm1140AxisParameter for HysteresisEnd {}
+impl WriteableTmcm1140AxisParameter for HysteresisEnd {}
+
+impl Tmcm1140AxisParameter for HysteresisStart {}
+impl ReadableTmcm1140AxisParameter for HysteresisStart {}
+impl WriteableTmcm1140AxisParameter for HysteresisStart {}
+
+impl Tmcm1140AxisParameter for ChopperOffTime {}
+impl ReadableTmcm1140AxisParameter for ChopperOffTime {}
+impl WriteableTmcm1140AxisParameter for ChopperOffTime {}
+
+impl Tmcm1140AxisParameter for SmartEnergyCurrentMinimum {}
+impl ReadableTmcm1140AxisParameter for SmartEnergyCurrentMinimum {}
+impl WriteableTmcm1140AxisParameter for SmartEnergyCurrentMinimum {}
+
+impl Tmcm1140AxisParameter for CurrentDownStep {}
+impl ReadableTmcm1140AxisParameter for CurrentDownStep {}
+impl WriteableTmcm1140AxisParameter for CurrentDownStep {}
+
+impl Tmcm1140AxisParameter for SmartEnergyHysteresis {}
+impl ReadableTmcm1140AxisParameter for SmartEnergyHysteresis {}
+impl WriteableTmcm1140AxisParameter for SmartEnergyHysteresis {}
+
+impl Tmcm1140AxisParameter for CurrentUpStep {}
+impl ReadableTmcm1140AxisParameter for CurrentUpStep {}
+impl WriteableTmcm1140AxisParameter for CurrentUpStep {}
+
+impl Tmcm1140AxisParameter for SmartEnergyHysteresisStart {}
+impl ReadableTmcm1140AxisParameter for SmartEnergyHysteresisStart {}
+impl WriteableTmcm1140AxisParameter for SmartEnergyHysteresisStart {}
+
+impl Tmcm1140AxisParameter for StallGuard2FilterEnable {}
+impl ReadableTmcm1140AxisParameter for StallGuard2FilterEnable {}
+impl WriteableTmcm1140AxisParameter for StallGuard2FilterEnable {}
+
+impl Tmcm1140AxisParameter for StallGuard2Threshold {}
+impl ReadableTmcm1140AxisParameter for StallGuard2Threshold {}
+impl WriteableTmcm1140AxisParameter for StallGuard2Threshold {}
+
+impl Tmcm1140AxisParameter for SmartEnergyActualCurrent {}
+impl ReadableTmcm1140AxisParameter for SmartEnergyActualCurrent {}
+
+impl Tmcm1140AxisParameter for StopOnStall {}
+impl ReadableTmcm1140AxisParameter for StopOnStall {}
+impl WriteableTmcm1140AxisParameter for StopOnStall {}
+
+impl Tmcm1140AxisParameter for SmartEnergyThresholdSpeed {}
+impl ReadableTmcm1140AxisParameter for SmartEnergyThresholdSpeed {}
+impl WriteableTmcm1140AxisParameter for SmartEnergyThresholdSpeed {}
+
+impl Tmcm1140AxisParameter for SlowRunCurrent {}
+impl ReadableTmcm1140AxisParameter for SlowRunCurrent {}
+impl WriteableTmcm1140AxisParameter for SlowRunCurrent {}
+
+impl Tmcm1140AxisParameter for FreewheelingDelay {}
+impl ReadableTmcm1140AxisParameter for FreewheelingDelay {}
+impl WriteableTmcm1140AxisParameter for FreewheelingDelay {}
+
+impl Tmcm1140AxisParameter for ActualLoadValue {}
+impl ReadableTmcm1140AxisParameter for ActualLoadValue {}
+
+impl Tmcm1140AxisParameter for DriverErrorFlags {}
+impl ReadableTmcm1140AxisParameter for DriverErrorFlags {}
+
+impl Tmcm1140AxisParameter for EncoderPosition {}
+impl ReadableTmcm1140AxisParameter for EncoderPosition {}
+impl WriteableTmcm1140AxisParameter for EncoderPosition {}
+
+impl Tmcm1140AxisParameter for EncoderResolution {}
+impl ReadableTmcm1140AxisParameter for EncoderResolution {}
+impl WriteableTmcm1140AxisParameter for EncoderResolution {}
+
+impl Tmcm1140AxisParameter for MaximumEncoderDeviation {}
+impl ReadableTmcm1140AxisParameter for MaximumEncoderDeviation {}
+impl WriteableTmcm1140AxisParameter for MaximumEncoderDeviation {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use modules::tmcm1140::instructions::SAP;
+    use Command;
+
+    #[test]
+    fn sap_target_speed() {
+        let command = Command::new(1, SAP::new(0, TargetSpeed::new(-2047)));
+        assert_eq!(command.serialize_can(), [5, 2, 0, 255, 255, 0xf8, 0x01]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn speed_out_of_range() {
+        ReferenceSearchSpeed::new(2048);
+    }
+}
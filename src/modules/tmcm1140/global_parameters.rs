@@ -0,0 +1,42 @@
+//! All global parameters useable with TMCM-1140 modules.
+//!
+//! The module has the bank 0 interface parameters for RS485 and CAN, the 56 user variables in
+//! bank 2 and the interrupt configuration in bank 3. The bank 0 and bank 3 parameters are the
+//! same for all per-product modules, only the number of user variables differs.
+
+pub use modules::tmcm::global_parameters::{
+    CANBitRate, CANId, CANReplyId, EEPROMLock, InputChange0Edge, InputChange1Edge, InterruptEdge,
+    SerialAddress, SerialBaudRate, Timer0Period, Timer1Period, Timer2Period,
+};
+
+use GlobalParameter;
+use ReadableGlobalParameter;
+use Return;
+use WriteableGlobalParameter;
+
+use modules::tmcm1140::{
+    ReadableTmcm1140GlobalParameter, Tmcm1140GlobalParameter, WriteableTmcm1140GlobalParameter,
+};
+
+user_variables!(
+    /// User variable
+    ///
+    /// A general purpose variable, `N` is the variable number (0..55). Use `STGP` to store it in
+    /// EEPROM, so it is restored at power up.
+    ///
+    /// ```compile_fail
+    /// use tmcl::modules::tmcm1140::global_parameters::UserVariable;
+    /// use tmcl::modules::tmcm1140::instructions::GGP;
+    /// use tmcl::Instruction;
+    ///
+    /// GGP::<UserVariable<56>>::new().type_number();
+    /// ```
+    UserVariable,
+    55
+);
+
+product_global_parameters!(
+    Tmcm1140GlobalParameter,
+    ReadableTmcm1140GlobalParameter,
+    WriteableTmcm1140GlobalParameter
+);
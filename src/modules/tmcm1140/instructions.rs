@@ -0,0 +1,27 @@
+//! All instructions available for TMCM-1140 modules.
+//!
+//! The TMCM-1140 supports the complete TMCL instruction set of `modules::tmcm`, so the
+//! instructions are the same for all per-product modules.
+
+pub use instructions::{
+    ApplicationStatus, EnterDownloadMode, ErrorFlag, ExitDownloadMode, FirmwareVersion,
+    GetApplicationStatus, GetFirmwareVersion, GetFirmwareVersionAscii, InterruptVector,
+    JumpCondition, MoveOperation, ProgramMemoryPart, ReadProgramMemory, ReferenceSearchAction,
+    ResetApplication, RestoreFactoryDefaults, RunApplication, StepApplication, StopApplication,
+    WaitEvent, AAP, ACO, AGP, CALC, CALCX, CCO, CLE, COMP, CSUB, DI, EI, GAP, GCO, GGP, GIO, JA,
+    JC, MST, MVP, RETI, RFS, ROL, ROR, RSAP, RSGP, RSUB, SAP, SCO, SGP, SIO, STAP, STGP, STOP,
+    VECT, WAIT,
+};
+
+use modules::tmcm1140::{
+    ReadableTmcm1140AxisParameter, ReadableTmcm1140GlobalParameter, Tmcm1140Instruction,
+    WriteableTmcm1140AxisParameter, WriteableTmcm1140GlobalParameter,
+};
+
+product_instructions!(
+    Tmcm1140Instruction,
+    ReadableTmcm1140AxisParameter,
+    WriteableTmcm1140AxisParameter,
+    ReadableTmcm1140GlobalParameter,
+    WriteableTmcm1140GlobalParameter
+);
@@ -0,0 +1,56 @@
+//! A `TMCM` type usable only with TMCM-1140 modules.
+//!
+//! The TMCM-1140 is a single axis module based on the TMC429 motion controller and the TMC262
+//! driver. Only the axis and global parameters the module actually has are available, with
+//! their valid ranges.
+//!
+//! ```compile_fail
+//! use tmcl::modules::tmcm::axis_parameters::MaximumDeceleration;
+//! use tmcl::modules::tmcm1140::instructions::SAP;
+//! use tmcl::modules::tmcm1140::Tmcm1140Instruction;
+//!
+//! fn tmcm1140_instruction<T: Tmcm1140Instruction>(_: T) {}
+//!
+//! // The TMCM-1140 has no separate maximum deceleration
+//! tmcm1140_instruction(SAP::new(0, MaximumDeceleration::new(1000)));
+//! ```
+
+pub mod axis_parameters;
+pub mod global_parameters;
+pub mod instructions;
+
+use AxisParameter;
+use GlobalParameter;
+use Instruction;
+use ReadableAxisParameter;
+use ReadableGlobalParameter;
+use WriteableAxisParameter;
+use WriteableGlobalParameter;
+
+product_module!(
+    /// This type represents a TMCM-1140 module.
+    Tmcm1140Module,
+    Tmcm1140Instruction,
+    1
+);
+
+/// An `Instruction` useable with TMCM-1140 modules.
+pub trait Tmcm1140Instruction: Instruction {}
+
+/// An `AxisParameter` useable with TMCM-1140 modules.
+pub trait Tmcm1140AxisParameter: AxisParameter {}
+
+/// A `ReadableAxisParameter` useable with TMCM-1140 modules.
+pub trait ReadableTmcm1140AxisParameter: ReadableAxisParameter {}
+
+/// A `WriteableAxisParameter` useable with TMCM-1140 modules.
+pub trait WriteableTmcm1140AxisParameter: WriteableAxisParameter {}
+
+/// A `GlobalParameter` useable with TMCM-1140 modules.
+pub trait Tmcm1140GlobalParameter: GlobalParameter {}
+
+/// A `ReadableGlobalParameter` useable with TMCM-1140 modules.
+pub trait ReadableTmcm1140GlobalParameter: ReadableGlobalParameter {}
+
+/// A `WriteableGlobalParameter` useable with TMCM-1140 modules.
+pub trait WriteableTmcm1140GlobalParameter: WriteableGlobalParameter {}
@@ -0,0 +1,315 @@
+//! All axis parameters useable with TMCM-1160 modules.
+//!
+//! Velocities and accelerations are 11 bit values of the TMC429 motion controller (0..2047).
+//! Parameters without module specific ranges are shared with `modules::tmcm::axis_parameters`.
+
+pub use modules::tmcm::axis_parameters::{
+    ActualLoadValue, ActualPosition, ActualSpeed, BoostCurrent, ChopperBlankTime, ChopperMode,
+    ChopperOffTime, CurrentDownStep, CurrentUpStep, DriverErrorFlags, EncoderPosition,
+    EncoderResolution, EndSwitchDistance, FreewheelingDelay, HomeSearchMode, HomeSwitchState,
+    HysteresisDecrement, HysteresisEnd, HysteresisStart, LastReferencePosition,
+    LeftLimitSwitchDisable, LeftLimitSwitchState, MaximumEncoderDeviation, MicrostepResolution,
+    PositionReachedFlag, PowerDownDelay, PulseDivisor, RampDivisor, ReferenceSearchMode,
+    RightLimitSwitchDisable, RightLimitSwitchState, SearchMode, SlowRunCurrent,
+    SmartEnergyActualCurrent, SmartEnergyCurrentMinimum, SmartEnergyHysteresis,
+    SmartEnergyHysteresisStart, SmartEnergyThresholdSpeed, StallGuard2FilterEnable,
+    StallGuard2Threshold, StopOnStall, TargetPosition,
+};
+
+use AxisParameter;
+use ReadableAxisParameter;
+use Return;
+use WriteableAxisParameter;
+
+use modules::tmcm1160::{
+    ReadableTmcm1160AxisParameter, Tmcm1160AxisParameter, WriteableTmcm1160AxisParameter,
+};
+
+/// The highest velocity of the module.
+const MAX_VELOCITY: u32 = 2047;
+
+/// The highest acceleration of the module.
+const MAX_ACCELERATION: u32 = 2047;
+
+axis_param_rw!(
+    /// The target rotation speed.
+    ///
+    /// The desired speed in velocity mode (-2047..2047). Not valid in position mode.
+    TargetSpeed,
+    i32,
+    2,
+    -(MAX_VELOCITY as i32)..=MAX_VELOCITY as i32
+);
+impl TargetSpeed {
+    pub fn new(speed: i32) -> Self {
+        assert!(Self::is_valid(speed));
+        TargetSpeed(speed)
+    }
+}
+impl Tmcm1160AxisParameter for TargetSpeed {}
+impl ReadableTmcm1160AxisParameter for TargetSpeed {}
+impl WriteableTmcm1160AxisParameter for TargetSpeed {}
+
+axis_param_rw!(
+    /// The maximum positioning speed.
+    ///
+    /// Adjust the pulse divisor (no. 154), if the speed value is very low (<50) or above the
+    /// upper limit (2047).
+    MaximumPositioningSpeed,
+    u32,
+    4,
+    0..=MAX_VELOCITY
+);
+impl MaximumPositioningSpeed {
+    pub fn new(speed: u32) -> Self {
+        assert!(Self::is_valid(speed));
+        MaximumPositioningSpeed(speed)
+    }
+}
+impl Tmcm1160AxisParameter for MaximumPositioningSpeed {}
+impl ReadableTmcm1160AxisParameter for MaximumPositioningSpeed {}
+impl WriteableTmcm1160AxisParameter for MaximumPositioningSpeed {}
+
+axis_param_rw!(
+    /// The maximum acceleration.
+    ///
+    /// Maximum acceleration during ramp-up and maximum deceleration during ramp-down (0..2047).
+    MaximumAcceleration,
+    u32,
+    5,
+    0..=MAX_ACCELERATION
+);
+impl MaximumAcceleration {
+    pub fn new(acc: u32) -> Self {
+        assert!(Self::is_valid(acc));
+        MaximumAcceleration(acc)
+    }
+}
+impl Tmcm1160AxisParameter for MaximumAcceleration {}
+impl ReadableTmcm1160AxisParameter for MaximumAcceleration {}
+impl WriteableTmcm1160AxisParameter for MaximumAcceleration {}
+
+axis_param_rw!(
+    /// The absolute maximum current
+    ///
+    /// The most important motor setting, since too high values might cause motor damage!
+    /// 255 means 100% of the maximum current of the module.
+    AbsoluteMaxCurrent,
+    u8,
+    6
+);
+impl AbsoluteMaxCurrent {
+    pub fn new(current: u8) -> Self {
+        AbsoluteMaxCurrent(current)
+    }
+}
+impl Tmcm1160AxisParameter for AbsoluteMaxCurrent {}
+impl ReadableTmcm1160AxisParameter for AbsoluteMaxCurrent {}
+impl WriteableTmcm1160AxisParameter for AbsoluteMaxCurrent {}
+
+axis_param_rw!(
+    /// The current used when the motor is not running.
+    ///
+    /// Same scaling as the absolute maximum current. Please see also parameter 214
+    /// (PowerDownDelay).
+    StandbyCurrent,
+    u8,
+    7
+);
+impl StandbyCurrent {
+    pub fn new(current: u8) -> Self {
+        StandbyCurrent(current)
+    }
+}
+impl Tmcm1160AxisParameter for StandbyCurrent {}
+impl ReadableTmcm1160AxisParameter for StandbyCurrent {}
+impl WriteableTmcm1160AxisParameter for StandbyCurrent {}
+
+axis_param_rw!(
+    /// Reference search speed
+    ///
+    /// This value specifes the speed for roughly searching the reference switch (0..2047).
+    ReferenceSearchSpeed,
+    u32,
+    194,
+    0..=MAX_VELOCITY
+);
+impl ReferenceSearchSpeed {
+    pub fn new(speed: u32) -> Self {
+        assert!(Self::is_valid(speed));
+        ReferenceSearchSpeed(speed)
+    }
+}
+impl Tmcm1160AxisParameter for ReferenceSearchSpeed {}
+impl ReadableTmcm1160AxisParameter for ReferenceSearchSpeed {}
+impl WriteableTmcm1160AxisParameter for ReferenceSearchSpeed {}
+
+axis_param_rw!(
+    /// Reference switch speed
+    ///
+    /// This parameter specifes the speed for searching the switching point (0..2047).
+    /// It should be slower than parameter 194.
+    ReferenceSwitchSpeed,
+    u32,
+    195,
+    0..=MAX_VELOCITY
+);
+impl ReferenceSwitchSpeed {
+    pub fn new(speed: u32) -> Self {
+        assert!(Self::is_valid(speed));
+        ReferenceSwitchSpeed(speed)
+    }
+}
+impl Tmcm1160AxisParameter for ReferenceSwitchSpeed {}
+impl ReadableTmcm1160AxisParameter for ReferenceSwitchSpeed {}
+impl WriteableTmcm1160AxisParameter for ReferenceSwitchSpeed {}
+
+impl Tmcm1160AxisParameter for TargetPosition {}
+impl ReadableTmcm1160AxisParameter for TargetPosition {}
+impl WriteableTmcm1160AxisParameter for TargetPosition {}
+
+impl Tmcm1160AxisParameter for ActualPosition {}
+impl ReadableTmcm1160AxisParameter for ActualPosition {}
+impl WriteableTmcm1160AxisParameter for ActualPosition {}
+
+impl Tmcm1160AxisParameter for ActualSpeed {}
+impl ReadableTmcm1160AxisParameter for ActualSpeed {}
+
+impl Tmcm1160AxisParameter for PositionReachedFlag {}
+impl ReadableTmcm1160AxisParameter for PositionReachedFlag {}
+
+impl Tmcm1160AxisParameter for HomeSwitchState {}
+impl ReadableTmcm1160AxisParameter for HomeSwitchState {}
+
+impl Tmcm1160AxisParameter for RightLimitSwitchState {}
+impl ReadableTmcm1160AxisParameter for RightLimitSwitchState {}
+
+impl Tmcm1160AxisParameter for LeftLimitSwitchState {}
+impl ReadableTmcm1160AxisParameter for LeftLimitSwitchState {}
+
+impl Tmcm1160AxisParameter for RightLimitSwitchDisable {}
+impl ReadableTmcm1160AxisParameter for RightLimitSwitchDisable {}
+impl WriteableTmcm1160AxisParameter for RightLimitSwitchDisable {}
+
+impl Tmcm1160AxisParameter for LeftLimitSwitchDisable {}
+impl ReadableTmcm1160AxisParameter for LeftLimitSwitchDisable {}
+impl WriteableTmcm1160AxisParameter for LeftLimitSwitchDisable {}
+
+impl Tmcm1160AxisParameter for MicrostepResolution {}
+impl ReadableTmcm1160AxisParameter for MicrostepResolution {}
+impl WriteableTmcm1160AxisParameter for MicrostepResolution {}
+
+impl Tmcm1160AxisParameter for RampDivisor {}
+impl ReadableTmcm1160AxisParameter for RampDivisor {}
+impl WriteableTmcm1160AxisParameter for RampDivisor {}
+
+impl Tmcm1160AxisParameter for PulseDivisor {}
+impl ReadableTmcm1160AxisParameter for PulseDivisor {}
+impl WriteableTmcm1160AxisParameter for PulseDivisor {}
+
+impl Tmcm1160AxisParameter for ReferenceSearchMode {}
+impl ReadableTmcm1160AxisParameter for ReferenceSearchMode {}
+impl WriteableTmcm1160AxisParameter for ReferenceSearchMode {}
+
+impl Tmcm1160AxisParameter for EndSwitchDistance {}
+impl ReadableTmcm1160AxisParameter for EndSwitchDistance {}
+
+impl Tmcm1160AxisParameter for LastReferencePosition {}
+impl ReadableTmcm1160AxisParameter for LastReferencePosition {}
+
+impl Tmcm1160AxisParameter for BoostCurrent {}
+impl ReadableTmcm1160AxisParameter for BoostCurrent {}
+impl WriteableTmcm1160AxisParameter for BoostCurrent {}
+
+impl Tmcm1160AxisParameter for PowerDownDelay {}
+impl ReadableTmcm1160AxisParameter for PowerDownDelay {}
+impl WriteableTmcm1160AxisParameter for PowerDownDelay {}
+
+impl Tmcm1160AxisParameter for ChopperBlankTime {}
+impl ReadableTmcm1160AxisParameter for ChopperBlankTime {}
+impl WriteableTmcm1160AxisParameter for ChopperBlankTime {}
+
+impl Tmcm1160AxisParameter for ChopperMode {}
+impl ReadableTmcm1160AxisParameter for ChopperMode {}
+impl WriteableTmcm1160AxisParameter for ChopperMode {}
+
+impl Tmcm1160AxisParameter for HysteresisDecrement {}
+impl ReadableTmcm1160AxisParameter for HysteresisDecrement {}
+impl WriteableTmcm1160AxisParameter for HysteresisDecrement {}
+
+impl Tmcm1160AxisParameter for HysteresisEnd {}
+impl ReadableTmcm1160AxisParameter for HysteresisEnd {}
+impl WriteableTmcm1160AxisParameter for HysteresisEnd {}
+
+impl Tmcm1160AxisParameter for HysteresisStart {}
+impl ReadableTmcm1160AxisParameter for HysteresisStart {}
+impl WriteableTmcm1160AxisParameter for HysteresisStart {}
+
+impl Tmcm1160AxisParameter for ChopperOffTime {}
+impl ReadableTmcm1160AxisParameter for ChopperOffTime {}
+impl WriteableTmcm1160AxisParameter for ChopperOffTime {}
+
+impl Tmcm1160AxisParameter for SmartEnergyCurrentMinimum {}
+impl ReadableTmcm1160AxisParameter for SmartEnergyCurrentMinimum {}
+impl WriteableTmcm1160AxisParameter for SmartEnergyCurrentMinimum {}
+
+impl Tmcm1160AxisParameter for CurrentDownStep {}
+impl ReadableTmcm1160AxisParameter for CurrentDownStep {}
+impl WriteableTmcm1160AxisParameter for CurrentDownStep {}
+
+impl Tmcm1160AxisParameter for SmartEnergyHysteresis {}
+impl ReadableTmcm1160AxisParameter for SmartEnergyHysteresis {}
+impl WriteableTmcm1160AxisParameter for SmartEnergyHysteresis {}
+
+impl Tmcm1160AxisParameter for CurrentUpStep {}
+impl ReadableTmcm1160AxisParameter for CurrentUpStep {}
+impl WriteableTmcm1160AxisParameter for CurrentUpStep {}
+
+impl Tmcm1160AxisParameter for SmartEnergyHysteresisStart {}
+impl ReadableTmcm1160AxisParameter for SmartEnergyHysteresisStart {}
+impl WriteableTmcm1160AxisParameter for SmartEnergyHysteresisStart {}
+
+impl Tmcm1160AxisParameter for StallGuard2FilterEnable {}
+impl ReadableTmcm1160AxisParameter for StallGuard2FilterEnable {}
+impl WriteableTmcm1160AxisParameter for StallGuard2FilterEnable {}
+
+impl Tmcm1160AxisParameter for StallGuard2Threshold {}
+impl ReadableTmcm1160AxisParameter for StallGuard2Threshold {}
+impl WriteableTmcm1160AxisParameter for StallGuard2Threshold {}
+
+impl Tmcm1160AxisParameter for SmartEnergyActualCurrent {}
+impl ReadableTmcm1160AxisParameter for SmartEnergyActualCurrent {}
+
+impl Tmcm1160AxisParameter for StopOnStall {}
+impl ReadableTmcm1160AxisParameter for StopOnStall {}
+impl WriteableTmcm1160AxisParameter for StopOnStall {}
+
+impl Tmcm1160AxisParameter for SmartEnergyThresholdSpeed {}
+impl ReadableTmcm1160AxisParameter for SmartEnergyThresholdSpeed {}
+impl WriteableTmcm1160AxisParameter for SmartEnergyThresholdSpeed {}
+
+impl Tmcm1160AxisParameter for SlowRunCurrent {}
+impl ReadableTmcm1160AxisParameter for SlowRunCurrent {}
+impl WriteableTmcm1160AxisParameter for SlowRunCurrent {}
+
+impl Tmcm1160AxisParameter for FreewheelingDelay {}
+impl ReadableTmcm1160AxisParameter for FreewheelingDelay {}
+impl WriteableTmcm1160AxisParameter for FreewheelingDelay {}
+
+impl Tmcm1160AxisParameter for ActualLoadValue {}
+impl ReadableTmcm1160AxisParameter for ActualLoadValue {}
+
+impl Tmcm1160AxisParameter for DriverErrorFlags {}
+impl ReadableTmcm1160AxisParameter for DriverErrorFlags {}
+
+impl Tmcm1160AxisParameter for EncoderPosition {}
+impl ReadableTmcm1160AxisParameter for EncoderPosition {}
+impl WriteableTmcm1160AxisParameter for EncoderPosition {}
+
+impl Tmcm1160AxisParameter for EncoderResolution {}
+impl ReadableTmcm1160AxisParameter for EncoderResolution {}
+impl WriteableTmcm1160AxisParameter for EncoderResolution {}
+
+impl Tmcm1160AxisParameter for MaximumEncoderDeviation {}
+impl ReadableTmcm1160AxisParameter for MaximumEncoderDeviation {}
+impl WriteableTmcm1160AxisParameter for MaximumEncoderDeviation {}
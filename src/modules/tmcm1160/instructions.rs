@@ -0,0 +1,27 @@
+//! All instructions available for TMCM-1160 modules.
+//!
+//! The TMCM-1160 supports the complete TMCL instruction set of `modules::tmcm`, so the
+//! instructions are the same for all per-product modules.
+
+pub use instructions::{
+    ApplicationStatus, EnterDownloadMode, ErrorFlag, ExitDownloadMode, FirmwareVersion,
+    GetApplicationStatus, GetFirmwareVersion, GetFirmwareVersionAscii, InterruptVector,
+    JumpCondition, MoveOperation, ProgramMemoryPart, ReadProgramMemory, ReferenceSearchAction,
+    ResetApplication, RestoreFactoryDefaults, RunApplication, StepApplication, StopApplication,
+    WaitEvent, AAP, ACO, AGP, CALC, CALCX, CCO, CLE, COMP, CSUB, DI, EI, GAP, GCO, GGP, GIO, JA,
+    JC, MST, MVP, RETI, RFS, ROL, ROR, RSAP, RSGP, RSUB, SAP, SCO, SGP, SIO, STAP, STGP, STOP,
+    VECT, WAIT,
+};
+
+use modules::tmcm1160::{
+    ReadableTmcm1160AxisParameter, ReadableTmcm1160GlobalParameter, Tmcm1160Instruction,
+    WriteableTmcm1160AxisParameter, WriteableTmcm1160GlobalParameter,
+};
+
+product_instructions!(
+    Tmcm1160Instruction,
+    ReadableTmcm1160AxisParameter,
+    WriteableTmcm1160AxisParameter,
+    ReadableTmcm1160GlobalParameter,
+    WriteableTmcm1160GlobalParameter
+);
@@ -0,0 +1,45 @@
+//! A `TMCM` type usable only with TMCM-1160 modules.
+//!
+//! The TMCM-1160 is a single axis module based on the TMC429 motion controller and the TMC262
+//! driver for up to 2.8A RMS. Only the axis and global parameters the module actually has are
+//! available, with their valid ranges.
+
+pub mod axis_parameters;
+pub mod global_parameters;
+pub mod instructions;
+
+use AxisParameter;
+use GlobalParameter;
+use Instruction;
+use ReadableAxisParameter;
+use ReadableGlobalParameter;
+use WriteableAxisParameter;
+use WriteableGlobalParameter;
+
+product_module!(
+    /// This type represents a TMCM-1160 module.
+    Tmcm1160Module,
+    Tmcm1160Instruction,
+    1
+);
+
+/// An `Instruction` useable with TMCM-1160 modules.
+pub trait Tmcm1160Instruction: Instruction {}
+
+/// An `AxisParameter` useable with TMCM-1160 modules.
+pub trait Tmcm1160AxisParameter: AxisParameter {}
+
+/// A `ReadableAxisParameter` useable with TMCM-1160 modules.
+pub trait ReadableTmcm1160AxisParameter: ReadableAxisParameter {}
+
+/// A `WriteableAxisParameter` useable with TMCM-1160 modules.
+pub trait WriteableTmcm1160AxisParameter: WriteableAxisParameter {}
+
+/// A `GlobalParameter` useable with TMCM-1160 modules.
+pub trait Tmcm1160GlobalParameter: GlobalParameter {}
+
+/// A `ReadableGlobalParameter` useable with TMCM-1160 modules.
+pub trait ReadableTmcm1160GlobalParameter: ReadableGlobalParameter {}
+
+/// A `WriteableGlobalParameter` useable with TMCM-1160 modules.
+pub trait WriteableTmcm1160GlobalParameter: WriteableGlobalParameter {}
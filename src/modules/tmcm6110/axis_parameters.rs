@@ -0,0 +1,305 @@
+//! All axis parameters useable with TMCM-6110 modules.
+//!
+//! Velocities and accelerations are 11 bit values of the TMC429 motion controller (0..2047).
+//! Parameters without module specific ranges are shared with `modules::tmcm::axis_parameters`.
+
+pub use modules::tmcm::axis_parameters::{
+    ActualLoadValue, ActualPosition, ActualSpeed, BoostCurrent, ChopperBlankTime, ChopperMode,
+    ChopperOffTime, CurrentDownStep, CurrentUpStep, DriverErrorFlags, EndSwitchDistance,
+    FreewheelingDelay, HomeSearchMode, HomeSwitchState, HysteresisDecrement, HysteresisEnd,
+    HysteresisStart, LastReferencePosition, LeftLimitSwitchDisable, LeftLimitSwitchState,
+    MicrostepResolution, PositionReachedFlag, PowerDownDelay, PulseDivisor, RampDivisor,
+    ReferenceSearchMode, RightLimitSwitchDisable, RightLimitSwitchState, SearchMode,
+    SlowRunCurrent, SmartEnergyActualCurrent, SmartEnergyCurrentMinimum, SmartEnergyHysteresis,
+    SmartEnergyHysteresisStart, SmartEnergyThresholdSpeed, StallGuard2FilterEnable,
+    StallGuard2Threshold, StopOnStall, TargetPosition, Vsense,
+};
+
+use AxisParameter;
+use ReadableAxisParameter;
+use Return;
+use WriteableAxisParameter;
+
+use modules::tmcm6110::{
+    ReadableTmcm6110AxisParameter, Tmcm6110AxisParameter, WriteableTmcm6110AxisParameter,
+};
+
+/// The highest velocity of the module.
+const MAX_VELOCITY: u32 = 2047;
+
+/// The highest acceleration of the module.
+const MAX_ACCELERATION: u32 = 2047;
+
+axis_param_rw!(
+    /// The target rotation speed.
+    ///
+    /// The desired speed in velocity mode (-2047..2047). Not valid in position mode.
+    TargetSpeed,
+    i32,
+    2,
+    -(MAX_VELOCITY as i32)..=MAX_VELOCITY as i32
+);
+impl TargetSpeed {
+    pub fn new(speed: i32) -> Self {
+        assert!(Self::is_valid(speed));
+        TargetSpeed(speed)
+    }
+}
+impl Tmcm6110AxisParameter for TargetSpeed {}
+impl ReadableTmcm6110AxisParameter for TargetSpeed {}
+impl WriteableTmcm6110AxisParameter for TargetSpeed {}
+
+axis_param_rw!(
+    /// The maximum positioning speed.
+    ///
+    /// Adjust the pulse divisor (no. 154), if the speed value is very low (<50) or above the
+    /// upper limit (2047).
+    MaximumPositioningSpeed,
+    u32,
+    4,
+    0..=MAX_VELOCITY
+);
+impl MaximumPositioningSpeed {
+    pub fn new(speed: u32) -> Self {
+        assert!(Self::is_valid(speed));
+        MaximumPositioningSpeed(speed)
+    }
+}
+impl Tmcm6110AxisParameter for MaximumPositioningSpeed {}
+impl ReadableTmcm6110AxisParameter for MaximumPositioningSpeed {}
+impl WriteableTmcm6110AxisParameter for MaximumPositioningSpeed {}
+
+axis_param_rw!(
+    /// The maximum acceleration.
+    ///
+    /// Maximum acceleration during ramp-up and maximum deceleration during ramp-down (0..2047).
+    MaximumAcceleration,
+    u32,
+    5,
+    0..=MAX_ACCELERATION
+);
+impl MaximumAcceleration {
+    pub fn new(acc: u32) -> Self {
+        assert!(Self::is_valid(acc));
+        MaximumAcceleration(acc)
+    }
+}
+impl Tmcm6110AxisParameter for MaximumAcceleration {}
+impl ReadableTmcm6110AxisParameter for MaximumAcceleration {}
+impl WriteableTmcm6110AxisParameter for MaximumAcceleration {}
+
+axis_param_rw!(
+    /// The absolute maximum current
+    ///
+    /// The most important motor setting, since too high values might cause motor damage!
+    /// 255 means 100% of the maximum current of the module.
+    AbsoluteMaxCurrent,
+    u8,
+    6
+);
+impl AbsoluteMaxCurrent {
+    pub fn new(current: u8) -> Self {
+        AbsoluteMaxCurrent(current)
+    }
+}
+impl Tmcm6110AxisParameter for AbsoluteMaxCurrent {}
+impl ReadableTmcm6110AxisParameter for AbsoluteMaxCurrent {}
+impl WriteableTmcm6110AxisParameter for AbsoluteMaxCurrent {}
+
+axis_param_rw!(
+    /// The current used when the motor is not running.
+    ///
+    /// Same scaling as the absolute maximum current. Please see also parameter 214
+    /// (PowerDownDelay).
+    StandbyCurrent,
+    u8,
+    7
+);
+impl StandbyCurrent {
+    pub fn new(current: u8) -> Self {
+        StandbyCurrent(current)
+    }
+}
+impl Tmcm6110AxisParameter for StandbyCurrent {}
+impl ReadableTmcm6110AxisParameter for StandbyCurrent {}
+impl WriteableTmcm6110AxisParameter for StandbyCurrent {}
+
+axis_param_rw!(
+    /// Reference search speed
+    ///
+    /// This value specifes the speed for roughly searching the reference switch (0..2047).
+    ReferenceSearchSpeed,
+    u32,
+    194,
+    0..=MAX_VELOCITY
+);
+impl ReferenceSearchSpeed {
+    pub fn new(speed: u32) -> Self {
+        assert!(Self::is_valid(speed));
+        ReferenceSearchSpeed(speed)
+    }
+}
+impl Tmcm6110AxisParameter for ReferenceSearchSpeed {}
+impl ReadableTmcm6110AxisParameter for ReferenceSearchSpeed {}
+impl WriteableTmcm6110AxisParameter for ReferenceSearchSpeed {}
+
+axis_param_rw!(
+    /// Reference switch speed
+    ///
+    /// This parameter specifes the speed for searching the switching point (0..2047).
+    /// It should be slower than parameter 194.
+    ReferenceSwitchSpeed,
+    u32,
+    195,
+    0..=MAX_VELOCITY
+);
+impl ReferenceSwitchSpeed {
+    pub fn new(speed: u32) -> Self {
+        assert!(Self::is_valid(speed));
+        ReferenceSwitchSpeed(speed)
+    }
+}
+impl Tmcm6110AxisParameter for ReferenceSwitchSpeed {}
+impl ReadableTmcm6110AxisParameter for ReferenceSwitchSpeed {}
+impl WriteableTmcm6110AxisParameter for ReferenceSwitchSpeed {}
+
+impl Tmcm6110AxisParameter for TargetPosition {}
+impl ReadableTmcm6110AxisParameter for TargetPosition {}
+impl WriteableTmcm6110AxisParameter for TargetPosition {}
+
+impl Tmcm6110AxisParameter for ActualPosition {}
+impl ReadableTmcm6110AxisParameter for ActualPosition {}
+impl WriteableTmcm6110AxisParameter for ActualPosition {}
+
+impl Tmcm6110AxisParameter for ActualSpeed {}
+impl ReadableTmcm6110AxisParameter for ActualSpeed {}
+
+impl Tmcm6110AxisParameter for PositionReachedFlag {}
+impl ReadableTmcm6110AxisParameter for PositionReachedFlag {}
+
+impl Tmcm6110AxisParameter for HomeSwitchState {}
+impl ReadableTmcm6110AxisParameter for HomeSwitchState {}
+
+impl Tmcm6110AxisParameter for RightLimitSwitchState {}
+impl ReadableTmcm6110AxisParameter for RightLimitSwitchState {}
+
+impl Tmcm6110AxisParameter for LeftLimitSwitchState {}
+impl ReadableTmcm6110AxisParameter for LeftLimitSwitchState {}
+
+impl Tmcm6110AxisParameter for RightLimitSwitchDisable {}
+impl ReadableTmcm6110AxisParameter for RightLimitSwitchDisable {}
+impl WriteableTmcm6110AxisParameter for RightLimitSwitchDisable {}
+
+impl Tmcm6110AxisParameter for LeftLimitSwitchDisable {}
+impl ReadableTmcm6110AxisParameter for LeftLimitSwitchDisable {}
+impl WriteableTmcm6110AxisParameter for LeftLimitSwitchDisable {}
+
+impl Tmcm6110AxisParameter for MicrostepResolution {}
+impl ReadableTmcm6110AxisParameter for MicrostepResolution {}
+impl WriteableTmcm6110AxisParameter for MicrostepResolution {}
+
+impl Tmcm6110AxisParameter for RampDivisor {}
+impl ReadableTmcm6110AxisParameter for RampDivisor {}
+impl WriteableTmcm6110AxisParameter for RampDivisor {}
+
+impl Tmcm6110AxisParameter for PulseDivisor {}
+impl ReadableTmcm6110AxisParameter for PulseDivisor {}
+impl WriteableTmcm6110AxisParameter for PulseDivisor {}
+
+impl Tmcm6110AxisParameter for Vsense {}
+impl ReadableTmcm6110AxisParameter for Vsense {}
+
+impl Tmcm6110AxisParameter for ReferenceSearchMode {}
+impl ReadableTmcm6110AxisParameter for ReferenceSearchMode {}
+impl WriteableTmcm6110AxisParameter for ReferenceSearchMode {}
+
+impl Tmcm6110AxisParameter for EndSwitchDistance {}
+impl ReadableTmcm6110AxisParameter for EndSwitchDistance {}
+
+impl Tmcm6110AxisParameter for LastReferencePosition {}
+impl ReadableTmcm6110AxisParameter for LastReferencePosition {}
+
+impl Tmcm6110AxisParameter for BoostCurrent {}
+impl ReadableTmcm6110AxisParameter for BoostCurrent {}
+impl WriteableTmcm6110AxisParameter for BoostCurrent {}
+
+impl Tmcm6110AxisParameter for PowerDownDelay {}
+impl ReadableTmcm6110AxisParameter for PowerDownDelay {}
+impl WriteableTmcm6110AxisParameter for PowerDownDelay {}
+
+impl Tmcm6110AxisParameter for ChopperBlankTime {}
+impl ReadableTmcm6110AxisParameter for ChopperBlankTime {}
+impl WriteableTmcm6110AxisParameter for ChopperBlankTime {}
+
+impl Tmcm6110AxisParameter for ChopperMode {}
+impl ReadableTmcm6110AxisParameter for ChopperMode {}
+impl WriteableTmcm6110AxisParameter for ChopperMode {}
+
+impl Tmcm6110AxisParameter for HysteresisDecrement {}
+impl ReadableTmcm6110AxisParameter for HysteresisDecrement {}
+impl WriteableTmcm6110AxisParameter for HysteresisDecrement {}
+
+impl Tmcm6110AxisParameter for HysteresisEnd {}
+impl ReadableTmcm6110AxisParameter for HysteresisEnd {}
+impl WriteableTmcm6110AxisParameter for HysteresisEnd {}
+
+impl Tmcm6110AxisParameter for HysteresisStart {}
+impl ReadableTmcm6110AxisParameter for HysteresisStart {}
+impl WriteableTmcm6110AxisParameter for HysteresisStart {}
+
+impl Tmcm6110AxisParameter for ChopperOffTime {}
+impl ReadableTmcm6110AxisParameter for ChopperOffTime {}
+impl WriteableTmcm6110AxisParameter for ChopperOffTime {}
+
+impl Tmcm6110AxisParameter for SmartEnergyCurrentMinimum {}
+impl ReadableTmcm6110AxisParameter for SmartEnergyCurrentMinimum {}
+impl WriteableTmcm6110AxisParameter for SmartEnergyCurrentMinimum {}
+
+impl Tmcm6110AxisParameter for CurrentDownStep {}
+impl ReadableTmcm6110AxisParameter for CurrentDownStep {}
+impl WriteableTmcm6110AxisParameter for CurrentDownStep {}
+
+impl Tmcm6110AxisParameter for SmartEnergyHysteresis {}
+impl ReadableTmcm6110AxisParameter for SmartEnergyHysteresis {}
+impl WriteableTmcm6110AxisParameter for SmartEnergyHysteresis {}
+
+impl Tmcm6110AxisParameter for CurrentUpStep {}
+impl ReadableTmcm6110AxisParameter for CurrentUpStep {}
+impl WriteableTmcm6110AxisParameter for CurrentUpStep {}
+
+impl Tmcm6110AxisParameter for SmartEnergyHysteresisStart {}
+impl ReadableTmcm6110AxisParameter for SmartEnergyHysteresisStart {}
+impl WriteableTmcm6110AxisParameter for SmartEnergyHysteresisStart {}
+
+impl Tmcm6110AxisParameter for StallGuard2FilterEnable {}
+impl ReadableTmcm6110AxisParameter for StallGuard2FilterEnable {}
+impl WriteableTmcm6110AxisParameter for StallGuard2FilterEnable {}
+
+impl Tmcm6110AxisParameter for StallGuard2Threshold {}
+impl ReadableTmcm6110AxisParameter for StallGuard2Threshold {}
+impl WriteableTmcm6110AxisParameter for StallGuard2Threshold {}
+
+impl Tmcm6110AxisParameter for SmartEnergyActualCurrent {}
+impl ReadableTmcm6110AxisParameter for SmartEnergyActualCurrent {}
+
+impl Tmcm6110AxisParameter for StopOnStall {}
+impl ReadableTmcm6110AxisParameter for StopOnStall {}
+impl WriteableTmcm6110AxisParameter for StopOnStall {}
+
+impl Tmcm6110AxisParameter for SmartEnergyThresholdSpeed {}
+impl ReadableTmcm6110AxisParameter for SmartEnergyThresholdSpeed {}
+impl WriteableTmcm6110AxisParameter for SmartEnergyThresholdSpeed {}
+
+impl Tmcm6110AxisParameter for SlowRunCurrent {}
+impl ReadableTmcm6110AxisParameter for SlowRunCurrent {}
+impl WriteableTmcm6110AxisParameter for SlowRunCurrent {}
+
+impl Tmcm6110AxisParameter for FreewheelingDelay {}
+impl ReadableTmcm6110AxisParameter for FreewheelingDelay {}
+impl WriteableTmcm6110AxisParameter for FreewheelingDelay {}
+
+impl Tmcm6110AxisParameter for ActualLoadValue {}
+impl ReadableTmcm6110AxisParameter for ActualLoadValue {}
+
+impl Tmcm6110AxisParameter for DriverErrorFlags {}
+impl ReadableTmcm6110AxisParameter for DriverErrorFlags {}
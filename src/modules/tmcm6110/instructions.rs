@@ -0,0 +1,27 @@
+//! All instructions available for TMCM-6110 modules.
+//!
+//! The TMCM-6110 supports the complete TMCL instruction set of `modules::tmcm`, so the
+//! instructions are the same for all per-product modules.
+
+pub use instructions::{
+    ApplicationStatus, EnterDownloadMode, ErrorFlag, ExitDownloadMode, FirmwareVersion,
+    GetApplicationStatus, GetFirmwareVersion, GetFirmwareVersionAscii, InterruptVector,
+    JumpCondition, MoveOperation, ProgramMemoryPart, ReadProgramMemory, ReferenceSearchAction,
+    ResetApplication, RestoreFactoryDefaults, RunApplication, StepApplication, StopApplication,
+    WaitEvent, AAP, ACO, AGP, CALC, CALCX, CCO, CLE, COMP, CSUB, DI, EI, GAP, GCO, GGP, GIO, JA,
+    JC, MST, MVP, RETI, RFS, ROL, ROR, RSAP, RSGP, RSUB, SAP, SCO, SGP, SIO, STAP, STGP, STOP,
+    VECT, WAIT,
+};
+
+use modules::tmcm6110::{
+    ReadableTmcm6110AxisParameter, ReadableTmcm6110GlobalParameter, Tmcm6110Instruction,
+    WriteableTmcm6110AxisParameter, WriteableTmcm6110GlobalParameter,
+};
+
+product_instructions!(
+    Tmcm6110Instruction,
+    ReadableTmcm6110AxisParameter,
+    WriteableTmcm6110AxisParameter,
+    ReadableTmcm6110GlobalParameter,
+    WriteableTmcm6110GlobalParameter
+);
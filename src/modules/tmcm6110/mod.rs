@@ -0,0 +1,114 @@
+//! A `TMCM` type usable only with TMCM-6110 modules.
+//!
+//! The TMCM-6110 is a six axis module based on two TMC429 motion controllers and TMC262
+//! drivers. Instructions addressing a motor above 5 are rejected with `Error::InvalidMotor`.
+//! Only the axis and global parameters the module actually has are available, with
+//! their valid ranges.
+
+pub mod axis_parameters;
+pub mod global_parameters;
+pub mod instructions;
+
+use AxisParameter;
+use GlobalParameter;
+use Instruction;
+use ReadableAxisParameter;
+use ReadableGlobalParameter;
+use WriteableAxisParameter;
+use WriteableGlobalParameter;
+
+product_module!(
+    /// This type represents a TMCM-6110 module.
+    Tmcm6110Module,
+    Tmcm6110Instruction,
+    6
+);
+
+/// An `Instruction` useable with TMCM-6110 modules.
+pub trait Tmcm6110Instruction: Instruction {}
+
+/// An `AxisParameter` useable with TMCM-6110 modules.
+pub trait Tmcm6110AxisParameter: AxisParameter {}
+
+/// A `ReadableAxisParameter` useable with TMCM-6110 modules.
+pub trait ReadableTmcm6110AxisParameter: ReadableAxisParameter {}
+
+/// A `WriteableAxisParameter` useable with TMCM-6110 modules.
+pub trait WriteableTmcm6110AxisParameter: WriteableAxisParameter {}
+
+/// A `GlobalParameter` useable with TMCM-6110 modules.
+pub trait Tmcm6110GlobalParameter: GlobalParameter {}
+
+/// A `ReadableGlobalParameter` useable with TMCM-6110 modules.
+pub trait ReadableTmcm6110GlobalParameter: ReadableGlobalParameter {}
+
+/// A `WriteableGlobalParameter` useable with TMCM-6110 modules.
+pub trait WriteableTmcm6110GlobalParameter: WriteableGlobalParameter {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use lib::cell::RefCell;
+
+    use modules::tmcm6110::axis_parameters::TargetPosition;
+    use modules::tmcm6110::instructions::{GAP, MST, MVP, ROR};
+    use Command;
+    use Error;
+    use Interface;
+    use OkStatus;
+    use Reply;
+    use Status;
+
+    /// Replies to every command, counting the transmitted commands.
+    struct MockInterface {
+        transmitted: u32,
+        reply: Option<Reply>,
+    }
+
+    impl Interface for MockInterface {
+        type Error = ();
+
+        fn transmit_command<T: Instruction>(&mut self, _command: &Command<T>) -> Result<(), ()> {
+            self.transmitted += 1;
+            self.reply = Some(Reply::new(
+                2,
+                1,
+                Status::Ok(OkStatus::Ok),
+                T::INSTRUCTION_NUMBER,
+                [0; 4],
+            ));
+            Ok(())
+        }
+
+        fn receive_reply(&mut self) -> Result<Reply, ()> {
+            self.reply.take().ok_or(())
+        }
+    }
+
+    #[test]
+    fn motor_numbers() {
+        let interface = RefCell::new(MockInterface {
+            transmitted: 0,
+            reply: None,
+        });
+        let module = Tmcm6110Module::new(&interface, 1);
+
+        assert_eq!(module.write_command(ROR::new(5, 100)), Ok(()));
+        assert_eq!(
+            module
+                .write_command(GAP::<TargetPosition>::new(5))
+                .map(i32::from),
+            Ok(0)
+        );
+        assert_eq!(
+            module.write_command(MST::new(6)),
+            Err(Error::InvalidMotor(6))
+        );
+        assert_eq!(
+            module.write_command(MVP::interpolated(0b10_0001, 0)),
+            Ok(())
+        );
+        assert_eq!(interface.borrow().transmitted, 3);
+    }
+}
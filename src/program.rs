@@ -0,0 +1,387 @@
+//! TMCL programs stored in the program memory of a module.
+//!
+//! A program is downloaded by putting the module in download mode, storing the instructions one
+//! by one and leaving download mode again. Both `GenericModule` and `TmcmModule` provide
+//! `download` for this, and `verify_program` for reading the program memory back afterwards.
+//!
+//! ```no_run
+//! extern crate tmcl;
+//!
+//! use std::cell::RefCell;
+//! use tmcl::modules::tmcm::instructions::*;
+//! use tmcl::modules::tmcm::TmcmModule as Module;
+//!
+//! # use tmcl::Interface;
+//! # use tmcl::Instruction;
+//! # use tmcl::Command;
+//! # use tmcl::Reply;
+//! #
+//! # struct MyInterface();
+//! # #[derive(Debug)]
+//! # struct MyInterfaceError();
+//! #
+//! # impl MyInterface { fn new() -> Self {unimplemented!()} }
+//! #
+//! # impl Interface for MyInterface {
+//!    # type Error = MyInterfaceError;
+//!    # fn transmit_command<T: Instruction>(&mut self, command: &Command<T>) -> Result<(), Self::Error> {
+//!        # unimplemented!()
+//!    # }
+//!    # fn receive_reply(&mut self) -> Result<Reply, Self::Error> {
+//!        # unimplemented!()
+//!    # }
+//! # }
+//! #
+//! fn main() -> Result<(), tmcl::Error<MyInterfaceError>> {
+//!     let interface = RefCell::new(MyInterface::new());
+//!     let module = Module::new(&interface, 1);
+//!
+//!     let program = module.download(0, |module| {
+//!         Ok([
+//!             module.store_instruction(ROR::new(0, 500))?,
+//!             module.store_instruction(WAIT::new(WaitEvent::Ticks, 100))?,
+//!             module.store_instruction(MST::new(0))?,
+//!             module.store_instruction(STOP::new())?,
+//!         ])
+//!     })?;
+//!     module.verify_program(0, &program)?;
+//!
+//!     module.write_command(RunApplication::from_address(0))?;
+//!     Ok(())
+//! }
+//! ```
+
+use interior_mut::InteriorMut;
+
+use calculate_checksum;
+use instructions::{EnterDownloadMode, ExitDownloadMode, ProgramMemoryPart, ReadProgramMemory};
+use modules::write_command;
+use Command;
+use Error;
+use Instruction;
+use Interface;
+use OkStatus;
+use ParseError;
+use Status;
+
+/// A single instruction as stored in the TMCL program memory.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct ProgramWord {
+    command_number: u8,
+    type_number: u8,
+    motor_bank_number: u8,
+    operand: [u8; 4],
+}
+
+impl ProgramWord {
+    pub fn new(
+        command_number: u8,
+        type_number: u8,
+        motor_bank_number: u8,
+        operand: [u8; 4],
+    ) -> Self {
+        ProgramWord {
+            command_number,
+            type_number,
+            motor_bank_number,
+            operand,
+        }
+    }
+
+    /// The program word storing `instruction`
+    pub fn from_instruction<T: Instruction>(instruction: &T) -> Self {
+        ProgramWord {
+            command_number: T::INSTRUCTION_NUMBER,
+            type_number: instruction.type_number(),
+            motor_bank_number: instruction.motor_bank_number(),
+            operand: instruction.operand(),
+        }
+    }
+
+    /// Returns the command number
+    pub fn command_number(&self) -> u8 {
+        self.command_number
+    }
+
+    /// Returns the type number
+    pub fn type_number(&self) -> u8 {
+        self.type_number
+    }
+
+    /// Returns the motor/bank number
+    pub fn motor_bank_number(&self) -> u8 {
+        self.motor_bank_number
+    }
+
+    /// Returns the operand as `[operand[0], operand[1], operand[2], operand[3]]`
+    pub fn operand(&self) -> [u8; 4] {
+        self.operand
+    }
+
+    /// Serialize into the 8 byte program word format
+    ///
+    /// This is the same format as used by `Command::serialize_i2c`:
+    /// `[CMD_N, TYPE_N, MOTOR_N, VALUE3, VALUE2, VALUE1, VALUE0, CHECKSUM]`
+    pub fn to_bytes(&self) -> [u8; 8] {
+        let mut data = [
+            self.command_number,
+            self.type_number,
+            self.motor_bank_number,
+            self.operand[3],
+            self.operand[2],
+            self.operand[1],
+            self.operand[0],
+            0,
+        ];
+        data[7] = calculate_checksum(&data[..7]);
+        data
+    }
+
+    /// Deserialize from the 8 byte program word format
+    pub fn from_bytes(data: &[u8]) -> Result<Self, ParseError> {
+        if data.len() < 8 {
+            return Err(ParseError::ShortFrame);
+        }
+        if calculate_checksum(&data[..7]) != data[7] {
+            return Err(ParseError::WrongChecksum);
+        }
+        Ok(ProgramWord::new(
+            data[0],
+            data[1],
+            data[2],
+            [data[6], data[5], data[4], data[3]],
+        ))
+    }
+}
+
+/// Put the module at `address` in download mode, call `f` and leave download mode again.
+///
+/// The interface is only borrowed while entering and leaving download mode, so `f` can store the
+/// program through the module. Download mode is left even if `f` returns an error.
+pub(crate) fn download<'a, IF, Cell, F, R>(
+    interface: &'a Cell,
+    address: u8,
+    start_address: u32,
+    f: F,
+) -> Result<R, Error<IF::Error>>
+where
+    IF: Interface + 'a,
+    Cell: InteriorMut<'a, IF>,
+    F: FnOnce() -> Result<R, Error<IF::Error>>,
+{
+    write_command(
+        &mut *interface
+            .borrow_int_mut()
+            .or(Err(Error::InterfaceUnavailable))?,
+        address,
+        EnterDownloadMode::new(start_address),
+    )?;
+    let result = f();
+    let exit = interface
+        .borrow_int_mut()
+        .or(Err(Error::InterfaceUnavailable))
+        .and_then(|mut interface| write_command(&mut *interface, address, ExitDownloadMode::new()));
+    let result = result?;
+    exit?;
+    Ok(result)
+}
+
+/// Store `instruction` in the program memory of the module at `address`, which must be in
+/// download mode.
+pub(crate) fn store_instruction<IF: Interface, I: Instruction>(
+    interface: &mut IF,
+    address: u8,
+    instruction: I,
+) -> Result<ProgramWord, Error<IF::Error>> {
+    let word = ProgramWord::from_instruction(&instruction);
+    interface
+        .transmit_command(&Command::new(address, instruction))
+        .map_err(Error::InterfaceError)?;
+    let reply = interface.receive_reply().map_err(Error::InterfaceError)?;
+    match reply.status() {
+        Status::Ok(OkStatus::LoadedIntoEEPROM) => Ok(word),
+        Status::Ok(OkStatus::Ok) => Err(Error::NotLoadedIntoEEPROM),
+        Status::Err(e) => Err(e.into()),
+    }
+}
+
+/// Read back the program word at `program_address` of the module at `address`.
+pub(crate) fn read_program_word<IF: Interface>(
+    interface: &mut IF,
+    address: u8,
+    program_address: u32,
+) -> Result<ProgramWord, Error<IF::Error>> {
+    let command = write_command(
+        interface,
+        address,
+        ReadProgramMemory::new(program_address, ProgramMemoryPart::Command),
+    )?;
+    let value = write_command(
+        interface,
+        address,
+        ReadProgramMemory::new(program_address, ProgramMemoryPart::Value),
+    )?;
+    Ok(ProgramWord::new(command[0], command[1], command[2], value))
+}
+
+/// Compare the program memory of the module at `address` starting at `start_address` with
+/// `program`.
+pub(crate) fn verify_program<IF: Interface>(
+    interface: &mut IF,
+    address: u8,
+    start_address: u32,
+    program: &[ProgramWord],
+) -> Result<(), Error<IF::Error>> {
+    for (program_address, word) in (start_address..).zip(program) {
+        if read_program_word(interface, address, program_address)? != *word {
+            return Err(Error::VerificationFailed(program_address));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use lib::cell::RefCell;
+
+    use instructions::{JumpCondition, WaitEvent, JC, MST, ROR, STOP, WAIT};
+    use modules::generic::GenericModule;
+    use modules::tmcm::TmcmModule;
+    use Command;
+    use Error;
+    use Interface;
+    use OkStatus;
+    use Reply;
+    use Status;
+
+    /// Simulates the program memory of a module.
+    struct MockModule {
+        memory: [ProgramWord; 8],
+        download_address: Option<u32>,
+        reply: Option<Reply>,
+    }
+
+    impl MockModule {
+        fn new() -> Self {
+            MockModule {
+                memory: [ProgramWord::new(0, 0, 0, [0; 4]); 8],
+                download_address: None,
+                reply: None,
+            }
+        }
+    }
+
+    impl Interface for MockModule {
+        type Error = ();
+
+        fn transmit_command<T: Instruction>(&mut self, command: &Command<T>) -> Result<(), ()> {
+            let word = ProgramWord::from_bytes(&command.serialize_i2c()).unwrap();
+            let address = u32::from_le_bytes(word.operand()) as usize;
+            let mut status = OkStatus::Ok;
+            let mut operand = [0u8; 4];
+            match (self.download_address, word.command_number()) {
+                (_, 132) => self.download_address = Some(address as u32),
+                (_, 133) => self.download_address = None,
+                (Some(next), _) => {
+                    self.memory[next as usize] = word;
+                    self.download_address = Some(next + 1);
+                    status = OkStatus::LoadedIntoEEPROM;
+                }
+                (None, 134) => {
+                    let stored = self.memory[address];
+                    operand = match word.type_number() {
+                        0 => [
+                            stored.command_number(),
+                            stored.type_number(),
+                            stored.motor_bank_number(),
+                            0,
+                        ],
+                        _ => stored.operand(),
+                    };
+                }
+                (None, _) => (),
+            }
+            self.reply = Some(Reply::new(
+                2,
+                1,
+                Status::Ok(status),
+                T::INSTRUCTION_NUMBER,
+                operand,
+            ));
+            Ok(())
+        }
+
+        fn receive_reply(&mut self) -> Result<Reply, ()> {
+            self.reply.take().ok_or(())
+        }
+    }
+
+    #[test]
+    fn same_format_as_i2c() {
+        let word = ProgramWord::from_instruction(&JC::new(JumpCondition::NotZero, 17));
+        assert_eq!(
+            word.to_bytes(),
+            Command::new(1, JC::new(JumpCondition::NotZero, 17)).serialize_i2c()
+        );
+        assert_eq!(ProgramWord::from_bytes(&word.to_bytes()), Ok(word));
+    }
+
+    #[test]
+    fn wrong_checksum() {
+        assert_eq!(
+            ProgramWord::from_bytes(&[21, 1, 0, 0, 0, 0, 17, 0]),
+            Err(ParseError::WrongChecksum)
+        );
+    }
+
+    #[test]
+    fn download_and_verify() {
+        let interface = RefCell::new(MockModule::new());
+        let module = TmcmModule::new(&interface, 1);
+
+        let program = module
+            .download(3, |module| {
+                Ok([
+                    module.store_instruction(ROR::new(0, 500))?,
+                    module.store_instruction(WAIT::new(WaitEvent::Ticks, 100))?,
+                    module.store_instruction(MST::new(0))?,
+                    module.store_instruction(STOP::new())?,
+                ])
+            })
+            .unwrap();
+        assert_eq!(interface.borrow().download_address, None);
+        assert_eq!(&interface.borrow().memory[3..7], &program[..]);
+        assert_eq!(module.verify_program(3, &program), Ok(()));
+
+        interface.borrow_mut().memory[5] = ProgramWord::from_instruction(&MST::new(1));
+        assert_eq!(
+            module.verify_program(3, &program),
+            Err(Error::VerificationFailed(5))
+        );
+    }
+
+    #[test]
+    fn download_mode_left_on_error() {
+        let interface = RefCell::new(MockModule::new());
+        let module = GenericModule::new(&interface, 1);
+
+        let result: Result<(), _> = module.download(0, |module| {
+            module.store_instruction(STOP::new())?;
+            Err(Error::NotLoadedIntoEEPROM)
+        });
+        assert_eq!(result, Err(Error::NotLoadedIntoEEPROM));
+        assert_eq!(interface.borrow().download_address, None);
+    }
+
+    #[test]
+    fn not_in_download_mode() {
+        let interface = RefCell::new(MockModule::new());
+        let module = GenericModule::new(&interface, 1);
+        assert_eq!(
+            module.store_instruction(STOP::new()),
+            Err(Error::NotLoadedIntoEEPROM)
+        );
+    }
+}
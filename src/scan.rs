@@ -0,0 +1,237 @@
+use instructions::{FirmwareVersion, GetFirmwareVersion};
+use Command;
+use Error;
+use ReplyTimeout;
+use Return;
+use Status;
+
+/// A module that answered a `scan`.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct DiscoveredModule {
+    address: u8,
+    firmware_version: FirmwareVersion,
+}
+
+impl DiscoveredModule {
+    /// Returns the address the module answered at
+    pub fn address(&self) -> u8 {
+        self.address
+    }
+
+    /// Returns the module type, e.g. 1140 for a TMCM-1140
+    pub fn module_type(&self) -> u16 {
+        self.firmware_version.module_type()
+    }
+
+    /// Returns the module type and firmware revision
+    pub fn firmware_version(&self) -> FirmwareVersion {
+        self.firmware_version
+    }
+}
+
+/// Probe the module addresses in `addresses`, returning an iterator over the modules that answer.
+///
+/// Every address is probed with `GetFirmwareVersion`, waiting `probe_timeout` for the reply.
+/// The reply timeout of the interface is set to `probe_timeout` for the scan and restored
+/// afterwards. A probe that times out means that there is no module at that address. Other
+/// errors, and modules answering with an error status, are returned as errors. The interface
+/// must only accept replies to the last command from the probed module, as `SerialInterface` and
+/// `CanInterface` do.
+///
+/// On CAN the address is the CAN identifier the module listens to. Addresses are `u8`, so
+/// modules listening to a CAN identifier above 255 can't be probed.
+///
+/// ```
+/// # extern crate tmcl;
+/// use tmcl::{scan, DiscoveredModule, Error, ReplyTimeout};
+///
+/// fn list_modules<IF: ReplyTimeout>(
+///     interface: &mut IF,
+///     probe_timeout: IF::Timeout,
+/// ) -> Result<(), Error<IF::Error>> {
+///     for module in scan(interface, 1..=255, probe_timeout) {
+///         let module: DiscoveredModule = module?;
+///         let version = module.firmware_version();
+///         println!(
+///             "{}: TMCM-{} V{}.{}",
+///             module.address(),
+///             module.module_type(),
+///             version.major(),
+///             version.minor()
+///         );
+///     }
+///     Ok(())
+/// }
+/// # fn main() {}
+/// ```
+pub fn scan<'a, IF: ReplyTimeout, A: IntoIterator<Item = u8>>(
+    interface: &'a mut IF,
+    addresses: A,
+    probe_timeout: IF::Timeout,
+) -> Scan<'a, IF, A::IntoIter> {
+    Scan {
+        interface,
+        addresses: addresses.into_iter(),
+        probe_timeout: Some(probe_timeout),
+        previous_timeout: None,
+    }
+}
+
+/// Iterator returned by `scan`, probing one address at a time.
+///
+/// The reply timeout of the interface is restored when all addresses are probed, or when the
+/// `Scan` is dropped.
+#[derive(Debug)]
+pub struct Scan<'a, IF: ReplyTimeout + 'a, A> {
+    interface: &'a mut IF,
+    addresses: A,
+    probe_timeout: Option<IF::Timeout>,
+    previous_timeout: Option<IF::Timeout>,
+}
+
+impl<'a, IF: ReplyTimeout, A: Iterator<Item = u8>> Iterator for Scan<'a, IF, A> {
+    type Item = Result<DiscoveredModule, Error<IF::Error>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(probe_timeout) = self.probe_timeout.take() {
+            let previous_timeout = self.interface.reply_timeout();
+            if let Err(e) = self.interface.set_reply_timeout(probe_timeout) {
+                return Some(Err(Error::InterfaceError(e)));
+            }
+            self.previous_timeout = Some(previous_timeout);
+        }
+        for address in &mut self.addresses {
+            if let Err(e) = self
+                .interface
+                .transmit_command(&Command::new(address, GetFirmwareVersion::new()))
+            {
+                return Some(Err(Error::InterfaceError(e)));
+            }
+            let reply = match self.interface.receive_reply() {
+                Ok(reply) => reply,
+                Err(ref e) if IF::is_timeout(e) => continue,
+                Err(e) => return Some(Err(Error::InterfaceError(e))),
+            };
+            return Some(match reply.status() {
+                Status::Ok(_) => Ok(DiscoveredModule {
+                    address,
+                    firmware_version: FirmwareVersion::from_operand(reply.operand()),
+                }),
+                Status::Err(e) => Err(e.into()),
+            });
+        }
+        if let Some(previous_timeout) = self.previous_timeout.take() {
+            if let Err(e) = self.interface.set_reply_timeout(previous_timeout) {
+                return Some(Err(Error::InterfaceError(e)));
+            }
+        }
+        None
+    }
+}
+
+impl<'a, IF: ReplyTimeout, A> Drop for Scan<'a, IF, A> {
+    fn drop(&mut self) {
+        if let Some(previous_timeout) = self.previous_timeout.take() {
+            let _ = self.interface.set_reply_timeout(previous_timeout);
+        }
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+
+    use simulator::{SimulatedBus, SimulatedModule};
+    use Instruction;
+    use Interface;
+    use OkStatus;
+    use Reply;
+
+    #[test]
+    fn simulated_bus() {
+        let mut first = SimulatedModule::new(3, 1);
+        first.set_firmware_version(1140, 2, 11);
+        let mut second = SimulatedModule::new(12, 6);
+        second.set_firmware_version(6110, 1, 4);
+        let mut bus = SimulatedBus::new(vec![second, first]);
+
+        let modules = scan(&mut bus, 1..=20, ())
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(modules.len(), 2);
+        assert_eq!(modules[0].address(), 3);
+        assert_eq!(modules[0].module_type(), 1140);
+        assert_eq!(modules[0].firmware_version().minor(), 11);
+        assert_eq!(modules[1].address(), 12);
+        assert_eq!(
+            modules[1].firmware_version(),
+            FirmwareVersion::new(6110, 1, 4)
+        );
+    }
+
+    /// Answers every probe at address 5, and fails to receive at address 7.
+    #[derive(Debug)]
+    struct FailingInterface {
+        address: u8,
+        timeout: u32,
+    }
+
+    impl Interface for FailingInterface {
+        type Error = &'static str;
+
+        fn transmit_command<T: Instruction>(
+            &mut self,
+            command: &Command<T>,
+        ) -> Result<(), Self::Error> {
+            self.address = command.module_address();
+            Ok(())
+        }
+
+        fn receive_reply(&mut self) -> Result<Reply, Self::Error> {
+            match self.address {
+                5 => Ok(Reply::new(2, 5, Status::Ok(OkStatus::Ok), 136, [0; 4])),
+                7 => Err("port closed"),
+                _ => Err("timeout"),
+            }
+        }
+    }
+
+    impl ReplyTimeout for FailingInterface {
+        type Timeout = u32;
+
+        fn reply_timeout(&self) -> u32 {
+            self.timeout
+        }
+
+        fn set_reply_timeout(&mut self, timeout: u32) -> Result<(), Self::Error> {
+            self.timeout = timeout;
+            Ok(())
+        }
+
+        fn is_timeout(error: &Self::Error) -> bool {
+            *error == "timeout"
+        }
+    }
+
+    #[test]
+    fn receive_error() {
+        let mut interface = FailingInterface {
+            address: 0,
+            timeout: 1000,
+        };
+        {
+            let mut modules = scan(&mut interface, 1..=10, 20);
+            assert_eq!(modules.next().unwrap().unwrap().address(), 5);
+            assert_eq!(modules.interface.reply_timeout(), 20);
+            assert_eq!(
+                modules.next().unwrap(),
+                Err(Error::InterfaceError("port closed"))
+            );
+        }
+        assert_eq!(interface.reply_timeout(), 1000);
+
+        let modules = scan(&mut interface, 8..=10, 20).collect::<Vec<_>>();
+        assert!(modules.is_empty());
+        assert_eq!(interface.reply_timeout(), 1000);
+    }
+}
@@ -0,0 +1,356 @@
+use std::io;
+use std::io::{Read, Write};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use Command;
+use Instruction;
+use Interface;
+use Reply;
+use ReplyTimeout;
+
+/// An `Interface` for TMCM modules connected through RS232, RS485 or any other byte stream.
+///
+/// Works with anything implementing `Read + Write`, such as a serial port, a pty or a TCP stream.
+/// Commands and replies use the 9 byte binary format of `Command::serialize`.
+///
+/// Bytes that do not form a valid reply (wrong checksum or status) are skipped one at a time
+/// until the stream is synchronised with the reply frames again. Valid replies from other
+/// modules or to other commands are discarded.
+///
+/// Reads should not block forever, so the stream must either be non-blocking or have a read
+/// timeout configured. Otherwise the reply timeout of this interface can't be honoured.
+#[derive(Debug)]
+pub struct SerialInterface<T> {
+    port: T,
+    timeout: Duration,
+    expected: Option<(u8, u8)>,
+}
+
+/// All possible errors when communicating over a byte stream.
+#[derive(Debug)]
+pub enum SerialError {
+    /// The underlying stream had an error.
+    IoError(io::Error),
+
+    /// No valid reply was received before the timeout expired.
+    Timeout,
+
+    /// A reply was requested before any command was transmitted.
+    NoCommandTransmitted,
+}
+
+impl From<io::Error> for SerialError {
+    fn from(e: io::Error) -> Self {
+        SerialError::IoError(e)
+    }
+}
+
+impl<T: Read + Write> SerialInterface<T> {
+    /// The reply timeout used by `SerialInterface::new`.
+    pub const DEFAULT_TIMEOUT: Duration = Duration::from_millis(100);
+
+    /// Create a new interface on the given byte stream
+    pub fn new(port: T) -> Self {
+        Self::with_timeout(port, Self::DEFAULT_TIMEOUT)
+    }
+
+    /// Create a new interface on the given byte stream with a custom reply timeout
+    pub fn with_timeout(port: T, timeout: Duration) -> Self {
+        SerialInterface {
+            port,
+            timeout,
+            expected: None,
+        }
+    }
+
+    /// Returns the reply timeout
+    pub fn timeout(&self) -> Duration {
+        self.timeout
+    }
+
+    /// Set the reply timeout
+    pub fn set_timeout(&mut self, timeout: Duration) {
+        self.timeout = timeout;
+    }
+
+    /// Returns a reference to the underlying byte stream
+    pub fn get_ref(&self) -> &T {
+        &self.port
+    }
+
+    /// Returns a mutable reference to the underlying byte stream
+    pub fn get_mut(&mut self) -> &mut T {
+        &mut self.port
+    }
+
+    /// Release the underlying byte stream
+    pub fn release(self) -> T {
+        self.port
+    }
+
+    /// Receive the reply to a `GetFirmwareVersionAscii` instruction
+    ///
+    /// This reply has no status and checksum, so it can't be received with `receive_reply`.
+    /// Returns the reply address followed by the 8 characters of the version string,
+    /// which can be parsed with `FirmwareVersion::from_ascii`.
+    pub fn receive_ascii_reply(&mut self) -> Result<[u8; 9], SerialError> {
+        self.expected.ok_or(SerialError::NoCommandTransmitted)?;
+        let deadline = Instant::now() + self.timeout;
+        let mut frame = [0u8; 9];
+        let mut len = 0;
+
+        while len < frame.len() {
+            if Instant::now() > deadline {
+                return Err(SerialError::Timeout);
+            }
+            match self.read_byte()? {
+                Some(byte) => {
+                    frame[len] = byte;
+                    len += 1;
+                }
+                None => thread::sleep(Duration::from_millis(1)),
+            }
+        }
+        self.expected = None;
+        Ok(frame)
+    }
+
+    /// Read a single byte, returns `None` if no byte is available yet.
+    fn read_byte(&mut self) -> Result<Option<u8>, SerialError> {
+        let mut byte = [0u8];
+        match self.port.read(&mut byte) {
+            Ok(0) => Ok(None),
+            Ok(_) => Ok(Some(byte[0])),
+            Err(ref e)
+                if e.kind() == io::ErrorKind::WouldBlock
+                    || e.kind() == io::ErrorKind::TimedOut
+                    || e.kind() == io::ErrorKind::Interrupted =>
+            {
+                Ok(None)
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+}
+
+impl<T: Read + Write> Interface for SerialInterface<T> {
+    type Error = SerialError;
+
+    fn transmit_command<I: Instruction>(
+        &mut self,
+        command: &Command<I>,
+    ) -> Result<(), Self::Error> {
+        self.expected = Some((command.module_address(), I::INSTRUCTION_NUMBER));
+        self.port.write_all(&command.serialize())?;
+        self.port.flush()?;
+        Ok(())
+    }
+
+    fn receive_reply(&mut self) -> Result<Reply, Self::Error> {
+        let (module_address, command_number) =
+            self.expected.ok_or(SerialError::NoCommandTransmitted)?;
+        let deadline = Instant::now() + self.timeout;
+        let mut frame = [0u8; 9];
+        let mut len = 0;
+
+        loop {
+            if Instant::now() > deadline {
+                return Err(SerialError::Timeout);
+            }
+
+            match self.read_byte()? {
+                Some(byte) => {
+                    frame[len] = byte;
+                    len += 1;
+                }
+                None => {
+                    thread::sleep(Duration::from_millis(1));
+                    continue;
+                }
+            }
+
+            if len < frame.len() {
+                continue;
+            }
+
+            match Reply::deserialize(&frame) {
+                Ok(reply) => {
+                    len = 0;
+                    if reply.module_address() == module_address
+                        && reply.command_number() == command_number
+                    {
+                        self.expected = None;
+                        return Ok(reply);
+                    }
+                }
+                Err(_) => {
+                    // Not a reply frame, resynchronise by dropping the first byte.
+                    frame.copy_within(1.., 0);
+                    len -= 1;
+                }
+            }
+        }
+    }
+}
+
+impl<T: Read + Write> ReplyTimeout for SerialInterface<T> {
+    type Timeout = Duration;
+
+    fn reply_timeout(&self) -> Duration {
+        self.timeout
+    }
+
+    fn set_reply_timeout(&mut self, timeout: Duration) -> Result<(), SerialError> {
+        self.timeout = timeout;
+        Ok(())
+    }
+
+    fn is_timeout(error: &SerialError) -> bool {
+        matches!(error, SerialError::Timeout)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::io::Cursor;
+
+    use instructions::{FirmwareVersion, GetFirmwareVersionAscii, GIO, ROR};
+    use OkStatus;
+    use Status;
+
+    struct MockPort {
+        written: Vec<u8>,
+        input: Cursor<Vec<u8>>,
+    }
+
+    impl MockPort {
+        fn new(input: Vec<u8>) -> Self {
+            MockPort {
+                written: Vec::new(),
+                input: Cursor::new(input),
+            }
+        }
+    }
+
+    impl Read for MockPort {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            self.input.read(buf)
+        }
+    }
+
+    impl Write for MockPort {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.written.write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn resynchronise_after_garbage() {
+        let mut input = vec![0xff, 0x13, 2];
+        // A valid reply from another module
+        input.extend_from_slice(&[2, 4, 100, 15, 0, 0, 0, 1, 122]);
+        input.extend_from_slice(&[2, 1, 100, 15, 0, 0, 0, 1, 119]);
+        let mut interface = SerialInterface::new(MockPort::new(input));
+
+        interface
+            .transmit_command(&Command::new(1, GIO::new(0, 1)))
+            .unwrap();
+        assert_eq!(
+            interface.get_ref().written,
+            vec![1, 15, 1, 0, 0, 0, 0, 0, 17]
+        );
+
+        let reply = interface.receive_reply().unwrap();
+        assert_eq!(reply.module_address(), 1);
+        assert_eq!(reply.status(), Status::Ok(OkStatus::Ok));
+        assert_eq!(reply.operand(), [1, 0, 0, 0]);
+    }
+
+    #[test]
+    fn timeout_on_wrong_checksum() {
+        let input = vec![2, 1, 100, 1, 0, 0, 0, 0, 105];
+        let mut interface =
+            SerialInterface::with_timeout(MockPort::new(input), Duration::from_millis(10));
+
+        interface
+            .transmit_command(&Command::new(1, ROR::new(0, 0)))
+            .unwrap();
+        match interface.receive_reply() {
+            Err(SerialError::Timeout) => (),
+            other => panic!("expected timeout, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn ascii_firmware_version() {
+        let mut input = vec![2];
+        input.extend_from_slice(b"1140V211");
+        let mut interface = SerialInterface::new(MockPort::new(input));
+
+        interface
+            .transmit_command(&Command::new(1, GetFirmwareVersionAscii::new()))
+            .unwrap();
+        let reply = interface.receive_ascii_reply().unwrap();
+        assert_eq!(
+            FirmwareVersion::from_ascii(&reply[1..]),
+            Ok(FirmwareVersion::new(1140, 2, 11))
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn pty_pair() {
+        use std::fs::File;
+        use std::os::unix::io::FromRawFd;
+        use std::ptr;
+
+        let mut master = 0;
+        let mut slave = 0;
+        let (mut host, mut module) = unsafe {
+            assert_eq!(
+                ::libc::openpty(
+                    &mut master,
+                    &mut slave,
+                    ptr::null_mut(),
+                    ptr::null(),
+                    ptr::null()
+                ),
+                0
+            );
+            for &fd in &[master, slave] {
+                let mut termios = ::std::mem::zeroed();
+                ::libc::tcgetattr(fd, &mut termios);
+                ::libc::cfmakeraw(&mut termios);
+                termios.c_cc[::libc::VMIN] = 0;
+                termios.c_cc[::libc::VTIME] = 1;
+                ::libc::tcsetattr(fd, ::libc::TCSANOW, &termios);
+            }
+            (File::from_raw_fd(master), File::from_raw_fd(slave))
+        };
+
+        let simulated_module = thread::spawn(move || {
+            let mut command = [0u8; 9];
+            module.read_exact(&mut command).unwrap();
+            assert_eq!(command, [3, 1, 0, 0, 0, 0, 0, 250, 254]);
+            module
+                .write_all(&[0x55, 2, 3, 100, 1, 0, 0, 0, 0, 106])
+                .unwrap();
+        });
+
+        let mut interface = SerialInterface::with_timeout(&mut host, Duration::from_millis(500));
+        interface
+            .transmit_command(&Command::new(3, ROR::new(0, 250)))
+            .unwrap();
+        let reply = interface.receive_reply().unwrap();
+        assert_eq!(reply, Reply::new(2, 3, Status::Ok(OkStatus::Ok), 1, [0; 4]));
+
+        simulated_module.join().unwrap();
+    }
+}
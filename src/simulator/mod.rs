@@ -0,0 +1,1086 @@
+//! An in-memory TMCM module for testing without hardware.
+//!
+//! `SimulatedModule` implements `Interface` and answers commands the way the firmware of a
+//! TMCM module does. It keeps the axis parameters of every motor, the global parameters and the
+//! digital and analog I/O ports, together with a simulated EEPROM for `STAP`, `RSAP`, `STGP` and
+//! `RSGP`.
+//!
+//! The motors are moved by a trapezoidal ramp generator on a virtual clock, which only runs when
+//! `SimulatedModule::advance` is called. Limit and home switches can be placed at fixed positions
+//! for testing reference searches.
+//!
+//! ```
+//! extern crate tmcl;
+//!
+//! use std::cell::RefCell;
+//!
+//! use tmcl::modules::tmcm::axis_parameters::*;
+//! use tmcl::modules::tmcm::instructions::*;
+//! use tmcl::modules::tmcm::TmcmModule as Module;
+//! use tmcl::simulator::SimulatedModule;
+//! use tmcl::{ErrStatus, Error};
+//!
+//! fn main() {
+//!     let interface = RefCell::new(SimulatedModule::new(1, 1));
+//!     let module = Module::new(&interface, 1);
+//!
+//!     module.write_command(SAP::new(0, MaximumPositioningSpeed::new(500))).unwrap();
+//!     let speed = module.write_command(GAP::<MaximumPositioningSpeed>::new(0)).unwrap();
+//!     assert_eq!(u32::from(speed), 500);
+//!
+//!     assert_eq!(
+//!         module.write_command(SAP::new(0, MaximumPositioningSpeed::new(5000))),
+//!         Err(Error::ProtocolError(ErrStatus::InvalidValue))
+//!     );
+//! }
+//! ```
+
+use std::collections::HashMap;
+use std::ops::Range;
+use std::time::Duration;
+
+use Command;
+use ErrStatus;
+use Instruction;
+use Interface;
+use OkStatus;
+use Reply;
+use ReplyTimeout;
+use Status;
+
+mod motion;
+
+use self::motion::{Motion, MAX_POSITION, MAX_VELOCITY};
+
+/// The address replies are sent to.
+const HOST_ADDRESS: u8 = 2;
+
+/// The number of digital inputs, analog inputs and digital outputs.
+const PORTS: u8 = 8;
+
+/// Magic values of the EEPROM lock global parameter.
+const EEPROM_LOCK: i32 = 1234;
+const EEPROM_UNLOCK: i32 = 4321;
+
+/// The time step of the motion simulation.
+const TICK: Duration = Duration::from_millis(1);
+
+/// A simulated TMCM module.
+///
+/// It only replies to commands addressed to its serial address (global parameter 66), so
+/// several simulated modules can share a bus. The supported instructions are `ROR`, `ROL`,
+/// `MST`, `MVP` (absolute and relative), `SAP`, `GAP`, `STAP`, `RSAP`, `SGP`, `GGP`, `STGP`,
+/// `RSGP`, `RFS`, `SIO`, `GIO` and `GetFirmwareVersion`, all other instructions are answered
+/// with `ErrStatus::InvalidCommand`.
+///
+/// Parameters are checked like the firmware does. Unknown parameters are answered with
+/// `ErrStatus::WrongType`, as are attempts to write or store read-only parameters. Values out
+/// of range and motors the module does not have are answered with `ErrStatus::InvalidValue`.
+/// While the configuration EEPROM is locked, storing parameters is answered with
+/// `ErrStatus::EEPROMLocked`.
+#[derive(Debug, Clone)]
+pub struct SimulatedModule {
+    motors: u8,
+    axis_parameters: HashMap<(u8, u8), i32>,
+    stored_axis_parameters: HashMap<(u8, u8), i32>,
+    global_parameters: HashMap<(u8, u8), i32>,
+    stored_global_parameters: HashMap<(u8, u8), i32>,
+    inputs: [bool; PORTS as usize],
+    analog_inputs: [u16; PORTS as usize],
+    outputs: [bool; PORTS as usize],
+    motion: Vec<Motion>,
+    time: Duration,
+    firmware_version: (u16, u8, u8),
+    reply: Option<Reply>,
+}
+
+/// All possible errors when communicating with a `SimulatedModule`.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum SimulatorError {
+    /// No reply was sent, because the last command was addressed to a different module.
+    Timeout,
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+enum Access {
+    Read,
+    ReadWrite,
+}
+
+/// Description of a simulated parameter.
+#[derive(Debug, Clone, Copy)]
+struct Parameter {
+    number: u8,
+    access: Access,
+    min: i32,
+    max: i32,
+    default: i32,
+}
+
+const fn rw(number: u8, min: i32, max: i32, default: i32) -> Parameter {
+    Parameter {
+        number,
+        access: Access::ReadWrite,
+        min,
+        max,
+        default,
+    }
+}
+
+const fn r(number: u8) -> Parameter {
+    Parameter {
+        number,
+        access: Access::Read,
+        min: i32::MIN,
+        max: i32::MAX,
+        default: 0,
+    }
+}
+
+/// The axis parameters of a TMCM module, with the ranges of a TMCM-1140.
+const AXIS_PARAMETERS: &[Parameter] = &[
+    rw(0, i32::MIN, i32::MAX, 0),
+    rw(1, i32::MIN, i32::MAX, 0),
+    rw(2, -2047, 2047, 0),
+    r(3),
+    rw(4, 0, 2047, 1000),
+    rw(5, 0, 2047, 1000),
+    rw(6, 0, 255, 128),
+    rw(7, 0, 255, 8),
+    r(8),
+    r(9),
+    r(10),
+    r(11),
+    rw(12, 0, 1, 0),
+    rw(13, 0, 1, 0),
+    rw(140, 0, 8, 8),
+    rw(153, 0, 13, 7),
+    rw(154, 0, 13, 3),
+    rw(162, 0, 3, 2),
+    rw(163, 0, 1, 0),
+    rw(164, 0, 3, 0),
+    rw(165, -3, 12, 2),
+    rw(166, 0, 8, 4),
+    rw(167, 0, 15, 3),
+    rw(168, 0, 1, 0),
+    rw(169, 0, 3, 0),
+    rw(170, 0, 15, 0),
+    rw(171, 1, 3, 1),
+    rw(172, 0, 15, 0),
+    rw(173, 0, 1, 0),
+    rw(174, -64, 63, 0),
+    r(179),
+    r(180),
+    rw(181, 0, 2047, 0),
+    rw(182, 0, 2047, 0),
+    rw(183, 0, 255, 0),
+    rw(193, 1, 255, 1),
+    rw(194, 0, 2047, 2047),
+    rw(195, 0, 2047, 1024),
+    r(196),
+    r(197),
+    rw(200, 0, 255, 0),
+    rw(203, -1, 2048, 2048),
+    rw(204, 0, 65535, 0),
+    r(206),
+    r(208),
+    rw(209, i32::MIN, i32::MAX, 0),
+    rw(210, 0, 65535, 0),
+    rw(212, 0, i32::MAX, 0),
+    rw(214, 1, 65535, 200),
+];
+
+/// The global parameters of bank 0 and 3, bank 2 holds the user variables.
+const GLOBAL_PARAMETERS: &[(u8, Parameter)] = &[
+    (0, rw(65, 0, 11, 0)),
+    (0, rw(66, 1, 255, 1)),
+    (0, rw(69, 2, 8, 8)),
+    (0, rw(70, 0, 0x7ff, 2)),
+    (0, rw(71, 0, 0x7ff, 1)),
+    (0, rw(73, 0, 1, 0)),
+    (3, rw(0, 0, i32::MAX, 0)),
+    (3, rw(1, 0, i32::MAX, 0)),
+    (3, rw(2, 0, i32::MAX, 0)),
+    (3, rw(39, 0, 3, 0)),
+    (3, rw(40, 0, 3, 0)),
+];
+
+/// The highest user variable number.
+const USER_VARIABLES: u8 = 55;
+
+fn axis_parameter(number: u8) -> Option<Parameter> {
+    AXIS_PARAMETERS.iter().find(|p| p.number == number).cloned()
+}
+
+fn global_parameter(bank: u8, number: u8) -> Option<Parameter> {
+    match bank {
+        2 if number <= USER_VARIABLES => Some(rw(number, i32::MIN, i32::MAX, 0)),
+        _ => GLOBAL_PARAMETERS
+            .iter()
+            .find(|&&(b, ref p)| b == bank && p.number == number)
+            .map(|&(_, p)| p),
+    }
+}
+
+impl SimulatedModule {
+    /// Create a new module with the given serial address and number of motors
+    ///
+    /// All parameters have their default values.
+    pub fn new(address: u8, motors: u8) -> Self {
+        let mut axis_parameters = HashMap::new();
+        for motor in 0..motors {
+            for parameter in AXIS_PARAMETERS {
+                axis_parameters.insert((motor, parameter.number), parameter.default);
+            }
+        }
+        let mut global_parameters = HashMap::new();
+        for &(bank, parameter) in GLOBAL_PARAMETERS {
+            global_parameters.insert((bank, parameter.number), parameter.default);
+        }
+        for number in 0..=USER_VARIABLES {
+            global_parameters.insert((2, number), 0);
+        }
+        global_parameters.insert((0, 66), i32::from(address));
+
+        SimulatedModule {
+            motors,
+            stored_axis_parameters: axis_parameters.clone(),
+            axis_parameters,
+            stored_global_parameters: global_parameters.clone(),
+            global_parameters,
+            inputs: [false; PORTS as usize],
+            analog_inputs: [0; PORTS as usize],
+            outputs: [false; PORTS as usize],
+            motion: (0..motors).map(|_| Motion::new()).collect(),
+            time: Duration::from_secs(0),
+            firmware_version: (1140, 1, 0),
+            reply: None,
+        }
+    }
+
+    /// Returns the serial address the module answers to
+    pub fn address(&self) -> u8 {
+        self.global_parameters[&(0, 66)] as u8
+    }
+
+    /// Returns the number of motors
+    pub fn motors(&self) -> u8 {
+        self.motors
+    }
+
+    /// Set the module type and firmware revision reported by `GetFirmwareVersion`
+    ///
+    /// The default is a TMCM-1140 with firmware version 1.0.
+    pub fn set_firmware_version(&mut self, module_type: u16, major: u8, minor: u8) {
+        self.firmware_version = (module_type, major, minor);
+    }
+
+    /// Returns the value of an axis parameter, if the module has it
+    pub fn axis_parameter(&self, motor: u8, number: u8) -> Option<i32> {
+        self.axis_parameters.get(&(motor, number)).cloned()
+    }
+
+    /// Set the value of an axis parameter, including read-only parameters
+    ///
+    /// No range checks are done, this is meant for simulating the state of the motor.
+    /// Parameters the module does not have are ignored.
+    pub fn set_axis_parameter(&mut self, motor: u8, number: u8, value: i32) {
+        if let Some(v) = self.axis_parameters.get_mut(&(motor, number)) {
+            *v = value;
+            self.sync_motion(motor, number, value);
+        }
+    }
+
+    /// Place the left and right limit switches of a motor
+    ///
+    /// The left switch is active at and below `left`, the right switch at and above `right`.
+    /// Motors the module does not have are ignored.
+    pub fn set_limit_switches(&mut self, motor: u8, left: Option<i32>, right: Option<i32>) {
+        let motion = match self.motion.get_mut(motor as usize) {
+            Some(motion) => motion,
+            None => return,
+        };
+        motion.set_limit_switches(left, right);
+        motion.update_parameters(motor, &mut self.axis_parameters);
+    }
+
+    /// Place the home switch of a motor, it is active for positions in `home`.
+    ///
+    /// Motors the module does not have are ignored.
+    pub fn set_home_switch(&mut self, motor: u8, home: Option<Range<i32>>) {
+        let motion = match self.motion.get_mut(motor as usize) {
+            Some(motion) => motion,
+            None => return,
+        };
+        motion.set_home_switch(home);
+        motion.update_parameters(motor, &mut self.axis_parameters);
+    }
+
+    /// Advance the virtual clock by `duration`, moving the motors.
+    ///
+    /// The motion is simulated in steps of one millisecond.
+    pub fn advance(&mut self, duration: Duration) {
+        let mut remaining = duration;
+        while remaining > Duration::from_secs(0) {
+            let dt = remaining.min(TICK);
+            for (motor, motion) in self.motion.iter_mut().enumerate() {
+                motion.tick(motor as u8, &mut self.axis_parameters, dt.as_secs_f64());
+            }
+            remaining -= dt;
+            self.time += dt;
+        }
+    }
+
+    /// Returns the time of the virtual clock
+    pub fn time(&self) -> Duration {
+        self.time
+    }
+
+    /// Returns the value of a global parameter, if the module has it
+    pub fn global_parameter(&self, bank: u8, number: u8) -> Option<i32> {
+        self.global_parameters.get(&(bank, number)).cloned()
+    }
+
+    /// Set the state of a digital input, ports the module does not have are ignored
+    pub fn set_input(&mut self, port: u8, state: bool) {
+        if let Some(input) = self.inputs.get_mut(port as usize) {
+            *input = state;
+        }
+    }
+
+    /// Set the value of an analog input (0..1023), ports the module does not have are ignored
+    pub fn set_analog_input(&mut self, port: u8, value: u16) {
+        if let Some(input) = self.analog_inputs.get_mut(port as usize) {
+            *input = value.min(1023);
+        }
+    }
+
+    /// Returns the state of a digital output, false for ports the module does not have
+    pub fn output(&self, port: u8) -> bool {
+        self.outputs.get(port as usize).cloned().unwrap_or(false)
+    }
+
+    /// Simulate a power cycle, restoring all parameters from EEPROM and clearing the outputs.
+    ///
+    /// The motors are stopped.
+    pub fn reset(&mut self) {
+        self.axis_parameters = self.stored_axis_parameters.clone();
+        self.global_parameters = self.stored_global_parameters.clone();
+        self.outputs = [false; PORTS as usize];
+        self.reply = None;
+        for (motor, motion) in self.motion.iter_mut().enumerate() {
+            let motor = motor as u8;
+            self.axis_parameters.insert((motor, 2), 0);
+            motion.rotate();
+            motion.set_speed(0);
+            motion.set_position(self.axis_parameters[&(motor, 1)]);
+            motion.update_parameters(motor, &mut self.axis_parameters);
+        }
+    }
+
+    /// Keep the motion state in line with a written axis parameter.
+    fn sync_motion(&mut self, motor: u8, number: u8, value: i32) {
+        match number {
+            1 => self.motion[motor as usize].set_position(value),
+            3 => self.motion[motor as usize].set_speed(value),
+            _ => (),
+        }
+    }
+
+    fn eeprom_locked(&self) -> bool {
+        self.global_parameters[&(0, 73)] != 0
+    }
+
+    /// Execute a command, returns the status and the reply value.
+    fn execute(&mut self, command: u8, type_number: u8, bank: u8, value: i32) -> (Status, i32) {
+        let result = match command {
+            1..=4 | 13 => self.motion_command(command, type_number, bank, value),
+            5..=8 => self.axis_command(command, type_number, bank, value),
+            9..=12 => self.global_command(command, type_number, bank, value),
+            14 => self.set_output(type_number, bank, value),
+            15 => self.get_input(type_number, bank),
+            // Only the binary form of GetFirmwareVersion, the ASCII form has no regular reply
+            136 if type_number == 1 => {
+                let (module_type, major, minor) = self.firmware_version;
+                Ok(i32::from(module_type) << 16 | i32::from(major) << 8 | i32::from(minor))
+            }
+            136 => Err(ErrStatus::WrongType),
+            _ => Err(ErrStatus::InvalidCommand),
+        };
+        match result {
+            Ok(value) => (Status::Ok(OkStatus::Ok), value),
+            Err(e) => (Status::Err(e), 0),
+        }
+    }
+
+    fn axis_command(
+        &mut self,
+        command: u8,
+        number: u8,
+        motor: u8,
+        value: i32,
+    ) -> Result<i32, ErrStatus> {
+        let parameter = axis_parameter(number).ok_or(ErrStatus::WrongType)?;
+        if motor >= self.motors {
+            return Err(ErrStatus::InvalidValue);
+        }
+        let key = (motor, number);
+        match command {
+            // GAP
+            6 => return Ok(self.axis_parameters[&key]),
+            _ if parameter.access == Access::Read => return Err(ErrStatus::WrongType),
+            // SAP
+            5 => {
+                check_range(&parameter, value)?;
+                self.axis_parameters.insert(key, value);
+                self.sync_motion(motor, number, value);
+            }
+            // STAP
+            7 => {
+                if self.eeprom_locked() {
+                    return Err(ErrStatus::EEPROMLocked);
+                }
+                let value = self.axis_parameters[&key];
+                self.stored_axis_parameters.insert(key, value);
+            }
+            // RSAP
+            _ => {
+                let value = self.stored_axis_parameters[&key];
+                self.axis_parameters.insert(key, value);
+            }
+        }
+        Ok(value)
+    }
+
+    fn motion_command(
+        &mut self,
+        command: u8,
+        type_number: u8,
+        motor: u8,
+        value: i32,
+    ) -> Result<i32, ErrStatus> {
+        if motor >= self.motors {
+            return Err(ErrStatus::InvalidValue);
+        }
+        let motion = &mut self.motion[motor as usize];
+        match command {
+            // ROR and ROL
+            1 | 2 => {
+                if !(0..=MAX_VELOCITY).contains(&value) {
+                    return Err(ErrStatus::InvalidValue);
+                }
+                let speed = if command == 1 { value } else { -value };
+                self.axis_parameters.insert((motor, 2), speed);
+                motion.rotate();
+            }
+            // MST
+            3 => {
+                self.axis_parameters.insert((motor, 2), 0);
+                motion.rotate();
+            }
+            // MVP
+            4 => {
+                let target = match type_number {
+                    0 => value,
+                    1 => self.axis_parameters[&(motor, 1)].saturating_add(value),
+                    _ => return Err(ErrStatus::WrongType),
+                };
+                if !(-MAX_POSITION..=MAX_POSITION).contains(&target) {
+                    return Err(ErrStatus::InvalidValue);
+                }
+                self.axis_parameters.insert((motor, 0), target);
+                motion.move_to_target();
+            }
+            // RFS
+            _ => match type_number {
+                0 => {
+                    if !motion.start_reference_search(self.axis_parameters[&(motor, 193)]) {
+                        return Err(ErrStatus::InvalidValue);
+                    }
+                }
+                1 => {
+                    self.axis_parameters.insert((motor, 2), 0);
+                    motion.rotate();
+                }
+                2 => return Ok(motion.reference_search_active() as i32),
+                _ => return Err(ErrStatus::WrongType),
+            },
+        }
+        Ok(value)
+    }
+
+    fn global_command(
+        &mut self,
+        command: u8,
+        number: u8,
+        bank: u8,
+        value: i32,
+    ) -> Result<i32, ErrStatus> {
+        let parameter = global_parameter(bank, number).ok_or(ErrStatus::WrongType)?;
+        let key = (bank, number);
+        match command {
+            // GGP
+            10 => return Ok(self.global_parameters[&key]),
+            // SGP of the EEPROM lock, which takes magic values
+            9 if key == (0, 73) => {
+                let locked = match value {
+                    EEPROM_LOCK => 1,
+                    EEPROM_UNLOCK => 0,
+                    _ => return Err(ErrStatus::InvalidValue),
+                };
+                self.global_parameters.insert(key, locked);
+                self.stored_global_parameters.insert(key, locked);
+            }
+            // SGP, bank 0 is stored in EEPROM automatically
+            9 => {
+                check_range(&parameter, value)?;
+                if bank == 0 {
+                    if self.eeprom_locked() {
+                        return Err(ErrStatus::EEPROMLocked);
+                    }
+                    self.stored_global_parameters.insert(key, value);
+                }
+                self.global_parameters.insert(key, value);
+            }
+            // STGP
+            11 => {
+                if self.eeprom_locked() {
+                    return Err(ErrStatus::EEPROMLocked);
+                }
+                let value = self.global_parameters[&key];
+                self.stored_global_parameters.insert(key, value);
+            }
+            // RSGP
+            _ => {
+                let value = self.stored_global_parameters[&key];
+                self.global_parameters.insert(key, value);
+            }
+        }
+        Ok(value)
+    }
+
+    fn set_output(&mut self, port: u8, bank: u8, value: i32) -> Result<i32, ErrStatus> {
+        if bank != 2 {
+            return Err(ErrStatus::InvalidValue);
+        }
+        if port >= PORTS {
+            return Err(ErrStatus::WrongType);
+        }
+        match value {
+            0 | 1 => self.outputs[port as usize] = value == 1,
+            _ => return Err(ErrStatus::InvalidValue),
+        }
+        Ok(value)
+    }
+
+    fn get_input(&self, port: u8, bank: u8) -> Result<i32, ErrStatus> {
+        if port >= PORTS {
+            return Err(ErrStatus::WrongType);
+        }
+        let port = port as usize;
+        match bank {
+            0 => Ok(self.inputs[port] as i32),
+            1 => Ok(i32::from(self.analog_inputs[port])),
+            2 => Ok(self.outputs[port] as i32),
+            _ => Err(ErrStatus::InvalidValue),
+        }
+    }
+}
+
+fn check_range(parameter: &Parameter, value: i32) -> Result<(), ErrStatus> {
+    if (parameter.min..=parameter.max).contains(&value) {
+        Ok(())
+    } else {
+        Err(ErrStatus::InvalidValue)
+    }
+}
+
+impl Interface for SimulatedModule {
+    type Error = SimulatorError;
+
+    fn transmit_command<T: Instruction>(
+        &mut self,
+        command: &Command<T>,
+    ) -> Result<(), Self::Error> {
+        let frame = command.serialize();
+        self.reply = if frame[0] == self.address() {
+            let value = i32::from_be_bytes([frame[4], frame[5], frame[6], frame[7]]);
+            let (status, value) = self.execute(frame[1], frame[2], frame[3], value);
+            Some(Reply::new(
+                HOST_ADDRESS,
+                frame[0],
+                status,
+                frame[1],
+                value.to_le_bytes(),
+            ))
+        } else {
+            None
+        };
+        Ok(())
+    }
+
+    fn receive_reply(&mut self) -> Result<Reply, Self::Error> {
+        self.reply.take().ok_or(SimulatorError::Timeout)
+    }
+}
+
+/// Simulated modules reply immediately, so there is no timeout to set.
+impl ReplyTimeout for SimulatedModule {
+    type Timeout = ();
+
+    fn reply_timeout(&self) {}
+
+    fn set_reply_timeout(&mut self, _timeout: ()) -> Result<(), SimulatorError> {
+        Ok(())
+    }
+
+    fn is_timeout(error: &SimulatorError) -> bool {
+        *error == SimulatorError::Timeout
+    }
+}
+
+/// Several simulated modules sharing one bus.
+///
+/// Commands are passed to the modules with the address of the command. Every module keeps its
+/// reply until it is received, and the replies are received in the order of the modules.
+#[derive(Debug, Clone)]
+pub struct SimulatedBus {
+    modules: Vec<SimulatedModule>,
+}
+
+impl SimulatedBus {
+    /// Create a new bus with the given modules
+    pub fn new(modules: Vec<SimulatedModule>) -> Self {
+        SimulatedBus { modules }
+    }
+
+    /// Returns the modules on the bus
+    pub fn modules(&self) -> &[SimulatedModule] {
+        &self.modules
+    }
+
+    /// Returns the modules on the bus, for changing their simulated state
+    pub fn modules_mut(&mut self) -> &mut [SimulatedModule] {
+        &mut self.modules
+    }
+
+    /// Advance the virtual clock of all modules by `duration`
+    pub fn advance(&mut self, duration: Duration) {
+        for module in &mut self.modules {
+            module.advance(duration);
+        }
+    }
+}
+
+impl Interface for SimulatedBus {
+    type Error = SimulatorError;
+
+    fn transmit_command<T: Instruction>(
+        &mut self,
+        command: &Command<T>,
+    ) -> Result<(), Self::Error> {
+        for module in &mut self.modules {
+            if module.address() == command.module_address() {
+                module.transmit_command(command)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn receive_reply(&mut self) -> Result<Reply, Self::Error> {
+        self.modules
+            .iter_mut()
+            .find_map(|module| module.reply.take())
+            .ok_or(SimulatorError::Timeout)
+    }
+}
+
+/// Simulated modules reply immediately, so there is no timeout to set.
+impl ReplyTimeout for SimulatedBus {
+    type Timeout = ();
+
+    fn reply_timeout(&self) {}
+
+    fn set_reply_timeout(&mut self, _timeout: ()) -> Result<(), SimulatorError> {
+        Ok(())
+    }
+
+    fn is_timeout(error: &SimulatorError) -> bool {
+        *error == SimulatorError::Timeout
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::cell::RefCell;
+
+    use metadata::{ParameterInfo, ParameterTable};
+    use modules::generic::instructions::SAP as GenericSAP;
+    use modules::generic::GenericModule;
+    use modules::tmcm::axis_parameters::{
+        ActualPosition, ActualSpeed, HysteresisEnd, LastReferencePosition, LeftLimitSwitchState,
+        MaximumPositioningSpeed, PositionReachedFlag, PulseDivisor, ReferenceSearchMode,
+        ReferenceSwitchSpeed, SearchMode,
+    };
+    use modules::tmcm::global_parameters::{EEPROMLock, SerialAddress, UserVariable};
+    use modules::tmcm::instructions::{
+        MoveOperation, ReferenceSearchAction, GAP, GGP, GIO, MST, MVP, RFS, ROL, ROR, RSAP, SAP,
+        SCO, SGP, SIO, STAP, STGP,
+    };
+    use modules::tmcm::TmcmModule;
+    use Error;
+
+    #[test]
+    fn axis_parameters() {
+        let interface = RefCell::new(SimulatedModule::new(1, 2));
+        let module = TmcmModule::new(&interface, 1);
+
+        assert_eq!(
+            module.write_command(SAP::new(1, HysteresisEnd::new(-3))),
+            Ok(())
+        );
+        assert_eq!(
+            module
+                .write_command(GAP::<HysteresisEnd>::new(1))
+                .map(i32::from),
+            Ok(-3)
+        );
+        assert_eq!(interface.borrow().axis_parameter(1, 165), Some(-3));
+        assert_eq!(
+            module
+                .write_command(GAP::<HysteresisEnd>::new(2))
+                .map(i32::from),
+            Err(Error::ProtocolError(ErrStatus::InvalidValue))
+        );
+        assert_eq!(
+            module.write_command(SAP::new(0, MaximumPositioningSpeed::new(2048))),
+            Err(Error::ProtocolError(ErrStatus::InvalidValue))
+        );
+        assert_eq!(
+            module.write_command(SCO::new(1, 0, 100)),
+            Err(Error::ProtocolError(ErrStatus::InvalidCommand))
+        );
+
+        interface.borrow_mut().set_axis_parameter(0, 3, -200);
+        assert_eq!(
+            module
+                .write_command(GAP::<ActualSpeed>::new(0))
+                .map(i32::from),
+            Ok(-200)
+        );
+    }
+
+    #[test]
+    fn read_only_parameters() {
+        // Send all writes unchecked, to be answered by the simulated module
+        const UNCHECKED: ParameterTable = ParameterTable {
+            axis: &[],
+            global: &[],
+        };
+        let interface = RefCell::new(SimulatedModule::new(1, 1));
+        let module = GenericModule::with_parameters(&interface, 1, &UNCHECKED);
+
+        assert_eq!(
+            module.write_command(GenericSAP::new(0, 3, [0, 0, 0, 0])),
+            Err(Error::ProtocolError(ErrStatus::WrongType))
+        );
+        assert_eq!(
+            module.write_command(GenericSAP::new(0, 99, [0, 0, 0, 0])),
+            Err(Error::ProtocolError(ErrStatus::WrongType))
+        );
+    }
+
+    /// The simulated TMCM-1140 may be narrower than the TMCM family, e.g. it only takes currents
+    /// up to 255, but must not accept values the metadata rejects.
+    #[test]
+    fn within_metadata() {
+        use metadata::Access as MetadataAccess;
+        use modules::tmcm::metadata::PARAMETERS;
+
+        let check = |parameter: &Parameter, info: Option<&ParameterInfo>| {
+            let info = info.unwrap();
+            match parameter.access {
+                Access::Read => assert_eq!(info.access, MetadataAccess::Read),
+                Access::ReadWrite => {
+                    assert_eq!(info.access, MetadataAccess::ReadWrite);
+                    assert!(info.min <= i64::from(parameter.min), "{}", info.name);
+                    assert!(info.max >= i64::from(parameter.max), "{}", info.name);
+                }
+            }
+        };
+        for parameter in AXIS_PARAMETERS {
+            check(parameter, PARAMETERS.axis_parameter(parameter.number));
+        }
+        // The EEPROM lock takes magic values, it is simulated separately
+        for &(bank, ref parameter) in GLOBAL_PARAMETERS.iter().filter(|p| p.1.number != 73) {
+            check(
+                parameter,
+                PARAMETERS.global_parameter(bank, parameter.number),
+            );
+        }
+    }
+
+    #[test]
+    fn eeprom() {
+        let interface = RefCell::new(SimulatedModule::new(1, 1));
+        let module = TmcmModule::new(&interface, 1);
+
+        module
+            .write_command(SAP::new(0, MaximumPositioningSpeed::new(500)))
+            .unwrap();
+        module
+            .write_command(STAP::<MaximumPositioningSpeed>::new(0))
+            .unwrap();
+        module
+            .write_command(SAP::new(0, MaximumPositioningSpeed::new(100)))
+            .unwrap();
+        interface.borrow_mut().reset();
+        assert_eq!(
+            module
+                .write_command(GAP::<MaximumPositioningSpeed>::new(0))
+                .map(u32::from),
+            Ok(500)
+        );
+        module
+            .write_command(SAP::new(0, MaximumPositioningSpeed::new(100)))
+            .unwrap();
+        module
+            .write_command(RSAP::<MaximumPositioningSpeed>::new(0))
+            .unwrap();
+        assert_eq!(interface.borrow().axis_parameter(0, 4), Some(500));
+
+        module
+            .write_command(SGP::new(EEPROMLock::locked()))
+            .unwrap();
+        assert_eq!(
+            module.write_command(STGP::<UserVariable<3>>::new()),
+            Err(Error::ProtocolError(ErrStatus::EEPROMLocked))
+        );
+        assert_eq!(
+            module
+                .write_command(GGP::<EEPROMLock>::new())
+                .map(bool::from),
+            Ok(true)
+        );
+    }
+
+    #[test]
+    fn serial_address() {
+        let interface = RefCell::new(SimulatedModule::new(1, 1));
+        let module = TmcmModule::new(&interface, 1);
+        let moved = TmcmModule::new(&interface, 5);
+
+        assert_eq!(
+            moved.write_command(GIO::new(0, 0)),
+            Err(Error::InterfaceError(SimulatorError::Timeout))
+        );
+        module
+            .write_command(SGP::new(SerialAddress::new(5)))
+            .unwrap();
+        assert_eq!(interface.borrow().address(), 5);
+        assert_eq!(moved.write_command(GIO::new(0, 0)), Ok(0));
+    }
+
+    #[test]
+    fn io_ports() {
+        let interface = RefCell::new(SimulatedModule::new(1, 1));
+        let module = TmcmModule::new(&interface, 1);
+
+        interface.borrow_mut().set_input(2, true);
+        interface.borrow_mut().set_analog_input(1, 512);
+        assert_eq!(module.write_command(GIO::new(0, 2)), Ok(1));
+        assert_eq!(module.write_command(GIO::new(1, 1)), Ok(512));
+
+        assert_eq!(module.write_command(SIO::new(2, 7, true)), Ok(()));
+        assert!(interface.borrow().output(7));
+        assert_eq!(module.write_command(GIO::new(2, 7)), Ok(1));
+        assert_eq!(
+            module.write_command(SIO::new(2, 8, true)),
+            Err(Error::ProtocolError(ErrStatus::WrongType))
+        );
+
+        // Ports and motors the module does not have are ignored
+        let mut simulated = interface.borrow_mut();
+        simulated.set_input(8, true);
+        simulated.set_analog_input(200, 512);
+        assert!(!simulated.output(8));
+        simulated.set_limit_switches(1, Some(-1000), Some(1000));
+        simulated.set_home_switch(1, Some(0..10));
+    }
+
+    #[test]
+    fn velocity_ramp() {
+        let interface = RefCell::new(SimulatedModule::new(1, 1));
+        let module = TmcmModule::new(&interface, 1);
+        let speed = || i32::from(module.write_command(GAP::<ActualSpeed>::new(0)).unwrap());
+
+        // The default acceleration of 1000 with ramp divisor 7 is about 15 velocity units per ms
+        module.write_command(ROR::new(0, 1000)).unwrap();
+        interface.borrow_mut().advance(Duration::from_millis(10));
+        assert!((140..=160).contains(&speed()));
+        interface.borrow_mut().advance(Duration::from_secs(1));
+        assert_eq!(speed(), 1000);
+
+        module.write_command(ROL::new(0, 500)).unwrap();
+        interface.borrow_mut().advance(Duration::from_secs(1));
+        assert_eq!(speed(), -500);
+        assert!(interface.borrow().axis_parameter(0, 1).unwrap() > 0);
+
+        module.write_command(MST::new(0)).unwrap();
+        interface.borrow_mut().advance(Duration::from_millis(100));
+        assert_eq!(speed(), 0);
+        assert_eq!(
+            module.write_command(ROR::new(0, 2048)),
+            Err(Error::ProtocolError(ErrStatus::InvalidValue))
+        );
+    }
+
+    #[test]
+    fn positioning() {
+        let interface = RefCell::new(SimulatedModule::new(1, 1));
+        let module = TmcmModule::new(&interface, 1);
+        let reached = || {
+            bool::from(
+                module
+                    .write_command(GAP::<PositionReachedFlag>::new(0))
+                    .unwrap(),
+            )
+        };
+
+        // 1000 velocity units with pulse divisor 3 are about 30500 microsteps per second
+        module
+            .write_command(MVP::new(0, MoveOperation::Absolute(100_000)))
+            .unwrap();
+        interface.borrow_mut().advance(Duration::from_secs(3));
+        assert!(!reached());
+        assert_eq!(
+            module
+                .write_command(GAP::<ActualSpeed>::new(0))
+                .map(i32::from),
+            Ok(1000)
+        );
+        interface.borrow_mut().advance(Duration::from_secs(1));
+        assert!(reached());
+        assert_eq!(
+            module
+                .write_command(GAP::<ActualPosition>::new(0))
+                .map(i32::from),
+            Ok(100_000)
+        );
+
+        // Twice the pulse divisor halves the speed
+        module
+            .write_command(SAP::new(0, PulseDivisor::new(4)))
+            .unwrap();
+        module
+            .write_command(MVP::new(0, MoveOperation::Relative(-100_000)))
+            .unwrap();
+        interface.borrow_mut().advance(Duration::from_secs(4));
+        assert!(!reached());
+        interface.borrow_mut().advance(Duration::from_secs(3));
+        assert!(reached());
+        assert_eq!(interface.borrow().axis_parameter(0, 1), Some(0));
+    }
+
+    #[test]
+    fn limit_switches() {
+        let interface = RefCell::new(SimulatedModule::new(1, 1));
+        let module = TmcmModule::new(&interface, 1);
+        interface
+            .borrow_mut()
+            .set_limit_switches(0, Some(-1000), Some(1000));
+
+        module.write_command(ROL::new(0, 500)).unwrap();
+        interface.borrow_mut().advance(Duration::from_secs(1));
+        assert_eq!(
+            module
+                .write_command(GAP::<ActualSpeed>::new(0))
+                .map(i32::from),
+            Ok(0)
+        );
+        assert_eq!(
+            module
+                .write_command(GAP::<LeftLimitSwitchState>::new(0))
+                .map(bool::from),
+            Ok(true)
+        );
+        assert!(interface.borrow().axis_parameter(0, 1).unwrap() <= -1000);
+    }
+
+    #[test]
+    fn reference_search() {
+        let interface = RefCell::new(SimulatedModule::new(1, 1));
+        let module = TmcmModule::new(&interface, 1);
+        interface
+            .borrow_mut()
+            .set_limit_switches(0, Some(-5000), Some(20000));
+        interface.borrow_mut().set_axis_parameter(0, 1, 3000);
+
+        module
+            .write_command(SAP::new(
+                0,
+                ReferenceSearchMode::LimitSwitchSearch {
+                    search_mode: SearchMode::LeftSwitch,
+                    swap_left_right: false,
+                },
+            ))
+            .unwrap();
+        module
+            .write_command(SAP::new(0, ReferenceSwitchSpeed::new(100)))
+            .unwrap();
+        module
+            .write_command(RFS::new(0, ReferenceSearchAction::Start))
+            .unwrap();
+        interface.borrow_mut().advance(Duration::from_millis(10));
+        assert_eq!(
+            module.write_command(RFS::new(0, ReferenceSearchAction::Status)),
+            Ok(true)
+        );
+        interface.borrow_mut().advance(Duration::from_secs(2));
+        assert_eq!(
+            module.write_command(RFS::new(0, ReferenceSearchAction::Status)),
+            Ok(false)
+        );
+        assert_eq!(
+            module
+                .write_command(GAP::<ActualPosition>::new(0))
+                .map(i32::from),
+            Ok(0)
+        );
+
+        // The switching point was found within one simulation step at the reference switch speed
+        let reference = i32::from(
+            module
+                .write_command(GAP::<LastReferencePosition>::new(0))
+                .unwrap(),
+        );
+        assert!((-5000..-4995).contains(&reference));
+    }
+
+    #[test]
+    fn home_switch_search() {
+        let interface = RefCell::new(SimulatedModule::new(1, 1));
+        let module = TmcmModule::new(&interface, 1);
+        interface
+            .borrow_mut()
+            .set_limit_switches(0, Some(-50_000), Some(50_000));
+        interface
+            .borrow_mut()
+            .set_home_switch(0, Some(20_000..25_000));
+
+        // Search in negative direction, reverse at the left limit switch and stop at the home switch
+        interface.borrow_mut().set_axis_parameter(0, 193, 5);
+        module
+            .write_command(RFS::new(0, ReferenceSearchAction::Start))
+            .unwrap();
+        interface.borrow_mut().advance(Duration::from_secs(5));
+        assert_eq!(
+            module.write_command(RFS::new(0, ReferenceSearchAction::Status)),
+            Ok(false)
+        );
+        let reference = interface.borrow().axis_parameter(0, 197).unwrap();
+        assert!((19_900..20_000).contains(&reference));
+    }
+}
@@ -0,0 +1,297 @@
+//! The ramp generator and reference search of a simulated motor.
+
+use std::collections::HashMap;
+use std::ops::Range;
+
+/// The clock frequency of the motion controller, in Hz.
+const CLOCK: f64 = 16_000_000.0;
+
+/// The largest target position of `MVP`.
+pub(super) const MAX_POSITION: i32 = 1 << 23;
+
+/// The largest velocity of `ROR` and `ROL`.
+pub(super) const MAX_VELOCITY: i32 = 2047;
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+enum Mode {
+    /// Ramping to the target speed (axis parameter 2)
+    Velocity,
+    /// Ramping to the target position (axis parameter 0)
+    Position,
+    ReferenceSearch(Search),
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+enum Switch {
+    Left,
+    Right,
+    Home,
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+enum Phase {
+    /// Searching the opposite end switch first, for measuring the end switch distance
+    OppositeEnd,
+    /// Moving towards the reference switch with the reference search speed
+    Approach,
+    /// Moving off the reference switch with the reference switch speed
+    Leave,
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+struct Search {
+    phase: Phase,
+    switch: Switch,
+    /// The direction towards the reference switch, 1 or -1
+    direction: f64,
+    /// Reverse at the end switch in the search direction
+    reverse_at_end: bool,
+    invert_home: bool,
+    opposite_end: Option<f64>,
+}
+
+/// The motion state of a simulated motor.
+#[derive(Debug, Clone)]
+pub(super) struct Motion {
+    mode: Mode,
+    /// In microsteps
+    position: f64,
+    /// In the internal velocity unit of the module
+    speed: f64,
+    left_switch: Option<i32>,
+    right_switch: Option<i32>,
+    home_switch: Option<Range<i32>>,
+}
+
+/// The axis parameters of one motor.
+struct Axis<'a> {
+    motor: u8,
+    parameters: &'a mut HashMap<(u8, u8), i32>,
+}
+
+impl<'a> Axis<'a> {
+    fn get(&self, number: u8) -> i32 {
+        self.parameters[&(self.motor, number)]
+    }
+
+    fn set(&mut self, number: u8, value: i32) {
+        self.parameters.insert((self.motor, number), value);
+    }
+}
+
+impl Motion {
+    pub(super) fn new() -> Self {
+        Motion {
+            mode: Mode::Velocity,
+            position: 0.0,
+            speed: 0.0,
+            left_switch: None,
+            right_switch: None,
+            home_switch: None,
+        }
+    }
+
+    pub(super) fn set_position(&mut self, position: i32) {
+        self.position = f64::from(position);
+    }
+
+    pub(super) fn set_speed(&mut self, speed: i32) {
+        self.speed = f64::from(speed);
+    }
+
+    pub(super) fn set_limit_switches(&mut self, left: Option<i32>, right: Option<i32>) {
+        self.left_switch = left;
+        self.right_switch = right;
+    }
+
+    pub(super) fn set_home_switch(&mut self, home: Option<Range<i32>>) {
+        self.home_switch = home;
+    }
+
+    /// Rotate with the target speed, as set by `ROR`, `ROL` and `MST`.
+    pub(super) fn rotate(&mut self) {
+        self.mode = Mode::Velocity;
+    }
+
+    /// Move to the target position, as set by `MVP`.
+    pub(super) fn move_to_target(&mut self) {
+        self.mode = Mode::Position;
+    }
+
+    /// Start a reference search with the given reference search mode (axis parameter 193).
+    ///
+    /// Returns `false` if the mode is not valid.
+    pub(super) fn start_reference_search(&mut self, mode: i32) -> bool {
+        let (switch, direction, opposite_end, reverse_at_end) = match mode & 0x0f {
+            1 | 4 => (Switch::Left, -1.0, false, false),
+            2 | 3 => (Switch::Left, -1.0, true, false),
+            5 => (Switch::Home, -1.0, false, true),
+            6 => (Switch::Home, 1.0, false, true),
+            7 => (Switch::Home, 1.0, false, false),
+            8 => (Switch::Home, -1.0, false, false),
+            _ => return false,
+        };
+        let (switch, direction) = match switch {
+            Switch::Left if mode & 64 != 0 => (Switch::Right, -direction),
+            _ => (switch, direction),
+        };
+        self.mode = Mode::ReferenceSearch(Search {
+            phase: if opposite_end {
+                Phase::OppositeEnd
+            } else {
+                Phase::Approach
+            },
+            switch,
+            direction,
+            reverse_at_end,
+            invert_home: switch == Switch::Home && mode & 128 != 0,
+            opposite_end: None,
+        });
+        true
+    }
+
+    pub(super) fn reference_search_active(&self) -> bool {
+        matches!(self.mode, Mode::ReferenceSearch(_))
+    }
+
+    fn switch_active(&self, switch: Switch) -> bool {
+        let position = self.position.round() as i32;
+        match switch {
+            Switch::Left => self.left_switch.is_some_and(|left| position <= left),
+            Switch::Right => self.right_switch.is_some_and(|right| position >= right),
+            Switch::Home => self
+                .home_switch
+                .as_ref()
+                .is_some_and(|home| home.contains(&position)),
+        }
+    }
+
+    /// Advance the motion by `dt` seconds and update the axis parameters of `motor`.
+    pub(super) fn tick(&mut self, motor: u8, parameters: &mut HashMap<(u8, u8), i32>, dt: f64) {
+        let mut axis = Axis { motor, parameters };
+        let pulse_divisor = axis.get(154);
+        let ramp_divisor = axis.get(153);
+        // Microsteps per second of one velocity unit
+        let velocity_unit = CLOCK / f64::from(1 << (pulse_divisor + 16));
+        // Velocity units per second
+        let acceleration = f64::from(axis.get(5)) * CLOCK / 2f64.powi(ramp_divisor + 13);
+
+        let target_speed = match self.mode {
+            Mode::Velocity => f64::from(axis.get(2)),
+            Mode::Position => {
+                let remaining = f64::from(axis.get(0)) - self.position;
+                let step = self.speed * velocity_unit * dt;
+                if self.speed * remaining >= 0.0
+                    && step.abs() >= remaining.abs()
+                    && self.speed.abs() <= acceleration * dt
+                {
+                    self.position = f64::from(axis.get(0));
+                    self.speed = 0.0;
+                    self.update(&mut axis);
+                    return;
+                }
+                let braking_distance =
+                    self.speed * self.speed / (2.0 * acceleration) * velocity_unit;
+                if self.speed * remaining > 0.0 && braking_distance >= remaining.abs() {
+                    0.0
+                } else {
+                    remaining.signum() * f64::from(axis.get(4))
+                }
+            }
+            Mode::ReferenceSearch(search) => match self.reference_search(search, &mut axis) {
+                Some(speed) => speed,
+                None => {
+                    self.update(&mut axis);
+                    return;
+                }
+            },
+        };
+
+        let max_change = acceleration * dt;
+        self.speed += (target_speed - self.speed).max(-max_change).min(max_change);
+
+        // The end switches stop the motor, unless they are disabled or searched for
+        if !self.reference_search_active() {
+            if self.speed > 0.0 && self.switch_active(Switch::Right) && axis.get(12) == 0 {
+                self.speed = 0.0;
+            }
+            if self.speed < 0.0 && self.switch_active(Switch::Left) && axis.get(13) == 0 {
+                self.speed = 0.0;
+            }
+        }
+
+        self.position += self.speed * velocity_unit * dt;
+        self.update(&mut axis);
+    }
+
+    /// The target speed of a reference search, or `None` if it just finished.
+    fn reference_search(&mut self, mut search: Search, axis: &mut Axis) -> Option<f64> {
+        let reference_active = self.switch_active(search.switch) != search.invert_home;
+        let search_speed = f64::from(axis.get(194));
+        let switch_speed = f64::from(axis.get(195));
+
+        match search.phase {
+            Phase::OppositeEnd => {
+                let opposite = match search.switch {
+                    Switch::Left => Switch::Right,
+                    _ => Switch::Left,
+                };
+                if self.switch_active(opposite) {
+                    search.opposite_end = Some(self.position);
+                    search.phase = Phase::Approach;
+                    self.speed = 0.0;
+                }
+            }
+            Phase::Approach => {
+                let end = if search.direction > 0.0 {
+                    Switch::Right
+                } else {
+                    Switch::Left
+                };
+                if reference_active {
+                    search.phase = Phase::Leave;
+                    self.speed = 0.0;
+                } else if search.reverse_at_end && self.switch_active(end) {
+                    search.direction = -search.direction;
+                    self.speed = 0.0;
+                }
+            }
+            Phase::Leave if !reference_active => {
+                // The switching point becomes the new zero position
+                let reference = self.position.round();
+                axis.set(197, reference as i32);
+                if let Some(end) = search.opposite_end {
+                    axis.set(196, (end - reference).abs().round() as i32);
+                }
+                axis.set(0, 0);
+                self.position = 0.0;
+                self.speed = 0.0;
+                self.mode = Mode::Position;
+                return None;
+            }
+            Phase::Leave => (),
+        }
+        let speed = match search.phase {
+            Phase::OppositeEnd => -search.direction * search_speed,
+            Phase::Approach => search.direction * search_speed,
+            Phase::Leave => -search.direction * switch_speed,
+        };
+        self.mode = Mode::ReferenceSearch(search);
+        Some(speed)
+    }
+
+    /// Update the axis parameters of `motor` reflecting the motion state.
+    pub(super) fn update_parameters(&self, motor: u8, parameters: &mut HashMap<(u8, u8), i32>) {
+        self.update(&mut Axis { motor, parameters });
+    }
+
+    fn update(&self, axis: &mut Axis) {
+        let position = self.position.round() as i32;
+        axis.set(1, position);
+        axis.set(3, self.speed.round() as i32);
+        axis.set(8, (position == axis.get(0)) as i32);
+        axis.set(9, self.switch_active(Switch::Home) as i32);
+        axis.set(10, self.switch_active(Switch::Right) as i32);
+        axis.set(11, self.switch_active(Switch::Left) as i32);
+    }
+}
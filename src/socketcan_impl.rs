@@ -1,44 +1,411 @@
 use std::io;
+use std::time::{Duration, Instant};
 
-use socketcan::{CANFrame, CANSocket};
+use socketcan::{CANFilter, CANFrame, CANSocket};
 
 use Command;
 use Instruction;
 use Interface;
+use ParseError;
 use Reply;
-use Status;
+use ReplyTimeout;
+
+/// Mask for the 11 bit identifiers used by TMCL.
+const STANDARD_ID_MASK: u32 = 0x7ff;
+
+/// An `Interface` for TMCM modules on a socketcan bus.
+///
+/// Commands are sent with the module address as CAN identifier. Replies are only accepted from
+/// the configured reply identifier and when they answer the last transmitted command from the
+/// module it was sent to, all other frames are skipped. Modules reply on the same identifier by
+/// default, so the module address in the reply tells them apart. This makes it safe to use on a bus shared with other traffic. Use
+/// `set_match_command(false)` to accept the replies to every command instead, e.g. for `Bus`.
+///
+/// `CANSocket` also implements `Interface` directly. It validates the received frames,
+/// but accepts whatever reply arrives next.
+#[derive(Debug)]
+pub struct CanInterface {
+    socket: CANSocket,
+    reply_id: Option<u32>,
+    timeout: Option<Duration>,
+    match_command: bool,
+    expected: Option<(u8, u8)>,
+}
+
+/// All possible errors when communicating over socketcan.
+#[derive(Debug)]
+pub enum CanError {
+    /// The socket had an error.
+    IoError(io::Error),
+
+    /// No reply was received before the timeout expired.
+    Timeout,
+
+    /// The identifier is not a valid 11 bit CAN identifier.
+    InvalidId(u32),
+
+    /// A remote frame was received instead of a reply.
+    RemoteFrame,
+
+    /// The reply frame was not valid.
+    ParseError(ParseError),
+
+    /// A reply was requested before any command was transmitted.
+    NoCommandTransmitted,
+}
+
+impl From<io::Error> for CanError {
+    fn from(e: io::Error) -> Self {
+        match e.kind() {
+            io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut => CanError::Timeout,
+            _ => CanError::IoError(e),
+        }
+    }
+}
+
+impl CanInterface {
+    /// Create a new interface accepting replies from any CAN identifier
+    pub fn new(socket: CANSocket) -> Self {
+        CanInterface {
+            socket,
+            reply_id: None,
+            timeout: None,
+            match_command: true,
+            expected: None,
+        }
+    }
+
+    /// Create a new interface only accepting replies from `reply_id`
+    ///
+    /// This is the CAN reply ID global parameter (70) of the modules on the bus.
+    /// A filter is installed on the socket so other frames are dropped by the kernel.
+    pub fn with_reply_id(socket: CANSocket, reply_id: u32) -> Result<Self, CanError> {
+        let mut interface = CanInterface::new(socket);
+        interface.set_reply_id(reply_id)?;
+        Ok(interface)
+    }
+
+    /// Only accept replies from `reply_id`
+    pub fn set_reply_id(&mut self, reply_id: u32) -> Result<(), CanError> {
+        if reply_id > STANDARD_ID_MASK {
+            return Err(CanError::InvalidId(reply_id));
+        }
+        let filter = CANFilter::new(reply_id, STANDARD_ID_MASK)
+            .map_err(|_| CanError::InvalidId(reply_id))?;
+        self.socket.set_filter(&[filter])?;
+        self.reply_id = Some(reply_id);
+        Ok(())
+    }
+
+    /// Returns the reply identifier, if any
+    pub fn reply_id(&self) -> Option<u32> {
+        self.reply_id
+    }
+
+    /// Set how long to wait for a reply, `None` waits forever
+    pub fn set_timeout(&mut self, timeout: Option<Duration>) -> Result<(), CanError> {
+        // A zero read timeout makes the socket block forever.
+        self.socket
+            .set_read_timeout(timeout.unwrap_or(Duration::from_secs(0)))?;
+        self.timeout = timeout;
+        Ok(())
+    }
+
+    /// Returns the reply timeout
+    pub fn timeout(&self) -> Option<Duration> {
+        self.timeout
+    }
+
+    /// Only accept replies to the last transmitted command from the module it was sent to, this
+    /// is on by default
+    ///
+    /// Turn it off to receive the replies to commands to several modules in flight at the same
+    /// time, as `Bus` does. Replies from any module to any command are accepted then.
+    pub fn set_match_command(&mut self, match_command: bool) {
+        self.match_command = match_command;
+    }
+
+    /// Returns true if only replies to the last transmitted command from the module it was sent
+    /// to are accepted
+    pub fn match_command(&self) -> bool {
+        self.match_command
+    }
+
+    /// Returns a reference to the underlying socket
+    pub fn get_ref(&self) -> &CANSocket {
+        &self.socket
+    }
+
+    /// Release the underlying socket
+    pub fn release(self) -> CANSocket {
+        self.socket
+    }
+}
+
+impl Interface for CanInterface {
+    type Error = CanError;
+
+    fn transmit_command<T: Instruction>(
+        &mut self,
+        command: &Command<T>,
+    ) -> Result<(), Self::Error> {
+        self.expected = Some((command.module_address(), T::INSTRUCTION_NUMBER));
+        self.socket.transmit_command(command)
+    }
+
+    fn receive_reply(&mut self) -> Result<Reply, Self::Error> {
+        let expected = if self.match_command {
+            Some(self.expected.ok_or(CanError::NoCommandTransmitted)?)
+        } else {
+            None
+        };
+        let socket = &self.socket;
+        let reply = receive_matching(self.reply_id, expected, self.timeout, |timeout| {
+            if let Some(timeout) = timeout {
+                socket.set_read_timeout(timeout)?;
+            }
+            Ok(socket.read_frame()?)
+        })?;
+        self.expected = None;
+        Ok(reply)
+    }
+}
+
+impl ReplyTimeout for CanInterface {
+    type Timeout = Option<Duration>;
+
+    fn reply_timeout(&self) -> Option<Duration> {
+        self.timeout
+    }
+
+    fn set_reply_timeout(&mut self, timeout: Option<Duration>) -> Result<(), CanError> {
+        self.set_timeout(timeout)
+    }
+
+    fn is_timeout(error: &CanError) -> bool {
+        matches!(error, CanError::Timeout)
+    }
+}
 
 impl Interface for CANSocket {
-    type Error = io::Error;
+    type Error = CanError;
 
     fn transmit_command<T: Instruction>(
         &mut self,
         command: &Command<T>,
     ) -> Result<(), Self::Error> {
-        let frame = CANFrame::new(
-            u32::from(command.module_address),
-            &command.serialize_can(),
-            false,
-            false,
-        )
-        .unwrap();
-        self.write_frame_insist(&frame)
+        let id = u32::from(command.module_address());
+        let frame = CANFrame::new(id, &command.serialize_can(), false, false)
+            .map_err(|_| CanError::InvalidId(id))?;
+        self.write_frame_insist(&frame)?;
+        Ok(())
     }
 
     fn receive_reply(&mut self) -> Result<Reply, Self::Error> {
-        // TODO: make robust
-        let frame = self.read_frame()?;
-        Ok(Reply::new(
-            frame.id() as u8,
-            frame.data()[0],
-            Status::try_from_u8(frame.data()[1]).unwrap(),
-            frame.data()[2],
-            [
-                frame.data()[6],
-                frame.data()[5],
-                frame.data()[4],
-                frame.data()[3],
-            ],
-        ))
+        loop {
+            if let Some(reply) = reply_from_frame(&self.read_frame()?)? {
+                return Ok(reply);
+            }
+        }
+    }
+}
+
+/// Read frames with `read_frame` until a valid reply from `reply_id` arrives, sent by the module
+/// and answering the command of the `expected` module address and command number.
+///
+/// Replies from any module to any command are accepted if `expected` is `None`.
+///
+/// `read_frame` is given the time left until the timeout expires. Frames from other identifiers,
+/// frames that are not valid replies and replies from other modules or to other commands are
+/// skipped.
+fn receive_matching<F>(
+    reply_id: Option<u32>,
+    expected: Option<(u8, u8)>,
+    timeout: Option<Duration>,
+    mut read_frame: F,
+) -> Result<Reply, CanError>
+where
+    F: FnMut(Option<Duration>) -> Result<CANFrame, CanError>,
+{
+    let deadline = timeout.map(|timeout| Instant::now() + timeout);
+
+    loop {
+        let remaining = match deadline {
+            Some(deadline) => {
+                let now = Instant::now();
+                if now >= deadline {
+                    return Err(CanError::Timeout);
+                }
+                Some((deadline - now).max(Duration::from_millis(1)))
+            }
+            None => None,
+        };
+
+        let frame = read_frame(remaining)?;
+        if reply_id.is_some_and(|id| id != frame.id()) {
+            continue;
+        }
+        if let Ok(Some(reply)) = reply_from_frame(&frame) {
+            if expected.is_none_or(|(module_address, command_number)| {
+                reply.module_address() == module_address && reply.command_number() == command_number
+            }) {
+                return Ok(reply);
+            }
+        }
+    }
+}
+
+/// Validate a received frame, returns `None` for error frames which should be skipped.
+fn reply_from_frame(frame: &CANFrame) -> Result<Option<Reply>, CanError> {
+    if frame.is_error() {
+        return Ok(None);
+    }
+    if frame.is_rtr() {
+        return Err(CanError::RemoteFrame);
+    }
+    if frame.is_extended() {
+        return Err(CanError::InvalidId(frame.id()));
+    }
+    Reply::deserialize_can(frame.id() as u16, frame.data())
+        .map(Some)
+        .map_err(CanError::ParseError)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::collections::VecDeque;
+    use std::thread;
+
+    use ErrStatus;
+    use Status;
+
+    fn frame(id: u32, data: &[u8]) -> CANFrame {
+        CANFrame::new(id, data, false, false).unwrap()
+    }
+
+    /// Read from `frames`, timing out when there are none left
+    fn read_from(
+        frames: Vec<CANFrame>,
+    ) -> impl FnMut(Option<Duration>) -> Result<CANFrame, CanError> {
+        let mut frames = VecDeque::from(frames);
+        move |_| frames.pop_front().ok_or(CanError::Timeout)
+    }
+
+    #[test]
+    fn valid_frame() {
+        let frame = CANFrame::new(2, &[1, 4, 5, 0, 0, 0, 0], false, false).unwrap();
+        let reply = reply_from_frame(&frame).unwrap().unwrap();
+        assert_eq!(reply.reply_address(), 2);
+        assert_eq!(reply.status(), Status::Err(ErrStatus::InvalidValue));
+    }
+
+    #[test]
+    fn short_frame() {
+        let frame = CANFrame::new(2, &[1, 100, 5], false, false).unwrap();
+        match reply_from_frame(&frame) {
+            Err(CanError::ParseError(ParseError::ShortFrame)) => (),
+            other => panic!("expected short frame, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn remote_frame() {
+        let frame = CANFrame::new(2, &[], true, false).unwrap();
+        match reply_from_frame(&frame) {
+            Err(CanError::RemoteFrame) => (),
+            other => panic!("expected remote frame, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn extended_frame() {
+        let frame = frame(0x1000, &[1, 100, 5, 0, 0, 0, 0]);
+        match reply_from_frame(&frame) {
+            Err(CanError::InvalidId(0x1000)) => (),
+            other => panic!("expected invalid id, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn reply_id_filter() {
+        let frames = vec![
+            frame(5, &[1, 100, 6, 0, 0, 0, 1]),
+            frame(0x402, &[1, 100]),
+            CANFrame::new(0x402, &[], true, false).unwrap(),
+            frame(0x402, &[1, 100, 6, 0, 0, 0, 2]),
+        ];
+        let reply = receive_matching(Some(0x402), Some((1, 6)), None, read_from(frames)).unwrap();
+        assert_eq!(reply.reply_address(), 0x402);
+        assert_eq!(reply.operand(), [2, 0, 0, 0]);
+    }
+
+    #[test]
+    fn skip_other_commands() {
+        let frames = vec![
+            frame(7, &[1, 100]),
+            frame(2, &[1, 100, 5, 0, 0, 0, 1]),
+            frame(3, &[1, 100, 6, 0, 0, 0, 2]),
+        ];
+        let reply = receive_matching(None, Some((1, 6)), None, read_from(frames)).unwrap();
+        assert_eq!(reply.reply_address(), 3);
+        assert_eq!(reply.command_number(), 6);
+
+        let frames = vec![frame(2, &[1, 100, 5, 0, 0, 0, 1])];
+        match receive_matching(None, Some((1, 6)), None, read_from(frames)) {
+            Err(CanError::Timeout) => (),
+            other => panic!("expected timeout, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn skip_other_modules() {
+        let frames = vec![
+            frame(2, &[3, 100, 6, 0, 0, 0, 1]),
+            frame(2, &[1, 100, 6, 0, 0, 0, 2]),
+        ];
+        let reply = receive_matching(Some(2), Some((1, 6)), None, read_from(frames)).unwrap();
+        assert_eq!(reply.module_address(), 1);
+        assert_eq!(reply.operand(), [2, 0, 0, 0]);
+
+        let frames = vec![frame(2, &[3, 100, 6, 0, 0, 0, 1])];
+        match receive_matching(Some(2), Some((1, 6)), None, read_from(frames)) {
+            Err(CanError::Timeout) => (),
+            other => panic!("expected timeout, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn any_command() {
+        let frames = vec![
+            frame(2, &[1, 100]),
+            frame(2, &[3, 100, 5, 0, 0, 0, 1]),
+            frame(2, &[1, 100, 6, 0, 0, 0, 2]),
+        ];
+        let mut read_frame = read_from(frames);
+        let reply = receive_matching(None, None, None, &mut read_frame).unwrap();
+        assert_eq!(reply.module_address(), 3);
+        let reply = receive_matching(None, None, None, &mut read_frame).unwrap();
+        assert_eq!(reply.module_address(), 1);
+    }
+
+    #[test]
+    fn timeout() {
+        let timeout = Duration::from_millis(20);
+        let start = Instant::now();
+        let mut reads = 0;
+        let result = receive_matching(Some(2), Some((1, 6)), Some(timeout), |remaining| {
+            assert!(remaining.unwrap() <= timeout);
+            reads += 1;
+            thread::sleep(Duration::from_millis(2));
+            Ok(frame(5, &[1, 100, 6, 0, 0, 0, 0]))
+        });
+        match result {
+            Err(CanError::Timeout) => (),
+            other => panic!("expected timeout, got {:?}", other),
+        }
+        assert!(start.elapsed() >= timeout);
+        assert!(reads > 1);
     }
 }